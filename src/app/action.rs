@@ -1,6 +1,7 @@
+use super::types::QueueMove;
 use derive_more::Display;
 
-#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
 pub enum Action {
     #[display("Quit")]
     Quit,
@@ -14,8 +15,20 @@ pub enum Action {
     PrevTorrent,
     #[display("Switch to Tab {}", _0)]
     SwitchTab(u8),
+    #[display("Highlight Torrent {}", _0)]
+    Highlight(usize),
+    #[display("Select Torrent {}", _0)]
+    SelectRow(usize),
+    #[display("Sort by Column {}", _0)]
+    SortBy(usize),
     #[display("Toggle Help")]
     ToggleHelp,
+    #[display("Toggle Notifications")]
+    ToggleNotifications,
+    #[display("Toggle Speed Graphs")]
+    ToggleGraphs,
+    #[display("Toggle Session Stats")]
+    ToggleStats,
     #[display("Toggle Torrent")]
     ToggleTorrent,
     #[display("Toggle All Torrents")]
@@ -30,6 +43,56 @@ pub enum Action {
     Delete(bool),
     #[display("Rename Torrent")]
     Rename,
+    #[display("Add Torrent")]
+    Add,
+    #[display("Open Details")]
+    OpenDetails,
+    #[display("Close Details")]
+    CloseDetails,
+    #[display("Next Pane")]
+    NextPane,
+    #[display("Previous Pane")]
+    PrevPane,
+    #[display("Toggle File Wanted")]
+    ToggleFileWanted,
+    #[display("Cycle File Priority")]
+    CycleFilePriority,
+    #[display("Select Files")]
+    SelectFiles,
+    #[display("Start Torrent(-s) Now")]
+    StartNow,
+    #[display("Verify Torrent(-s)")]
+    Verify,
+    #[display("Reannounce Torrent(-s)")]
+    Reannounce,
+    #[display("Move Torrent(-s) in Queue: {}", _0)]
+    Queue(QueueMove),
+    #[display("Sort by Next Column")]
+    SortColumn,
+    #[display("Reverse Sort")]
+    SortReverse,
+    #[display("Toggle Alternative Speed Limits")]
+    ToggleAltSpeed,
+    #[display("Set Global Speed Limits")]
+    SessionLimits,
+    #[display("Set Torrent Speed Limits")]
+    TorrentLimits,
+    #[display("Set Torrent Seeding Options")]
+    TorrentOptions,
+    #[display("Edit Trackers")]
+    Trackers,
+    #[display("Edit Labels")]
+    EditLabels,
+    #[display("Toggle Label Sidebar")]
+    ToggleLabels,
+    #[display("Next Label")]
+    NextLabel,
+    #[display("Previous Label")]
+    PrevLabel,
+    #[display("Filter by Label {}", _0)]
+    FilterLabel(usize),
+    #[display("Open Settings")]
+    Settings,
     #[display("Filter")]
     Filter,
     #[display("Clear Filter")]
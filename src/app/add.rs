@@ -0,0 +1,174 @@
+use super::{input::InputHandler, types::AddOptions};
+use crate::error::Result;
+use transmission_rpc::types::Priority;
+
+/// Field of the add-torrent dialog that currently has focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddField {
+    #[default]
+    Source,
+    DownloadDir,
+    Labels,
+    Paused,
+    Priority,
+}
+
+impl AddField {
+    pub const ALL: [Self; 5] = [
+        Self::Source,
+        Self::DownloadDir,
+        Self::Labels,
+        Self::Paused,
+        Self::Priority,
+    ];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Source => "Magnet/URL/file",
+            Self::DownloadDir => "Download dir",
+            Self::Labels => "Labels",
+            Self::Paused => "Start paused",
+            Self::Priority => "Priority",
+        }
+    }
+
+    const fn position(self) -> usize {
+        match self {
+            Self::Source => 0,
+            Self::DownloadDir => 1,
+            Self::Labels => 2,
+            Self::Paused => 3,
+            Self::Priority => 4,
+        }
+    }
+}
+
+/// State of the add-torrent dialog.
+#[derive(Debug, Default)]
+pub struct AddTorrentForm {
+    pub focus: AddField,
+    pub source: InputHandler,
+    pub download_dir: InputHandler,
+    pub labels: InputHandler,
+    pub paused: bool,
+    pub priority: Option<Priority>,
+}
+
+impl AddTorrentForm {
+    /// Moves focus to the next field, wrapping around.
+    pub const fn next_field(&mut self) {
+        let idx = (self.focus.position() + 1) % AddField::ALL.len();
+        self.focus = AddField::ALL[idx];
+    }
+
+    /// Moves focus to the previous field, wrapping around.
+    pub fn prev_field(&mut self) {
+        let idx = self
+            .focus
+            .position()
+            .checked_sub(1)
+            .unwrap_or(AddField::ALL.len() - 1);
+        self.focus = AddField::ALL[idx];
+    }
+
+    /// Returns the text input of the focused field, if it is a text field.
+    pub const fn focused_input_mut(&mut self) -> Option<&mut InputHandler> {
+        match self.focus {
+            AddField::Source => Some(&mut self.source),
+            AddField::DownloadDir => Some(&mut self.download_dir),
+            AddField::Labels => Some(&mut self.labels),
+            AddField::Paused | AddField::Priority => None,
+        }
+    }
+
+    /// Toggles or cycles the focused option field. `forward` selects the
+    /// direction priorities are cycled in.
+    pub fn cycle(&mut self, forward: bool) {
+        match self.focus {
+            AddField::Paused => self.paused = !self.paused,
+            AddField::Priority => {
+                const ORDER: [Priority; 3] = [Priority::Low, Priority::Normal, Priority::High];
+                let current = self.priority.unwrap_or(Priority::Normal);
+                let idx = ORDER.iter().position(|&p| p == current).unwrap_or(1);
+                let step = if forward { 1 } else { ORDER.len() - 1 };
+                self.priority = Some(ORDER[(idx + step) % ORDER.len()]);
+            }
+            AddField::Source | AddField::DownloadDir | AddField::Labels => {}
+        }
+    }
+
+    /// Completes the focused path field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory being completed cannot be read.
+    pub async fn complete(&mut self) -> Result<()> {
+        match self.focus {
+            AddField::Source => self.source.complete().await,
+            AddField::DownloadDir => self.download_dir.complete().await,
+            AddField::Labels | AddField::Paused | AddField::Priority => Ok(()),
+        }
+    }
+
+    /// Returns the options entered in the dialog.
+    #[must_use]
+    pub fn options(&self) -> AddOptions {
+        let download_dir = self.download_dir.text.trim();
+        AddOptions {
+            download_dir: (!download_dir.is_empty()).then(|| download_dir.to_owned()),
+            paused: self.paused,
+            labels: self
+                .labels
+                .text
+                .split(',')
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            priority: self.priority,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AddField, AddTorrentForm};
+    use transmission_rpc::types::Priority;
+
+    #[test]
+    fn focus_wraps_in_both_directions() {
+        let mut form = AddTorrentForm::default();
+        form.prev_field();
+        assert_eq!(form.focus, AddField::Priority);
+        form.next_field();
+        assert_eq!(form.focus, AddField::Source);
+    }
+
+    #[test]
+    fn priority_cycles_through_low_normal_high() {
+        let mut form = AddTorrentForm {
+            focus: AddField::Priority,
+            ..AddTorrentForm::default()
+        };
+        form.cycle(true);
+        assert_eq!(form.priority, Some(Priority::High));
+        form.cycle(true);
+        assert_eq!(form.priority, Some(Priority::Low));
+        form.cycle(false);
+        assert_eq!(form.priority, Some(Priority::High));
+    }
+
+    #[test]
+    fn options_split_labels_and_skip_empty_dir() {
+        let mut form = AddTorrentForm::default();
+        form.labels.set_text("tv, hd,,".into());
+        let options = form.options();
+        assert_eq!(options.labels, vec!["tv".to_string(), "hd".to_string()]);
+        assert_eq!(options.download_dir, None);
+    }
+}
@@ -1,80 +1,83 @@
-use super::{Torrents, types::Selected};
-use crate::error::Result;
-use std::{collections::HashSet, path::Path};
-use transmission_rpc::types::{Id, Torrent, TorrentAction, TorrentStatus};
+use super::{
+    Torrents,
+    labels::LabelChange,
+    options::OptionsChange,
+    settings::{DaemonSettings, SettingChange, set_arguments},
+    trackers::TrackerChange,
+    types::{AddOptions, FileChange, LimitChange, QueueMove, Selected, SessionStats, SpeedLimits},
+};
+use crate::error::{Result, TraxorError};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::de::IgnoredAny;
+use serde_json::{Map, Value, json};
+use std::path::Path;
+use tokio::fs;
+use transmission_rpc::types::{
+    Id, Priority, RpcResponse, RpcResponseArgument, SessionSetArgs, Torrent, TorrentAction,
+    TorrentAddArgs, TorrentAddedOrDuplicate, TorrentSetArgs,
+};
+use url::Url;
 
 impl Torrents {
-    /// Toggle selected torrents between started and stopped states.
+    /// Start torrents, respecting the download queue.
     ///
     /// # Errors
     ///
     /// Returns an error if the RPC call fails.
-    pub async fn toggle(&mut self, ids: Selected) -> Result<()> {
-        let selected: HashSet<_> = ids.into();
-
-        let (to_start, to_stop): (Vec<_>, Vec<_>) = self
-            .torrents
-            .iter()
-            .filter_map(|t| {
-                t.id.filter(|id| selected.contains(id))
-                    .map(|id| (Id::Id(id), t.status))
-            })
-            .partition(|(_, status)| matches!(status, Some(TorrentStatus::Stopped)));
+    pub async fn start(&mut self, ids: Selected) -> Result<()> {
+        self.action(ids, TorrentAction::Start).await
+    }
 
-        if !to_start.is_empty() {
-            let ids: Vec<_> = to_start.into_iter().map(|(id, _)| id).collect();
-            self.client
-                .torrent_action(TorrentAction::Start, ids)
-                .await?;
-        }
-        if !to_stop.is_empty() {
-            let ids: Vec<_> = to_stop.into_iter().map(|(id, _)| id).collect();
-            self.client.torrent_action(TorrentAction::Stop, ids).await?;
-        }
-        Ok(())
+    /// Stop torrents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn stop(&mut self, ids: Selected) -> Result<()> {
+        self.action(ids, TorrentAction::Stop).await
     }
 
-    /// Toggle all torrents between started and stopped states.
+    /// Start torrents immediately, bypassing the download queue.
     ///
     /// # Errors
     ///
     /// Returns an error if the RPC call fails.
-    pub async fn toggle_all(&mut self) -> Result<()> {
-        let (to_start, to_stop): (Vec<_>, Vec<_>) = self
-            .torrents
-            .iter()
-            .filter_map(|t| t.id().map(|id| (id, t.status)))
-            .partition(|(_, status)| matches!(status, Some(TorrentStatus::Stopped)));
+    pub async fn start_now(&mut self, ids: Selected) -> Result<()> {
+        self.action(ids, TorrentAction::StartNow).await
+    }
 
-        if !to_start.is_empty() {
-            let ids: Vec<_> = to_start.into_iter().map(|(id, _)| id).collect();
-            self.client
-                .torrent_action(TorrentAction::StartNow, ids)
-                .await?;
-        }
-        if !to_stop.is_empty() {
-            let ids: Vec<_> = to_stop.into_iter().map(|(id, _)| id).collect();
-            self.client.torrent_action(TorrentAction::Stop, ids).await?;
-        }
-        Ok(())
+    /// Verify the local data of torrents against their piece hashes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn verify(&mut self, ids: Selected) -> Result<()> {
+        self.action(ids, TorrentAction::Verify).await
     }
 
-    /// Start all torrents immediately.
+    /// Ask the trackers of torrents for more peers right away.
     ///
     /// # Errors
     ///
     /// Returns an error if the RPC call fails.
-    pub async fn start_all(&mut self) -> Result<()> {
-        self.action_all(TorrentAction::StartNow).await
+    pub async fn reannounce(&mut self, ids: Selected) -> Result<()> {
+        self.action(ids, TorrentAction::Reannounce).await
     }
 
-    /// Stop all torrents.
+    /// Move torrents within the queue.
     ///
     /// # Errors
     ///
     /// Returns an error if the RPC call fails.
-    pub async fn stop_all(&mut self) -> Result<()> {
-        self.action_all(TorrentAction::Stop).await
+    pub async fn queue_move(&mut self, ids: Selected, direction: QueueMove) -> Result<()> {
+        let ids: Vec<i64> = ids.into();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.rpc
+            .call::<IgnoredAny>(direction.method(), json!({ "ids": ids }))
+            .await?;
+        Ok(())
     }
 
     /// Move a torrent to a new location.
@@ -94,6 +97,7 @@ impl Torrents {
         self.client
             .torrent_set_location(vec![id], location.to_string_lossy().into_owned(), move_from)
             .await?;
+        self.invalidate();
         Ok(())
     }
 
@@ -122,6 +126,7 @@ impl Torrents {
         self.client
             .torrent_set_location(ids, location.to_owned(), Some(true))
             .await?;
+        self.invalidate();
         Ok(())
     }
 
@@ -141,14 +146,345 @@ impl Torrents {
                 name.to_string_lossy().into_owned(),
             )
             .await?;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Add a torrent from a magnet link, an URL or a local `.torrent` file.
+    ///
+    /// Local files are read and sent as base64 metainfo, so the daemon does not
+    /// need access to the local filesystem. Returns the name of the added torrent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, the RPC call fails or the
+    /// daemon rejects the torrent.
+    pub async fn add(&mut self, source: &str, options: AddOptions) -> Result<String> {
+        let source = source.trim();
+        let mut args = TorrentAddArgs {
+            download_dir: options.download_dir,
+            paused: Some(options.paused),
+            bandwidth_priority: options.priority,
+            labels: (!options.labels.is_empty()).then_some(options.labels),
+            ..TorrentAddArgs::default()
+        };
+        if is_remote_source(source) {
+            args.filename = Some(source.to_owned());
+        } else {
+            let metainfo = fs::read(source).await?;
+            args.metainfo = Some(STANDARD.encode(metainfo));
+        }
+
+        let response = self.client.torrent_add(args).await?;
+        if !response.is_ok() {
+            return Err(TraxorError::TransmissionRpc(response.result));
+        }
+        match response.arguments {
+            TorrentAddedOrDuplicate::TorrentAdded(torrent) => {
+                Ok(torrent.name.unwrap_or_else(|| source.to_owned()))
+            }
+            TorrentAddedOrDuplicate::TorrentDuplicate(torrent) => {
+                Err(TraxorError::TransmissionRpc(format!(
+                    "torrent already added: {}",
+                    torrent.name.unwrap_or_else(|| source.to_owned())
+                )))
+            }
+            TorrentAddedOrDuplicate::Error => Err(TraxorError::TransmissionRpc(
+                "unexpected torrent-add response".into(),
+            )),
+        }
+    }
+
+    /// Mark files of a torrent as wanted/unwanted or change their priority.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn set_files(
+        &mut self,
+        id: i64,
+        files: Vec<usize>,
+        change: FileChange,
+    ) -> Result<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+        let args = match change {
+            FileChange::Wanted(true) => TorrentSetArgs::new().files_wanted(files),
+            FileChange::Wanted(false) => TorrentSetArgs::new().files_unwanted(files),
+            FileChange::Priority(Priority::High) => TorrentSetArgs::new().priority_high(files),
+            FileChange::Priority(Priority::Normal) => TorrentSetArgs::new().priority_normal(files),
+            FileChange::Priority(Priority::Low) => TorrentSetArgs::new().priority_low(files),
+        };
+        self.client
+            .torrent_set(args, Some(vec![Id::Id(id)]))
+            .await?;
+        Ok(())
+    }
+
+    /// Fetch the global and alternative speed limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn speed_limits(&mut self) -> Result<SpeedLimits> {
+        self.rpc
+            .call("session-get", json!({ "fields": SpeedLimits::FIELDS }))
+            .await
+    }
+
+    /// Fetch the session and cumulative transfer totals along with the free
+    /// space in the default download directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an RPC call fails.
+    pub async fn session_stats(&mut self) -> Result<SessionStats> {
+        let totals = self.client.session_stats().await?.arguments;
+        let download_dir = self.client.session_get().await?.arguments.download_dir;
+        let free = self
+            .client
+            .free_space(download_dir.clone())
+            .await?
+            .arguments;
+        Ok(SessionStats {
+            totals: Box::new(totals),
+            download_dir,
+            free_space: free.size_bytes,
+        })
+    }
+
+    /// Fetch the daemon settings shown on the settings screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn settings(&mut self) -> Result<DaemonSettings> {
+        let response: Map<String, Value> = self
+            .rpc
+            .call("session-get", json!({ "fields": DaemonSettings::keys() }))
+            .await?;
+        Ok(DaemonSettings::from_response(&response))
+    }
+
+    /// Apply changes made on the settings screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn set_settings(&mut self, changes: &[SettingChange]) -> Result<()> {
+        self.rpc
+            .call::<IgnoredAny>("session-set", set_arguments(changes))
+            .await?;
+        Ok(())
+    }
+
+    /// Turn alternative speed limits ("turtle mode") on or off.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn set_alt_speed(&mut self, enabled: bool) -> Result<()> {
+        let args = SessionSetArgs {
+            alt_speed_enabled: Some(enabled),
+            ..SessionSetArgs::default()
+        };
+        check(self.client.session_set(args).await?)
+    }
+
+    /// Set the global download and upload limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn set_session_limits(&mut self, change: LimitChange) -> Result<()> {
+        let to_rpc = |limit: u32| i32::try_from(limit).unwrap_or(i32::MAX);
+        let args = SessionSetArgs {
+            speed_limit_down_enabled: Some(change.down.is_some()),
+            speed_limit_down: change.down.map(to_rpc),
+            speed_limit_up_enabled: Some(change.up.is_some()),
+            speed_limit_up: change.up.map(to_rpc),
+            ..SessionSetArgs::default()
+        };
+        check(self.client.session_set(args).await?)
+    }
+
+    /// Set per-torrent download and upload limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn set_torrent_limits(&mut self, ids: Selected, change: LimitChange) -> Result<()> {
+        let ids: Vec<Id> = ids.into();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let to_rpc = |limit: u32| usize::try_from(limit).unwrap_or(usize::MAX);
+        let mut args = TorrentSetArgs::new();
+        args.download_limited = Some(change.down.is_some());
+        args.download_limit = change.down.map(to_rpc);
+        args.upload_limited = Some(change.up.is_some());
+        args.upload_limit = change.up.map(to_rpc);
+        args.honors_session_limits = Some(change.honors_session_limits);
+        check(self.client.torrent_set(args, Some(ids)).await?)?;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Set seeding limits and bandwidth priority of torrents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn set_torrent_options(
+        &mut self,
+        ids: Selected,
+        change: OptionsChange,
+    ) -> Result<()> {
+        let ids: Vec<Id> = ids.into();
+        if ids.is_empty() || change.is_empty() {
+            return Ok(());
+        }
+        let mut args = TorrentSetArgs::new();
+        args.seed_ratio_mode = change.ratio_mode;
+        args.seed_ratio_limit = change.ratio_limit;
+        args.seed_idle_mode = change.idle_mode;
+        args.seed_idle_limit = change.idle_limit;
+        args.bandwidth_priority = change.priority;
+        check(self.client.torrent_set(args, Some(ids)).await?)?;
+        self.invalidate();
+        Ok(())
+    }
+
+    /// Replace the labels of torrents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an RPC call fails.
+    pub async fn set_labels(&mut self, changes: &[LabelChange]) -> Result<()> {
+        for change in changes {
+            let mut args = TorrentSetArgs::new();
+            args.labels = Some(change.labels.clone());
+            let ids = change.ids.iter().copied().map(Id::Id).collect();
+            check(self.client.torrent_set(args, Some(ids)).await?)?;
+        }
+        self.invalidate();
         Ok(())
     }
 
-    async fn action_all(&mut self, action: TorrentAction) -> Result<()> {
-        let ids: Vec<_> = self.torrents.iter().filter_map(Torrent::id).collect();
-        if !ids.is_empty() {
-            self.client.torrent_action(action, ids).await?;
+    /// Add, remove and replace the trackers of torrents. Every torrent is
+    /// tried, even after one fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TraxorError::PartialFailure`] naming the torrents that were
+    /// and were not updated if an RPC call fails.
+    pub async fn edit_trackers(&mut self, changes: &[TrackerChange]) -> Result<()> {
+        let mut applied = Vec::new();
+        let mut failed = Vec::new();
+        for change in changes {
+            let mut arguments = Map::new();
+            arguments.insert("ids".into(), json!([change.id]));
+            if !change.add.is_empty() {
+                arguments.insert("trackerAdd".into(), json!(change.add));
+            }
+            if !change.remove.is_empty() {
+                let ids: Vec<i64> = change.remove.iter().map(|t| t.id).collect();
+                arguments.insert("trackerRemove".into(), json!(ids));
+            }
+            if !change.replace.is_empty() {
+                // Flat list of id, URL pairs.
+                let pairs: Vec<Value> = change
+                    .replace
+                    .iter()
+                    .flat_map(|(tracker, announce)| [json!(tracker.id), json!(announce)])
+                    .collect();
+                arguments.insert("trackerReplace".into(), Value::Array(pairs));
+            }
+            match self
+                .rpc
+                .call::<IgnoredAny>("torrent-set", Value::Object(arguments))
+                .await
+            {
+                Ok(_) => applied.push(change.name.clone()),
+                Err(err) => failed.push((change.name.clone(), err.to_string())),
+            }
+        }
+        if !applied.is_empty() {
+            self.invalidate();
+        }
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(TraxorError::PartialFailure { applied, failed })
+        }
+    }
+
+    async fn action(&mut self, ids: Selected, action: TorrentAction) -> Result<()> {
+        let ids: Vec<Id> = ids.into();
+        if ids.is_empty() {
+            return Ok(());
         }
+        check(self.client.torrent_action(action, ids).await?)
+    }
+}
+
+/// Turns a daemon-reported failure into an error.
+fn check<T: RpcResponseArgument>(response: RpcResponse<T>) -> Result<()> {
+    if response.is_ok() {
         Ok(())
+    } else {
+        Err(TraxorError::TransmissionRpc(response.result))
+    }
+}
+
+/// Returns `true` if `source` should be fetched by the daemon rather than read locally.
+fn is_remote_source(source: &str) -> bool {
+    source.starts_with("magnet:")
+        || Url::parse(source).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_remote_source;
+    use crate::{
+        app::{Connection, Torrents, trackers::TrackerChange},
+        config::connection::ConnectionConfig,
+        error::TraxorError,
+    };
+
+    #[test]
+    fn magnets_and_urls_are_remote() {
+        assert!(is_remote_source("magnet:?xt=urn:btih:abc"));
+        assert!(is_remote_source("https://example.com/file.torrent"));
+        assert!(!is_remote_source("/home/user/file.torrent"));
+        assert!(!is_remote_source("file.torrent"));
+    }
+
+    #[tokio::test]
+    async fn tracker_edits_report_every_failed_torrent() {
+        let connection = ConnectionConfig {
+            url: "http://127.0.0.1:9/transmission/rpc".into(),
+            ..ConnectionConfig::default()
+        };
+        let Ok(connection) = Connection::resolve(&connection) else {
+            panic!("connection should resolve");
+        };
+        let Ok(mut torrents) = Torrents::new(&connection) else {
+            panic!("client should build");
+        };
+        let change = |id: i64| TrackerChange {
+            id,
+            name: format!("t{id}"),
+            add: vec!["https://new.example/announce".into()],
+            ..TrackerChange::default()
+        };
+        let err = torrents.edit_trackers(&[change(1), change(2)]).await;
+        let Err(TraxorError::PartialFailure { applied, failed }) = err else {
+            panic!("edit should fail for every torrent: {err:?}");
+        };
+        assert!(applied.is_empty());
+        let names: Vec<&str> = failed.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["t1", "t2"]);
     }
 }
@@ -1,6 +1,12 @@
 pub const DEFAULT_TICK_RATE_MS: u64 = 250;
 pub const TORRENT_UPDATE_INTERVAL_SECS: u64 = 2;
+/// Session stats and free space change slowly; fetch them every few updates.
+pub const SESSION_STATS_INTERVAL_SECS: u64 = 10;
+pub const RECONNECT_MAX_BACKOFF_SECS: u64 = 60;
+/// Incremental polls between full torrent listings.
+pub const FULL_REFRESH_EVERY_POLLS: u32 = 30;
 pub const DEFAULT_RPC_URL: &str = "http://localhost:9091/transmission/rpc";
+pub const DEFAULT_RPC_TIMEOUT_SECS: u64 = 10;
 
 pub const HELP_POPUP_HEIGHT: u16 = 15;
 pub const INPUT_WIDTH_DIVISOR: u16 = 4;
@@ -0,0 +1,354 @@
+use derive_more::Display;
+use ratatui::widgets::TableState;
+use std::collections::HashSet;
+use transmission_rpc::types::{Priority, Torrent, TorrentGetField};
+
+/// Fields listed in the General pane, in display order.
+pub const GENERAL_FIELDS: [TorrentGetField; 24] = [
+    TorrentGetField::Name,
+    TorrentGetField::Id,
+    TorrentGetField::HashString,
+    TorrentGetField::Status,
+    TorrentGetField::PercentDone,
+    TorrentGetField::TotalSize,
+    TorrentGetField::SizeWhenDone,
+    TorrentGetField::LeftUntilDone,
+    TorrentGetField::DownloadedEver,
+    TorrentGetField::UploadedEver,
+    TorrentGetField::UploadRatio,
+    TorrentGetField::RateDownload,
+    TorrentGetField::RateUpload,
+    TorrentGetField::Eta,
+    TorrentGetField::PeersConnected,
+    TorrentGetField::DownloadDir,
+    TorrentGetField::AddedDate,
+    TorrentGetField::DoneDate,
+    TorrentGetField::ActivityDate,
+    TorrentGetField::IsPrivate,
+    TorrentGetField::Labels,
+    TorrentGetField::ErrorString,
+    TorrentGetField::Comment,
+    TorrentGetField::Creator,
+];
+
+/// Fields fetched only for the torrent shown in the detail view.
+const PANE_FIELDS: [TorrentGetField; 5] = [
+    // Shown next to the status while verifying.
+    TorrentGetField::RecheckProgress,
+    TorrentGetField::Files,
+    TorrentGetField::FileStats,
+    TorrentGetField::Peers,
+    TorrentGetField::TrackerStats,
+];
+
+/// Pane of the torrent detail view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display)]
+pub enum DetailPane {
+    #[default]
+    General,
+    Files,
+    Peers,
+    Trackers,
+}
+
+impl DetailPane {
+    pub const ALL: [Self; 4] = [Self::General, Self::Files, Self::Peers, Self::Trackers];
+
+    const fn position(self) -> usize {
+        match self {
+            Self::General => 0,
+            Self::Files => 1,
+            Self::Peers => 2,
+            Self::Trackers => 3,
+        }
+    }
+}
+
+/// Detail view of a single torrent.
+#[derive(Debug)]
+pub struct DetailView {
+    id: i64,
+    pub torrent: Option<Torrent>,
+    pub pane: DetailPane,
+    pub state: TableState,
+    /// Indices of files selected in the Files pane.
+    pub selected_files: HashSet<usize>,
+}
+
+impl DetailView {
+    #[must_use]
+    pub fn new(id: i64) -> Self {
+        Self {
+            id,
+            torrent: None,
+            pane: DetailPane::default(),
+            state: TableState::default().with_selected(Some(0)),
+            selected_files: HashSet::new(),
+        }
+    }
+
+    /// Returns the id of the shown torrent.
+    #[must_use]
+    pub const fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Returns every field the detail view needs.
+    #[must_use]
+    pub fn fields() -> Vec<TorrentGetField> {
+        GENERAL_FIELDS.iter().chain(&PANE_FIELDS).copied().collect()
+    }
+
+    /// Switches to the next pane, wrapping around.
+    pub const fn next_pane(&mut self) {
+        let idx = (self.pane.position() + 1) % DetailPane::ALL.len();
+        self.switch_pane(DetailPane::ALL[idx]);
+    }
+
+    /// Switches to the previous pane, wrapping around.
+    pub fn prev_pane(&mut self) {
+        let idx = self
+            .pane
+            .position()
+            .checked_sub(1)
+            .unwrap_or(DetailPane::ALL.len() - 1);
+        self.switch_pane(DetailPane::ALL[idx]);
+    }
+
+    const fn switch_pane(&mut self, pane: DetailPane) {
+        self.pane = pane;
+        self.state.select(Some(0));
+    }
+
+    /// Number of rows in the active pane.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let Some(torrent) = &self.torrent else {
+            return 0;
+        };
+        match self.pane {
+            DetailPane::General => GENERAL_FIELDS.len(),
+            DetailPane::Files => torrent.files.as_ref().map_or(0, Vec::len),
+            DetailPane::Peers => torrent.peers.as_ref().map_or(0, Vec::len),
+            DetailPane::Trackers => torrent.tracker_stats.as_ref().map_or(0, Vec::len),
+        }
+    }
+
+    /// Returns `true` if the active pane has no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn next(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = self
+            .state
+            .selected()
+            .map_or(0, |i| if i >= len - 1 { 0 } else { i + 1 });
+        self.state.select(Some(i));
+    }
+
+    pub fn previous(&mut self) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        let i = self
+            .state
+            .selected()
+            .map_or(0, |i| if i == 0 { len - 1 } else { i - 1 });
+        self.state.select(Some(i));
+    }
+
+    /// Replaces the shown torrent data, keeping the row and file selections in range.
+    pub fn set_torrent(&mut self, torrent: Option<Torrent>) {
+        self.torrent = torrent;
+        let len = self.len();
+        if self.state.selected().is_some_and(|i| i >= len) {
+            self.state.select(len.checked_sub(1));
+        }
+        let file_count = self.file_names().len();
+        self.selected_files.retain(|&idx| idx < file_count);
+    }
+
+    fn file_names(&self) -> Vec<&str> {
+        self.torrent
+            .as_ref()
+            .and_then(|t| t.files.as_deref())
+            .unwrap_or_default()
+            .iter()
+            .map(|file| file.name.as_str())
+            .collect()
+    }
+
+    /// Toggles selection of the highlighted file and moves to the next one.
+    pub fn toggle_file_selection(&mut self) {
+        if self.pane != DetailPane::Files {
+            return;
+        }
+        if let Some(idx) = self.state.selected().filter(|&idx| idx < self.len()) {
+            if !self.selected_files.remove(&idx) {
+                self.selected_files.insert(idx);
+            }
+            self.next();
+        }
+    }
+
+    /// Selects every file, or clears the selection if all files are selected.
+    pub fn toggle_all_files(&mut self) {
+        if self.pane != DetailPane::Files {
+            return;
+        }
+        let count = self.file_names().len();
+        if self.selected_files.len() == count {
+            self.selected_files.clear();
+        } else {
+            self.selected_files = (0..count).collect();
+        }
+    }
+
+    /// Adds every file whose path matches one of the whitespace-separated
+    /// `patterns` to the selection. Returns the number of matching files.
+    ///
+    /// Patterns containing `*` or `?` are globs matched against the whole path;
+    /// anything else matches as a substring. Matching ignores case.
+    pub fn select_matching_files(&mut self, patterns: &str) -> usize {
+        let patterns = patterns
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>();
+        let matching = self
+            .file_names()
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| {
+                let name = name.to_lowercase();
+                patterns
+                    .iter()
+                    .any(|pattern| matches_pattern(&name, pattern))
+            })
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        let count = matching.len();
+        self.selected_files.extend(matching);
+        count
+    }
+
+    /// Returns the files an edit applies to: the selected files, or the
+    /// highlighted file when nothing is selected.
+    #[must_use]
+    pub fn target_files(&self) -> Vec<usize> {
+        if self.selected_files.is_empty() {
+            return self
+                .state
+                .selected()
+                .filter(|&idx| self.pane == DetailPane::Files && idx < self.len())
+                .into_iter()
+                .collect();
+        }
+        let mut files = self.selected_files.iter().copied().collect::<Vec<_>>();
+        files.sort_unstable();
+        files
+    }
+
+    /// Returns `true` if every file in `files` is currently wanted.
+    #[must_use]
+    pub fn all_wanted(&self, files: &[usize]) -> bool {
+        let stats = self
+            .torrent
+            .as_ref()
+            .and_then(|t| t.file_stats.as_deref())
+            .unwrap_or_default();
+        files
+            .iter()
+            .all(|&idx| stats.get(idx).is_none_or(|stat| stat.wanted))
+    }
+
+    /// Returns the priority following that of the first file in `files`,
+    /// cycling Low, Normal, High.
+    #[must_use]
+    pub fn next_priority(&self, files: &[usize]) -> Priority {
+        let current = files.first().and_then(|&idx| {
+            self.torrent
+                .as_ref()
+                .and_then(|t| t.file_stats.as_deref())
+                .and_then(|stats| stats.get(idx))
+                .map(|stat| stat.priority)
+        });
+        match current {
+            Some(Priority::Low) => Priority::Normal,
+            Some(Priority::Normal) | None => Priority::High,
+            Some(Priority::High) => Priority::Low,
+        }
+    }
+}
+
+/// Glob match supporting `*` and `?`, or a substring match when `pattern`
+/// contains neither. Expects both sides lowercased, so matching ignores case.
+fn matches_pattern(name: &str, pattern: &str) -> bool {
+    if !pattern.contains(['*', '?']) {
+        return name.contains(pattern);
+    }
+    let name = name.chars().collect::<Vec<_>>();
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let (mut n, mut p) = (0, 0);
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                n += 1;
+                p += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DetailPane, DetailView, matches_pattern};
+
+    #[test]
+    fn panes_wrap_and_reset_selection() {
+        let mut detail = DetailView::new(1);
+        detail.state.select(Some(3));
+        detail.prev_pane();
+        assert_eq!(detail.pane, DetailPane::Trackers);
+        assert_eq!(detail.state.selected(), Some(0));
+        detail.next_pane();
+        assert_eq!(detail.pane, DetailPane::General);
+    }
+
+    #[test]
+    fn navigation_without_data_is_a_no_op() {
+        let mut detail = DetailView::new(1);
+        detail.next();
+        detail.previous();
+        assert!(detail.is_empty());
+        assert_eq!(detail.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn patterns_match_globs_and_substrings() {
+        assert!(matches_pattern("show/s01e01.nfo", "*.nfo"));
+        assert!(!matches_pattern("show/s01e01.mkv", "*.nfo"));
+        assert!(matches_pattern("show/sample/s01e01.mkv", "sample"));
+        assert!(matches_pattern("show/s01e01.mkv", "show/s0?e01.*"));
+    }
+}
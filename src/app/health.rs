@@ -0,0 +1,153 @@
+use super::constants::{RECONNECT_MAX_BACKOFF_SECS, TORRENT_UPDATE_INTERVAL_SECS};
+use crate::error::TraxorError;
+use std::time::{Duration, Instant};
+
+/// Consecutive failures after which the daemon is considered unreachable
+/// rather than having a transient hiccup.
+const UNREACHABLE_AFTER: u32 = 3;
+
+/// State of the connection to the daemon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectionState {
+    /// No poll has finished yet.
+    #[default]
+    Connecting,
+    Connected,
+    /// The last few polls failed; retrying with backoff.
+    Retrying,
+    /// The daemon rejected the credentials.
+    Unauthorized,
+    /// Polls keep failing; still retrying with backoff.
+    Unreachable,
+}
+
+impl ConnectionState {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Connecting => "connecting",
+            Self::Connected => "connected",
+            Self::Retrying => "retrying",
+            Self::Unauthorized => "unauthorized",
+            Self::Unreachable => "unreachable",
+        }
+    }
+}
+
+/// Tracks whether the daemon answers and when to poll it next.
+///
+/// Failed polls are retried with exponential backoff, starting at the regular
+/// update interval and capped at [`RECONNECT_MAX_BACKOFF_SECS`].
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    state: ConnectionState,
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl ConnectionHealth {
+    #[must_use]
+    pub const fn state(&self) -> ConnectionState {
+        self.state
+    }
+
+    /// Returns `true` once the latest poll succeeded.
+    #[must_use]
+    pub const fn is_connected(&self) -> bool {
+        matches!(self.state, ConnectionState::Connected)
+    }
+
+    /// Returns `true` unless the latest request failed, including before the
+    /// first poll finishes.
+    #[must_use]
+    pub const fn is_usable(&self) -> bool {
+        matches!(
+            self.state,
+            ConnectionState::Connecting | ConnectionState::Connected
+        )
+    }
+
+    /// Returns `true` if the daemon should be polled at `now`.
+    #[must_use]
+    pub fn should_poll(&self, now: Instant) -> bool {
+        self.retry_at.is_none_or(|at| now >= at)
+    }
+
+    /// Time left until the next retry, if one is scheduled.
+    #[must_use]
+    pub fn retry_in(&self, now: Instant) -> Option<Duration> {
+        self.retry_at.map(|at| at.saturating_duration_since(now))
+    }
+
+    pub const fn record_success(&mut self) {
+        self.state = ConnectionState::Connected;
+        self.failures = 0;
+        self.retry_at = None;
+    }
+
+    /// Records a failed request and schedules the next poll.
+    pub fn record_failure(&mut self, error: &TraxorError, now: Instant) {
+        self.failures = self.failures.saturating_add(1);
+        self.state = match error {
+            TraxorError::Unauthorized => ConnectionState::Unauthorized,
+            _ if self.failures >= UNREACHABLE_AFTER => ConnectionState::Unreachable,
+            _ => ConnectionState::Retrying,
+        };
+        self.retry_at = Some(now + backoff(self.failures));
+    }
+}
+
+/// Delay before the next attempt after `failures` consecutive failures.
+fn backoff(failures: u32) -> Duration {
+    let exponent = failures.saturating_sub(1).min(16);
+    let secs = TORRENT_UPDATE_INTERVAL_SECS.saturating_mul(1 << exponent);
+    Duration::from_secs(secs.min(RECONNECT_MAX_BACKOFF_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectionHealth, ConnectionState, backoff};
+    use crate::{
+        app::constants::{RECONNECT_MAX_BACKOFF_SECS, TORRENT_UPDATE_INTERVAL_SECS},
+        error::TraxorError,
+    };
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let base = TORRENT_UPDATE_INTERVAL_SECS;
+        assert_eq!(backoff(1), Duration::from_secs(base));
+        assert_eq!(backoff(2), Duration::from_secs(base * 2));
+        assert_eq!(backoff(3), Duration::from_secs(base * 4));
+        assert_eq!(
+            backoff(u32::MAX),
+            Duration::from_secs(RECONNECT_MAX_BACKOFF_SECS)
+        );
+    }
+
+    #[test]
+    fn failures_move_from_retrying_to_unreachable_until_success() {
+        let mut health = ConnectionHealth::default();
+        let now = Instant::now();
+        assert!(health.should_poll(now));
+        assert!(health.is_usable());
+
+        let refused = TraxorError::TransmissionRpc("connection refused".into());
+        health.record_failure(&refused, now);
+        assert_eq!(health.state(), ConnectionState::Retrying);
+        assert!(!health.is_usable());
+        assert!(!health.should_poll(now));
+        assert!(health.should_poll(now + backoff(1)));
+
+        health.record_failure(&refused, now);
+        health.record_failure(&refused, now);
+        assert_eq!(health.state(), ConnectionState::Unreachable);
+
+        health.record_failure(&TraxorError::Unauthorized, now);
+        assert_eq!(health.state(), ConnectionState::Unauthorized);
+
+        health.record_success();
+        assert!(health.is_connected());
+        assert!(health.should_poll(now));
+    }
+}
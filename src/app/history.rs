@@ -0,0 +1,162 @@
+use super::store::TorrentStore;
+use crate::config::graph::GraphConfig;
+use std::collections::{HashMap, VecDeque};
+use transmission_rpc::types::TorrentGetField;
+
+/// Fields the graphs draw the highlighted torrent's speed limits from.
+const LIMIT_FIELDS: [TorrentGetField; 4] = [
+    TorrentGetField::DownloadLimit,
+    TorrentGetField::DownloadLimited,
+    TorrentGetField::UploadLimit,
+    TorrentGetField::UploadLimited,
+];
+
+/// Transfer rates reported by one update, in bytes per second.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sample {
+    pub down: u64,
+    pub up: u64,
+}
+
+/// Ring buffer of the most recent samples, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Samples(VecDeque<Sample>);
+
+impl Samples {
+    fn push(&mut self, sample: Sample, capacity: usize) {
+        while self.0.len() >= capacity {
+            self.0.pop_front();
+        }
+        self.0.push_back(sample);
+    }
+
+    /// Returns the newest sample.
+    #[must_use]
+    pub fn last(&self) -> Option<Sample> {
+        self.0.back().copied()
+    }
+
+    /// Download rates of the newest `count` samples, oldest first.
+    #[must_use]
+    pub fn down(&self, count: usize) -> Vec<u64> {
+        self.newest(count).map(|sample| sample.down).collect()
+    }
+
+    /// Upload rates of the newest `count` samples, oldest first.
+    #[must_use]
+    pub fn up(&self, count: usize) -> Vec<u64> {
+        self.newest(count).map(|sample| sample.up).collect()
+    }
+
+    fn newest(&self, count: usize) -> impl Iterator<Item = &Sample> {
+        self.0.iter().skip(self.0.len().saturating_sub(count))
+    }
+}
+
+/// Speed samples of the whole session and of every torrent, taken after each
+/// update.
+#[derive(Debug, Clone)]
+pub struct SpeedHistory {
+    capacity: usize,
+    /// Whether the graphs are shown.
+    pub visible: bool,
+    session: Samples,
+    torrents: HashMap<i64, Samples>,
+}
+
+impl SpeedHistory {
+    /// Fields the graphs need while they are shown.
+    #[must_use]
+    pub fn fields(&self) -> Vec<TorrentGetField> {
+        if self.visible {
+            LIMIT_FIELDS.to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    #[must_use]
+    pub fn new(config: &GraphConfig) -> Self {
+        Self {
+            capacity: config.history.max(1),
+            visible: config.visible,
+            session: Samples::default(),
+            torrents: HashMap::new(),
+        }
+    }
+
+    /// Samples the current rates. Torrents that are gone lose their history.
+    pub fn record(&mut self, torrents: &TorrentStore) {
+        let mut session = Sample::default();
+        for torrent in torrents {
+            let sample = Sample {
+                down: torrent.rate_download.unwrap_or_default().unsigned_abs(),
+                up: torrent.rate_upload.unwrap_or_default().unsigned_abs(),
+            };
+            session.down += sample.down;
+            session.up += sample.up;
+            if let Some(id) = torrent.id {
+                self.torrents
+                    .entry(id)
+                    .or_default()
+                    .push(sample, self.capacity);
+            }
+        }
+        self.session.push(session, self.capacity);
+        self.torrents.retain(|&id, _| torrents.get(id).is_some());
+    }
+
+    /// Summed rates of all torrents.
+    #[must_use]
+    pub const fn session(&self) -> &Samples {
+        &self.session
+    }
+
+    /// Rates of the torrent with the given id.
+    #[must_use]
+    pub fn torrent(&self, id: i64) -> Option<&Samples> {
+        self.torrents.get(&id)
+    }
+
+    pub const fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Sample, SpeedHistory};
+    use crate::app::test_utils::from_json;
+    use crate::{app::store::TorrentStore, config::graph::GraphConfig};
+    use serde_json::json;
+    use transmission_rpc::types::Torrent;
+
+    fn torrent(id: i64, down: i64, up: i64) -> Torrent {
+        from_json(json!({
+            "id": id,
+            "rateDownload": down,
+            "rateUpload": up,
+        }))
+    }
+
+    #[test]
+    fn keeps_the_newest_samples_and_forgets_removed_torrents() {
+        let mut history = SpeedHistory::new(&GraphConfig {
+            history: 3,
+            visible: false,
+        });
+        for rate in 1..=4 {
+            history.record(&TorrentStore::from(vec![
+                torrent(1, rate, 0),
+                torrent(2, 10, rate),
+            ]));
+        }
+        assert_eq!(history.session().down(10), [12, 13, 14]);
+        assert_eq!(history.session().up(2), [3, 4]);
+        assert_eq!(history.torrent(2).map(|s| s.up(10)), Some(vec![2, 3, 4]));
+
+        history.record(&TorrentStore::from(vec![torrent(2, 0, 0)]));
+        assert!(history.torrent(1).is_none());
+        assert_eq!(history.session().last(), Some(Sample { down: 0, up: 0 }));
+    }
+}
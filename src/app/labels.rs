@@ -0,0 +1,371 @@
+use super::{input::InputHandler, store::TorrentStore};
+use ratatui::widgets::ListState;
+use std::collections::BTreeMap;
+use transmission_rpc::types::Torrent;
+
+/// Labels to set on torrents that end up with the same list. The daemon
+/// replaces the whole list, so edits are sent per resulting list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelChange {
+    pub ids: Vec<i64>,
+    pub labels: Vec<String>,
+}
+
+/// Labels added to and removed from every edited torrent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelEdit {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+impl LabelEdit {
+    /// Parses comma-separated labels against those all edited torrents
+    /// share: shared labels left out are removed, new ones are added and
+    /// `-label` removes a label from every torrent that has it.
+    #[must_use]
+    pub fn parse(text: &str, shared: &[String]) -> Self {
+        let mut kept = Vec::new();
+        let mut remove = Vec::new();
+        for token in text.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            match token.strip_prefix('-').map(str::trim) {
+                Some("") => {}
+                Some(label) => push_unique(&mut remove, label),
+                None => push_unique(&mut kept, token),
+            }
+        }
+        let add = kept
+            .iter()
+            .filter(|label| !shared.contains(label) && !remove.contains(label))
+            .cloned()
+            .collect();
+        for label in shared.iter().filter(|label| !kept.contains(label)) {
+            push_unique(&mut remove, label);
+        }
+        Self { add, remove }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty()
+    }
+
+    /// Labels of a torrent after the edit, existing ones first.
+    #[must_use]
+    pub fn apply(&self, labels: &[String]) -> Vec<String> {
+        let mut result: Vec<String> = labels
+            .iter()
+            .filter(|label| !self.remove.contains(label))
+            .cloned()
+            .collect();
+        for label in &self.add {
+            push_unique(&mut result, label);
+        }
+        result
+    }
+
+    /// Groups the torrents whose labels change by their new labels.
+    pub fn changes<'a>(&self, torrents: impl IntoIterator<Item = &'a Torrent>) -> Vec<LabelChange> {
+        let mut groups: BTreeMap<Vec<String>, Vec<i64>> = BTreeMap::new();
+        for torrent in torrents {
+            let Some(id) = torrent.id else { continue };
+            let labels = torrent.labels.as_deref().unwrap_or_default();
+            let updated = self.apply(labels);
+            if updated != labels {
+                groups.entry(updated).or_default().push(id);
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(labels, ids)| LabelChange { ids, labels })
+            .collect()
+    }
+}
+
+fn push_unique(labels: &mut Vec<String>, label: &str) {
+    if !labels.iter().any(|l| l == label) {
+        labels.push(label.to_owned());
+    }
+}
+
+/// Every label in use with the number of torrents carrying it.
+#[must_use]
+pub fn label_counts(torrents: &TorrentStore) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for label in torrents.iter().flat_map(|t| t.labels.iter().flatten()) {
+        *counts.entry(label.clone()).or_default() += 1;
+    }
+    counts
+}
+
+/// Edit-labels popup for the selected torrents.
+#[derive(Debug, Default)]
+pub struct LabelsForm {
+    pub input: InputHandler,
+    ids: Vec<i64>,
+    /// Labels every edited torrent has, prefilled into the input.
+    shared: Vec<String>,
+    /// Labels in use, offered as completions.
+    known: Vec<String>,
+    completions: Vec<String>,
+    completion_idx: usize,
+    /// Input right after the last completion; Tab on it cycles instead.
+    completed: String,
+}
+
+impl LabelsForm {
+    #[must_use]
+    pub fn new(torrents: &[&Torrent], known: Vec<String>) -> Self {
+        let labels = |t: &Torrent| t.labels.clone().unwrap_or_default();
+        let mut shared = torrents.first().map(|&t| labels(t)).unwrap_or_default();
+        for torrent in torrents.iter().skip(1) {
+            let other = labels(torrent);
+            shared.retain(|label| other.contains(label));
+        }
+        let mut input = InputHandler::new();
+        input.set_text(shared.join(", "));
+        Self {
+            input,
+            ids: torrents.iter().filter_map(|t| t.id).collect(),
+            shared,
+            known,
+            ..Self::default()
+        }
+    }
+
+    /// Ids of the edited torrents.
+    #[must_use]
+    pub fn ids(&self) -> &[i64] {
+        &self.ids
+    }
+
+    /// The edit the input describes.
+    #[must_use]
+    pub fn edit(&self) -> LabelEdit {
+        LabelEdit::parse(&self.input.text, &self.shared)
+    }
+
+    /// Labels in use that start with the label being typed, ignoring case,
+    /// except those already typed.
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<&str> {
+        let (head, token) = self.split();
+        let prefix = token
+            .trim()
+            .trim_start_matches('-')
+            .trim_start()
+            .to_lowercase();
+        let typed: Vec<&str> = head.split(',').map(str::trim).collect();
+        self.known
+            .iter()
+            .filter(|label| label.to_lowercase().starts_with(&prefix))
+            .filter(|label| !typed.contains(&label.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Completes the label being typed, cycling through the matches on
+    /// repeated presses.
+    pub fn complete(&mut self) {
+        if !self.completions.is_empty() && self.input.text == self.completed {
+            self.completion_idx = (self.completion_idx + 1) % self.completions.len();
+        } else {
+            self.completions = self.suggestions().into_iter().map(str::to_owned).collect();
+            self.completion_idx = 0;
+        }
+        let Some(label) = self.completions.get(self.completion_idx) else {
+            return;
+        };
+        let (head, token) = self.split();
+        let sign = if token.trim_start().starts_with('-') {
+            "-"
+        } else {
+            ""
+        };
+        let text = if head.is_empty() {
+            format!("{sign}{label}")
+        } else {
+            format!("{head} {sign}{label}")
+        };
+        self.input.set_text(text.clone());
+        self.completed = text;
+    }
+
+    /// Input up to and including the last comma, and the label after it.
+    fn split(&self) -> (&str, &str) {
+        let text = self.input.text.as_str();
+        text.rfind(',')
+            .map_or(("", text), |idx| text.split_at(idx + 1))
+    }
+}
+
+/// Torrents the label sidebar lets through.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LabelFilter {
+    #[default]
+    All,
+    Label(String),
+    Unlabeled,
+}
+
+impl LabelFilter {
+    #[must_use]
+    pub fn matches(&self, torrent: &Torrent) -> bool {
+        let labels = torrent.labels.as_deref().unwrap_or_default();
+        match self {
+            Self::All => true,
+            Self::Label(label) => labels.contains(label),
+            Self::Unlabeled => labels.is_empty(),
+        }
+    }
+}
+
+/// Sidebar listing the labels in use; the highlighted one filters the
+/// torrent list.
+#[derive(Debug, Default)]
+pub struct LabelSidebar {
+    pub visible: bool,
+    pub filter: LabelFilter,
+    /// Scroll position of the rendered list.
+    pub state: ListState,
+}
+
+impl LabelSidebar {
+    /// Entries with their torrent counts: all torrents, each label, then
+    /// torrents without labels.
+    #[must_use]
+    pub fn entries(torrents: &TorrentStore) -> Vec<(LabelFilter, usize)> {
+        let unlabeled = torrents
+            .iter()
+            .filter(|t| LabelFilter::Unlabeled.matches(t))
+            .count();
+        std::iter::once((LabelFilter::All, torrents.len()))
+            .chain(
+                label_counts(torrents)
+                    .into_iter()
+                    .map(|(label, count)| (LabelFilter::Label(label), count)),
+            )
+            .chain(std::iter::once((LabelFilter::Unlabeled, unlabeled)))
+            .collect()
+    }
+
+    /// Position of the active filter among the entries.
+    #[must_use]
+    pub fn position(&self, entries: &[(LabelFilter, usize)]) -> Option<usize> {
+        entries
+            .iter()
+            .position(|(filter, _)| *filter == self.filter)
+    }
+
+    /// Filters by the entry at `idx`. Returns `true` if the filter changed.
+    pub fn select(&mut self, idx: usize, torrents: &TorrentStore) -> bool {
+        match Self::entries(torrents).into_iter().nth(idx) {
+            Some((filter, _)) if filter != self.filter => {
+                self.filter = filter;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Filters by the next or previous entry, wrapping around.
+    pub fn cycle(&mut self, forward: bool, torrents: &TorrentStore) {
+        let entries = Self::entries(torrents);
+        let len = entries.len();
+        let idx = match self.position(&entries) {
+            Some(idx) if forward => (idx + 1) % len,
+            Some(idx) => (idx + len - 1) % len,
+            None => 0,
+        };
+        self.select(idx, torrents);
+    }
+
+    /// Shows or hides the sidebar. Hiding it stops filtering by label.
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+        if !self.visible {
+            self.filter = LabelFilter::All;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LabelEdit, LabelFilter, LabelSidebar, LabelsForm};
+    use crate::app::store::TorrentStore;
+    use crate::app::test_utils::from_json;
+    use serde_json::json;
+    use transmission_rpc::types::Torrent;
+
+    fn torrent(id: i64, labels: &[&str]) -> Torrent {
+        from_json(json!({ "id": id, "labels": labels }))
+    }
+
+    fn strings(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|&l| l.to_owned()).collect()
+    }
+
+    #[test]
+    fn edit_adds_new_labels_and_removes_dropped_shared_ones() {
+        let shared = strings(&["tv", "hd"]);
+        let edit = LabelEdit::parse("tv, anime,, -old ,", &shared);
+        assert_eq!(edit.add, ["anime"]);
+        assert_eq!(edit.remove, ["old", "hd"]);
+
+        assert_eq!(
+            edit.apply(&strings(&["hd", "old", "tv", "x"])),
+            ["tv", "x", "anime"]
+        );
+        assert!(LabelEdit::parse("hd, tv", &shared).is_empty());
+    }
+
+    #[test]
+    fn changes_group_torrents_by_their_new_labels() {
+        let torrents = [torrent(1, &["tv"]), torrent(2, &[]), torrent(3, &["hd"])];
+        let edit = LabelEdit::parse("tv", &[]);
+        let changes = edit.changes(&torrents);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].ids, [3]);
+        assert_eq!(changes[0].labels, ["hd", "tv"]);
+        assert_eq!(changes[1].ids, [2]);
+    }
+
+    #[test]
+    fn form_prefills_shared_labels_and_completes_known_ones() {
+        let (a, b) = (torrent(1, &["tv", "hd"]), torrent(2, &["hd", "x"]));
+        let mut form = LabelsForm::new(&[&a, &b], strings(&["Movies", "hd", "music"]));
+        assert_eq!(form.input.text, "hd");
+        assert_eq!(form.ids(), [1, 2]);
+
+        form.input.set_text("hd, -m".into());
+        assert_eq!(form.suggestions(), ["Movies", "music"]);
+        form.complete();
+        assert_eq!(form.input.text, "hd, -Movies");
+        form.complete();
+        assert_eq!(form.input.text, "hd, -music");
+        assert_eq!(form.edit().remove, ["music"]);
+    }
+
+    #[test]
+    fn sidebar_cycles_through_labels_and_resets_when_hidden() {
+        let store = TorrentStore::from(vec![
+            torrent(1, &["tv"]),
+            torrent(2, &["hd", "tv"]),
+            torrent(3, &[]),
+        ]);
+        let entries = LabelSidebar::entries(&store);
+        let counts: Vec<usize> = entries.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, [3, 1, 2, 1]);
+
+        let mut sidebar = LabelSidebar::default();
+        sidebar.toggle();
+        sidebar.cycle(false, &store);
+        assert_eq!(sidebar.filter, LabelFilter::Unlabeled);
+        sidebar.cycle(true, &store);
+        sidebar.cycle(true, &store);
+        assert_eq!(sidebar.filter, LabelFilter::Label("hd".into()));
+        assert!(!sidebar.select(1, &store));
+
+        sidebar.toggle();
+        assert_eq!(sidebar.filter, LabelFilter::All);
+    }
+}
@@ -0,0 +1,79 @@
+use ratatui::layout::{Position, Rect};
+
+/// Screen areas that react to mouse clicks, recorded while rendering.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClickAreas {
+    /// Tab titles, including their padding.
+    pub tabs: Vec<Rect>,
+    /// Column headers of the torrent list.
+    pub headers: Vec<Rect>,
+    /// Rows of the torrent list, below the header.
+    pub rows: Rect,
+    /// Entries of the label sidebar, if shown.
+    pub labels: Rect,
+    /// The popup drawn on top, if any.
+    pub popup: Option<Rect>,
+}
+
+/// What a click landed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickTarget {
+    Tab(usize),
+    /// Column index within the active tab.
+    Header(usize),
+    /// Row index counted from the first visible row.
+    Row(usize),
+    /// Label sidebar entry counted from the first visible one.
+    Label(usize),
+}
+
+impl ClickAreas {
+    /// Returns what lies at `position`, ignoring any popup.
+    #[must_use]
+    pub fn target(&self, position: Position) -> Option<ClickTarget> {
+        if let Some(idx) = self.tabs.iter().position(|tab| tab.contains(position)) {
+            return Some(ClickTarget::Tab(idx));
+        }
+        if let Some(idx) = self.headers.iter().position(|col| col.contains(position)) {
+            return Some(ClickTarget::Header(idx));
+        }
+        if self.labels.contains(position) {
+            return Some(ClickTarget::Label(usize::from(position.y - self.labels.y)));
+        }
+        self.rows
+            .contains(position)
+            .then(|| ClickTarget::Row(usize::from(position.y - self.rows.y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClickAreas, ClickTarget};
+    use ratatui::layout::{Position, Rect};
+
+    #[test]
+    fn target_finds_tabs_headers_rows_and_labels() {
+        let areas = ClickAreas {
+            tabs: vec![Rect::new(1, 1, 5, 1), Rect::new(7, 1, 6, 1)],
+            headers: vec![Rect::new(3, 4, 10, 1), Rect::new(14, 4, 8, 1)],
+            rows: Rect::new(1, 6, 40, 10),
+            labels: Rect::new(42, 6, 10, 10),
+            popup: None,
+        };
+        assert_eq!(areas.target(Position::new(8, 1)), Some(ClickTarget::Tab(1)));
+        assert_eq!(
+            areas.target(Position::new(15, 4)),
+            Some(ClickTarget::Header(1))
+        );
+        assert_eq!(
+            areas.target(Position::new(20, 8)),
+            Some(ClickTarget::Row(2))
+        );
+        assert_eq!(
+            areas.target(Position::new(45, 9)),
+            Some(ClickTarget::Label(3))
+        );
+        assert_eq!(areas.target(Position::new(13, 4)), None);
+        assert_eq!(areas.target(Position::new(20, 16)), None);
+    }
+}
@@ -0,0 +1,199 @@
+use super::{input::InputHandler, types::LimitChange};
+use transmission_rpc::types::TorrentGetField;
+
+/// Fields the limits dialog prefills a torrent's limits from.
+pub const FIELDS: [TorrentGetField; 6] = [
+    TorrentGetField::Id,
+    TorrentGetField::DownloadLimit,
+    TorrentGetField::DownloadLimited,
+    TorrentGetField::UploadLimit,
+    TorrentGetField::UploadLimited,
+    TorrentGetField::HonorsSessionLimits,
+];
+
+/// What the speed limits dialog applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimitTarget {
+    /// Global limits set with `session-set`.
+    #[default]
+    Session,
+    /// Per-torrent limits of the selected torrents.
+    Torrents,
+}
+
+/// Field of the speed limits dialog that currently has focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimitField {
+    #[default]
+    Down,
+    Up,
+    HonorsSession,
+}
+
+impl LimitField {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Down => "Download (kB/s)",
+            Self::Up => "Upload (kB/s)",
+            Self::HonorsSession => "Global limits",
+        }
+    }
+}
+
+/// State of the speed limits dialog.
+#[derive(Debug, Default)]
+pub struct LimitsForm {
+    pub target: LimitTarget,
+    pub focus: LimitField,
+    pub down: InputHandler,
+    pub up: InputHandler,
+    pub honors_session_limits: bool,
+    /// Whether the limits to prefill are still being loaded.
+    pub loading: bool,
+    /// Validation error of the last submit.
+    pub error: Option<String>,
+}
+
+impl LimitsForm {
+    /// Creates a dialog prefilled with the current limits; `None` is unlimited.
+    #[must_use]
+    pub fn new(
+        target: LimitTarget,
+        down: Option<u64>,
+        up: Option<u64>,
+        honors_session_limits: bool,
+    ) -> Self {
+        let mut form = Self {
+            target,
+            honors_session_limits,
+            ..Self::default()
+        };
+        form.down
+            .set_text(down.map(|v| v.to_string()).unwrap_or_default());
+        form.up
+            .set_text(up.map(|v| v.to_string()).unwrap_or_default());
+        form
+    }
+
+    /// Fields shown for the dialog's target.
+    #[must_use]
+    pub const fn fields(&self) -> &'static [LimitField] {
+        match self.target {
+            LimitTarget::Session => &[LimitField::Down, LimitField::Up],
+            LimitTarget::Torrents => &[LimitField::Down, LimitField::Up, LimitField::HonorsSession],
+        }
+    }
+
+    fn position(&self) -> usize {
+        self.fields()
+            .iter()
+            .position(|&field| field == self.focus)
+            .unwrap_or_default()
+    }
+
+    /// Moves focus to the next field, wrapping around.
+    pub fn next_field(&mut self) {
+        let fields = self.fields();
+        self.focus = fields[(self.position() + 1) % fields.len()];
+    }
+
+    /// Moves focus to the previous field, wrapping around.
+    pub fn prev_field(&mut self) {
+        let fields = self.fields();
+        let idx = self.position().checked_sub(1).unwrap_or(fields.len() - 1);
+        self.focus = fields[idx];
+    }
+
+    /// Creates a dialog waiting for the limits to prefill.
+    #[must_use]
+    pub fn loading(target: LimitTarget) -> Self {
+        Self {
+            target,
+            loading: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the text input of the focused field, if it is a text field.
+    pub const fn focused_input_mut(&mut self) -> Option<&mut InputHandler> {
+        if self.loading {
+            return None;
+        }
+        match self.focus {
+            LimitField::Down => Some(&mut self.down),
+            LimitField::Up => Some(&mut self.up),
+            LimitField::HonorsSession => None,
+        }
+    }
+
+    /// Toggles the focused checkbox field.
+    pub const fn toggle(&mut self) {
+        if matches!(self.focus, LimitField::HonorsSession) {
+            self.honors_session_limits = !self.honors_session_limits;
+        }
+    }
+
+    /// Parses the entered limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the field whose value is not a whole number.
+    pub fn change(&self) -> Result<LimitChange, String> {
+        Ok(LimitChange {
+            down: parse_limit(LimitField::Down, &self.down.text)?,
+            up: parse_limit(LimitField::Up, &self.up.text)?,
+            honors_session_limits: self.honors_session_limits,
+        })
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Parses a limit in kB/s; blank, `-` or `unlimited` mean no limit.
+fn parse_limit(field: LimitField, text: &str) -> Result<Option<u32>, String> {
+    let text = text.trim();
+    if text.is_empty() || text == "-" || text.eq_ignore_ascii_case("unlimited") {
+        return Ok(None);
+    }
+    text.parse().map(Some).map_err(|_| {
+        format!(
+            "{}: expected a whole number or blank for unlimited",
+            field.label()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LimitField, LimitTarget, LimitsForm};
+
+    #[test]
+    fn session_dialog_skips_torrent_only_fields() {
+        let mut form = LimitsForm::new(LimitTarget::Session, None, None, true);
+        form.next_field();
+        form.next_field();
+        assert_eq!(form.focus, LimitField::Down);
+
+        let mut form = LimitsForm::new(LimitTarget::Torrents, None, None, true);
+        form.prev_field();
+        assert_eq!(form.focus, LimitField::HonorsSession);
+    }
+
+    #[test]
+    fn blank_means_unlimited_and_text_is_rejected() {
+        let mut form = LimitsForm::new(LimitTarget::Session, Some(500), None, true);
+        let Ok(change) = form.change() else {
+            panic!("prefilled limits should parse");
+        };
+        assert_eq!((change.down, change.up), (Some(500), None));
+
+        form.up.set_text("fast".into());
+        let Err(err) = form.change() else {
+            panic!("non-numeric limit should be rejected");
+        };
+        assert!(err.starts_with("Upload"));
+    }
+}
@@ -1,20 +1,62 @@
 pub mod action;
+mod add;
 mod command;
 pub mod constants;
+pub mod detail;
+pub mod health;
+pub mod history;
 mod input;
+pub mod labels;
+pub mod layout;
+mod limits;
+pub mod notification;
+pub mod options;
+pub mod query;
+mod rpc;
+pub mod rules;
+pub mod settings;
+pub mod sort;
+pub mod stats;
+pub mod store;
 mod tab;
+#[cfg(test)]
+pub(crate) mod test_utils;
 mod torrent;
+pub mod trackers;
 pub mod types;
 pub mod utils;
+pub mod worker;
 
 use crate::error::Result;
-use crate::{app::input::InputHandler, config::Config};
+use crate::{
+    app::input::InputHandler,
+    config::{Config, rules::RuleAction},
+};
+use detail::DetailView;
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
+use health::{ConnectionHealth, ConnectionState};
+use history::SpeedHistory;
+use labels::{LabelEdit, LabelSidebar, LabelsForm, label_counts};
+use layout::ClickAreas;
+use notification::Notifications;
+use options::{OptionsChange, OptionsForm};
+use query::{Query, QueryError};
 use ratatui::widgets::TableState;
-use std::path::PathBuf;
-use transmission_rpc::types::Torrent;
-use types::Selected;
-pub use {tab::Tab, torrent::Torrents};
+use rules::{Firing, Rules};
+use settings::SettingsForm;
+use stats::StatsView;
+use std::{collections::HashSet, path::PathBuf, time::Instant};
+use store::TorrentList;
+use trackers::TrackersForm;
+use transmission_rpc::types::{Torrent, TorrentGetField, TorrentStatus};
+use types::{FileChange, QueueMove, Selected, SpeedLimits};
+use worker::{Job, Jobs, Outcome, Request};
+pub use {
+    add::{AddField, AddTorrentForm},
+    limits::{LimitField, LimitTarget, LimitsForm},
+    tab::Tab,
+    torrent::{Connection, Torrents},
+};
 
 /// Input mode type for the application.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -24,45 +66,125 @@ pub enum InputMode {
     Move,
     Rename,
     Filter,
+    /// Pattern input selecting files in the detail view.
+    SelectFiles,
+    /// Add-torrent dialog.
+    Add,
+    /// Speed limits dialog.
+    Limits,
+    /// Edit-labels popup.
+    Labels,
+    /// Tracker editing dialog.
+    Trackers,
+    /// Seeding limits and bandwidth priority dialog.
+    Options,
+    /// Daemon settings screen.
+    Settings,
     /// Confirm delete dialog. Bool indicates whether to delete local data.
     ConfirmDelete(bool),
 }
 
 /// Main Application.
+///
+/// RPC calls are handed to a background [`worker::Worker`]; their results
+/// are applied by [`App::finish`], so the app is only ever touched by the
+/// UI task and renders without waiting on the daemon.
 #[derive(Debug)]
 pub struct App {
     pub running: bool,
     index: usize,
     tabs: Vec<Tab>,
     pub state: TableState,
-    pub torrents: Torrents,
+    pub torrents: TorrentList,
     pub show_help: bool,
     pub config: Config,
     pub input_handler: InputHandler,
     pub input_mode: InputMode,
     pub filter_text: String,
+    pub add_form: AddTorrentForm,
+    pub limits_form: LimitsForm,
+    /// Edit-labels popup; `None` while closed.
+    pub labels_form: Option<LabelsForm>,
+    /// Label sidebar filtering the torrent list.
+    pub labels: LabelSidebar,
+    /// Seeding options dialog; `None` while closed.
+    pub options_form: Option<OptionsForm>,
+    /// Tracker dialog; `None` until the trackers are loaded.
+    pub trackers_form: Option<TrackersForm>,
+    /// Settings screen; `None` until the settings are loaded.
+    pub settings_form: Option<SettingsForm>,
+    pub detail: Option<DetailView>,
+    /// Global speed limits, refreshed on every tick.
+    pub speed_limits: Option<SpeedLimits>,
+    pub notifications: Notifications,
+    pub show_notifications: bool,
+    pub connection: ConnectionHealth,
+    /// Transfer speeds of recent updates, for the graphs.
+    pub history: SpeedHistory,
+    /// Session stats popup.
+    pub stats: StatsView,
+    /// Housekeeping rules run after every update.
+    pub rules: Rules,
+    /// Requests sent to the worker and not answered yet.
+    pub jobs: Jobs,
+    /// Where the last frame put clickable elements.
+    pub click_areas: ClickAreas,
 }
 
+/// Fields every list view needs: ids for selection, status for row colors,
+/// transfer totals for the status bar and what the labels dialog and the
+/// move prompt prefill from.
+const BASE_FIELDS: [TorrentGetField; 10] = [
+    TorrentGetField::Id,
+    TorrentGetField::Name,
+    TorrentGetField::Status,
+    TorrentGetField::Error,
+    TorrentGetField::RateDownload,
+    TorrentGetField::RateUpload,
+    TorrentGetField::DownloadedEver,
+    TorrentGetField::UploadedEver,
+    TorrentGetField::DownloadDir,
+    TorrentGetField::Labels,
+];
+
 impl App {
     /// Constructs a new instance of [`App`].
     /// Returns instance of `Self`.
     ///
     /// # Errors
     ///
-    /// TODO: add error types
+    /// Returns an error if the password command of the connection fails.
     pub fn new(config: Config) -> Result<Self> {
         let tabs = config.tabs.iter().cloned().map(Tab::new).collect();
+        let connection = Connection::resolve(&config.connection)?;
         Ok(Self {
             running: true,
             tabs,
             index: 0,
             state: TableState::default(),
-            torrents: Torrents::new()?,
+            torrents: TorrentList::default(),
             show_help: false,
+            history: SpeedHistory::new(&config.graph),
+            stats: StatsView::default(),
+            rules: Rules::new(&config.rules, &config.automation),
             config,
             input_handler: InputHandler::new(),
             input_mode: InputMode::None,
             filter_text: String::new(),
+            add_form: AddTorrentForm::default(),
+            limits_form: LimitsForm::default(),
+            labels_form: None,
+            labels: LabelSidebar::default(),
+            options_form: None,
+            trackers_form: None,
+            settings_form: None,
+            detail: None,
+            speed_limits: None,
+            notifications: Notifications::default(),
+            show_notifications: false,
+            connection: ConnectionHealth::default(),
+            jobs: Jobs::new(connection),
+            click_areas: ClickAreas::default(),
         })
     }
 
@@ -70,17 +192,289 @@ impl App {
     ///
     /// TODO: add error types
     pub async fn complete_input(&mut self) -> Result<()> {
-        self.input_handler.complete().await
+        match self.input_mode {
+            InputMode::Add => self.add_form.complete().await,
+            InputMode::Limits | InputMode::Options | InputMode::Trackers => Ok(()),
+            InputMode::Labels => {
+                if let Some(form) = &mut self.labels_form {
+                    form.complete();
+                }
+                Ok(())
+            }
+            InputMode::Settings => match &mut self.settings_form {
+                Some(form) => form.complete().await,
+                None => Ok(()),
+            },
+            _ => self.input_handler.complete().await,
+        }
+    }
+
+    /// Returns the text input that receives typed characters.
+    ///
+    /// In the add-torrent dialog this is the focused text field, if any.
+    pub fn active_input_mut(&mut self) -> Option<&mut InputHandler> {
+        match self.input_mode {
+            InputMode::Add => self.add_form.focused_input_mut(),
+            InputMode::Limits => self.limits_form.focused_input_mut(),
+            InputMode::Labels => self.labels_form.as_mut().map(|form| &mut form.input),
+            InputMode::Options => self
+                .options_form
+                .as_mut()
+                .and_then(OptionsForm::focused_input_mut),
+            InputMode::Trackers => self
+                .trackers_form
+                .as_mut()
+                .and_then(TrackersForm::focused_input_mut),
+            InputMode::Settings => self
+                .settings_form
+                .as_mut()
+                .and_then(SettingsForm::focused_input_mut),
+            _ => Some(&mut self.input_handler),
+        }
+    }
+
+    /// Fields the torrent list needs for the active tab, its sort order and
+    /// the active filter, without duplicates.
+    #[must_use]
+    pub fn required_fields(&self) -> Vec<TorrentGetField> {
+        let tab = &self.tabs[self.index];
+        let filter = self.filter_query().unwrap_or_default();
+        let mut fields = Vec::new();
+        for field in BASE_FIELDS
+            .into_iter()
+            .chain(tab.required_fields())
+            .chain(filter.fields())
+            .chain(self.rules.fields())
+            .chain(self.history.fields())
+        {
+            if !fields.contains(&field) {
+                fields.push(field);
+            }
+        }
+        fields
     }
 
-    /// Handles the tick event of the terminal.
+    /// Switches the requested fields to what the current view needs. Marks a
+    /// poll as due when a field is missing, so a new tab does not show empty
+    /// columns until the next interval.
+    pub fn sync_fields(&mut self) {
+        let fields = self.required_fields();
+        let missing = self
+            .torrents
+            .fields
+            .as_ref()
+            .is_some_and(|current| fields.iter().any(|field| !current.contains(field)));
+        self.torrents.fields = Some(fields);
+        if missing && self.connection.is_usable() {
+            self.refresh();
+        }
+    }
+
+    /// Polls the torrent list, the open detail view and the speed limits if
+    /// a poll is due and none is still in flight. While the daemon is
+    /// failing, polls are spaced out by the connection's backoff.
     ///
     /// # Errors
     ///
-    /// TODO: add error types
-    pub async fn tick(&mut self) -> Result<()> {
-        self.torrents.update().await?;
-        Ok(())
+    /// Returns an error if the worker cannot be started.
+    pub fn tick(&mut self) -> Result<()> {
+        if !self.jobs.poll_ready() || !self.connection.should_poll(Instant::now()) {
+            return Ok(());
+        }
+        self.poll()
+    }
+
+    fn poll(&mut self) -> Result<()> {
+        let fields = self.required_fields();
+        self.torrents.fields = Some(fields.clone());
+        let stats = self.stats.request(Instant::now());
+        self.submit(Request::Poll {
+            fields,
+            detail: self.detail.as_ref().map(DetailView::id),
+            stats,
+        })
+        .inspect_err(|_| self.stats.cancel())
+    }
+
+    /// Polls after an action so its effect shows without waiting for the
+    /// next update. Polls already in flight are not stacked on; the poll
+    /// goes out once they finish.
+    const fn refresh(&mut self) {
+        self.jobs.mark_poll_due();
+    }
+
+    fn submit(&mut self, request: Request) -> Result<()> {
+        self.jobs.submit(request).map(|_| ())
+    }
+
+    /// Waits until every request in flight has finished and applies the
+    /// results.
+    pub async fn settle(&mut self) {
+        while let Some((job, result)) = self.jobs.recv().await {
+            self.finish(&job, result);
+        }
+    }
+
+    /// Applies the result of a finished request.
+    pub fn finish(&mut self, job: &Job, result: Result<Outcome>) {
+        if let Some(firing) = self.rules.finish(job.id) {
+            let error = result.as_ref().err().map(ToString::to_string);
+            if error.is_some() {
+                self.rules.retry(&firing);
+            }
+            self.audit(&firing, error.as_deref());
+        }
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(err) if job.background => {
+                self.stats.cancel();
+                self.connection.record_failure(&err, Instant::now());
+                self.notifications
+                    .warning(format!("Failed to update torrents: {err}"));
+                return;
+            }
+            Err(err) => {
+                if err.is_transport() {
+                    self.connection.record_failure(&err, Instant::now());
+                }
+                self.notifications
+                    .error(format!("{} failed: {err}", job.label));
+                return;
+            }
+        };
+        match outcome {
+            Outcome::Done => self.refresh(),
+            Outcome::Polled {
+                limits,
+                delta,
+                detail,
+                stats,
+            } => {
+                if matches!(
+                    self.connection.state(),
+                    ConnectionState::Retrying
+                        | ConnectionState::Unauthorized
+                        | ConnectionState::Unreachable
+                ) {
+                    self.notifications.info("Reconnected to Transmission");
+                }
+                self.connection.record_success();
+                self.speed_limits = Some(limits);
+                self.torrents.apply(delta);
+                self.history.record(&self.torrents.torrents);
+                if let Some((id, torrent)) = detail {
+                    self.show_details(id, torrent);
+                }
+                if let Some(stats) = stats {
+                    self.stats.set(stats, Instant::now());
+                }
+                self.run_rules();
+            }
+            Outcome::Details(id, torrent) => self.show_details(id, torrent),
+            Outcome::Added(name) => {
+                self.notifications.info(format!("Added {name}"));
+                self.refresh();
+            }
+            Outcome::SpeedLimits(limits) => self.speed_limits = Some(limits),
+            Outcome::Limits(_, torrent) => self.show_limits(torrent),
+            Outcome::Options(torrents) => {
+                if self.input_mode != InputMode::Options || self.options_form.is_some() {
+                    return;
+                }
+                if torrents.is_empty() {
+                    self.clear_input();
+                } else {
+                    self.options_form =
+                        Some(OptionsForm::new(&torrents.iter().collect::<Vec<_>>()));
+                }
+            }
+            Outcome::Trackers(torrents) => {
+                if self.input_mode == InputMode::Trackers && self.trackers_form.is_none() {
+                    self.trackers_form = Some(TrackersForm::new(torrents));
+                }
+            }
+            Outcome::Settings(settings) => {
+                if self.input_mode == InputMode::Settings && self.settings_form.is_none() {
+                    self.settings_form = Some(SettingsForm::new(settings));
+                }
+            }
+        }
+    }
+
+    /// Applies the rules to the updated torrents. Dry runs and actions that
+    /// change nothing are logged right away, others once they finish.
+    fn run_rules(&mut self) {
+        for firing in self.rules.evaluate(&self.torrents.torrents) {
+            self.notifications.info(firing.summary());
+            let request = if firing.dry_run {
+                None
+            } else {
+                self.rule_request(&firing)
+            };
+            let Some(request) = request else {
+                self.audit(&firing, None);
+                continue;
+            };
+            match self.jobs.submit(request) {
+                Ok(id) => self.rules.start(id, firing),
+                Err(err) => {
+                    self.rules.retry(&firing);
+                    self.audit(&firing, Some(&err.to_string()));
+                    self.notifications.error(err.to_string());
+                }
+            }
+        }
+    }
+
+    /// Writes a firing and how its action ended to the rules audit log.
+    fn audit(&mut self, firing: &Firing, error: Option<&str>) {
+        if let Err(err) = self.rules.audit.record(firing, error) {
+            self.notifications
+                .warning(format!("Failed to write the rules audit log: {err}"));
+        }
+    }
+
+    /// Request carrying out a rule's action, if anything changes.
+    fn rule_request(&self, firing: &Firing) -> Option<Request> {
+        let ids = Selected::List(firing.ids().into_iter().collect());
+        Some(match &firing.action {
+            RuleAction::Stop => Request::Stop(ids),
+            &RuleAction::Remove { delete_local_data } => Request::Remove {
+                ids,
+                delete_local_data,
+            },
+            RuleAction::Move(location) => Request::Move {
+                ids,
+                location: location.clone(),
+            },
+            RuleAction::Label(label) => {
+                let edit = LabelEdit {
+                    add: vec![label.clone()],
+                    remove: Vec::new(),
+                };
+                let torrents = &self.torrents.torrents;
+                let changes =
+                    edit.changes(firing.ids().into_iter().filter_map(|id| torrents.get(id)));
+                if changes.is_empty() {
+                    return None;
+                }
+                Request::SetLabels(changes)
+            }
+            &RuleAction::Priority(priority) => Request::TorrentOptions(
+                ids,
+                OptionsChange {
+                    priority: Some(priority),
+                    ..OptionsChange::default()
+                },
+            ),
+        })
+    }
+
+    /// Shows fetched data in the detail view if it is still open on `id`.
+    fn show_details(&mut self, id: i64, torrent: Option<Torrent>) {
+        if let Some(detail) = self.detail.as_mut().filter(|d| d.id() == id) {
+            detail.set_torrent(torrent);
+        }
     }
 
     /// Set running to false to quit the application.
@@ -90,6 +484,10 @@ impl App {
     }
 
     pub fn next(&mut self) {
+        if let Some(detail) = &mut self.detail {
+            detail.next();
+            return;
+        }
         let len = self.filtered_torrents().len();
         if len == 0 {
             return;
@@ -103,6 +501,10 @@ impl App {
     }
 
     pub fn previous(&mut self) {
+        if let Some(detail) = &mut self.detail {
+            detail.previous();
+            return;
+        }
         let len = self.filtered_torrents().len();
         if len == 0 {
             return;
@@ -148,6 +550,22 @@ impl App {
         self.index
     }
 
+    /// Sorts the active tab by its next column.
+    pub fn cycle_sort(&mut self) {
+        self.tabs[self.index].cycle_sort();
+    }
+
+    /// Sorts the active tab by the column at `idx`, or flips the direction
+    /// if it is already sorted by it.
+    pub fn sort_by(&mut self, idx: usize) {
+        self.tabs[self.index].sort_by(idx);
+    }
+
+    /// Reverses the sort direction of the active tab.
+    pub fn reverse_sort(&mut self) {
+        self.tabs[self.index].reverse_sort();
+    }
+
     /// Returns [`Tab`] slice
     #[inline]
     #[must_use]
@@ -155,6 +573,11 @@ impl App {
         &self.tabs
     }
 
+    /// Shows or hides the notification history.
+    pub const fn toggle_notifications(&mut self) {
+        self.show_notifications = !self.show_notifications;
+    }
+
     #[inline]
     pub const fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
@@ -170,26 +593,131 @@ impl App {
         self.show_help = true;
     }
 
+    /// Start stopped and stop running torrents among the selected or
+    /// highlighted ones.
+    ///
     /// # Errors
     ///
-    /// TODO: add error types
-    pub async fn toggle_torrents(&mut self) -> Result<()> {
-        let ids = self.selected(false);
-        self.torrents.toggle(ids).await?;
+    /// Returns an error if the worker is unavailable.
+    pub fn toggle_torrents(&mut self) -> Result<()> {
+        let ids: HashSet<_> = self.selected(false).into();
         self.close_help();
+        self.toggle(|id| ids.contains(&id), Request::Start)
+    }
+
+    /// Start all stopped torrents immediately and stop all others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn toggle_all(&mut self) -> Result<()> {
+        self.toggle(|_| true, Request::StartNow)
+    }
+
+    fn toggle(
+        &mut self,
+        include: impl Fn(i64) -> bool,
+        start: fn(Selected) -> Request,
+    ) -> Result<()> {
+        let (to_start, to_stop): (HashSet<_>, HashSet<_>) = self
+            .torrents
+            .torrents
+            .iter()
+            .filter_map(|t| t.id.filter(|&id| include(id)).map(|id| (id, t.status)))
+            .partition(|(_, status)| matches!(status, Some(TorrentStatus::Stopped)));
+        let ids = |set: HashSet<(i64, Option<TorrentStatus>)>| {
+            Selected::List(set.into_iter().map(|(id, _)| id).collect())
+        };
+        if !to_start.is_empty() {
+            self.submit(start(ids(to_start)))?;
+        }
+        if !to_stop.is_empty() {
+            self.submit(Request::Stop(ids(to_stop)))?;
+        }
         Ok(())
     }
 
+    /// Start all torrents immediately.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn start_all(&mut self) -> Result<()> {
+        let ids = self.all_ids();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.submit(Request::StartNow(ids))
+    }
+
+    /// Stop all torrents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn stop_all(&mut self) -> Result<()> {
+        let ids = self.all_ids();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.submit(Request::Stop(ids))
+    }
+
+    fn all_ids(&self) -> Selected {
+        Selected::List(self.torrents.torrents.iter().filter_map(|t| t.id).collect())
+    }
+
+    /// Start selected or highlighted torrent(s), bypassing the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn start_now(&mut self) -> Result<()> {
+        let ids = self.selected(false);
+        self.submit(Request::StartNow(ids))
+    }
+
+    /// Verify the data of selected or highlighted torrent(s).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn verify(&mut self) -> Result<()> {
+        let ids = self.selected(false);
+        self.submit(Request::Verify(ids))
+    }
+
+    /// Reannounce selected or highlighted torrent(s) to their trackers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn reannounce(&mut self) -> Result<()> {
+        let ids = self.selected(false);
+        self.submit(Request::Reannounce(ids))
+    }
+
+    /// Move selected or highlighted torrent(s) within the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn queue_move(&mut self, direction: QueueMove) -> Result<()> {
+        let ids = self.selected(false);
+        self.submit(Request::Queue(ids, direction))
+    }
+
     /// Move selected or highlighted torrent(s) to a new location.
     ///
     /// # Errors
     ///
-    /// Returns an error if the RPC call fails.
-    pub async fn move_torrent(&mut self) -> Result<()> {
+    /// Returns an error if the worker is unavailable.
+    pub fn move_torrent(&mut self) -> Result<()> {
         let ids = self.selected(false);
-        self.torrents
-            .move_torrents(ids, &self.input_handler.text)
-            .await?;
+        self.submit(Request::Move {
+            ids,
+            location: self.input_handler.text.clone(),
+        })?;
         self.clear_input();
         Ok(())
     }
@@ -207,15 +735,16 @@ impl App {
     ///
     /// # Errors
     ///
-    /// Returns an error if the RPC call fails.
-    pub async fn rename_torrent(&mut self) -> Result<()> {
+    /// Returns an error if the worker is unavailable.
+    pub fn rename_torrent(&mut self) -> Result<()> {
         let Some(torrent) = self.get_current_torrent() else {
             self.clear_input();
             return Ok(());
         };
-        self.torrents
-            .rename(&torrent, std::path::Path::new(&self.input_handler.text))
-            .await?;
+        self.submit(Request::Rename {
+            torrent: Box::new(torrent),
+            name: self.input_handler.text.clone(),
+        })?;
         self.clear_input();
         Ok(())
     }
@@ -228,6 +757,288 @@ impl App {
         self.input_mode = InputMode::Rename;
     }
 
+    /// Open the detail view for the highlighted torrent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn open_details(&mut self) -> Result<()> {
+        let Some(id) = self.get_current_torrent().and_then(|t| t.id) else {
+            return Ok(());
+        };
+        self.close_help();
+        self.detail = Some(DetailView::new(id));
+        self.submit(Request::Details(id))
+    }
+
+    /// Close the detail view and return to the torrent list.
+    pub fn close_details(&mut self) {
+        self.detail = None;
+    }
+
+    /// Toggle the wanted state of the selected (or highlighted) files.
+    ///
+    /// Files are marked unwanted when all of them are wanted, wanted otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn toggle_files_wanted(&mut self) -> Result<()> {
+        let Some(detail) = &self.detail else {
+            return Ok(());
+        };
+        let files = detail.target_files();
+        let change = FileChange::Wanted(!detail.all_wanted(&files));
+        self.update_files(files, change)
+    }
+
+    /// Cycle the priority of the selected (or highlighted) files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn cycle_files_priority(&mut self) -> Result<()> {
+        let Some(detail) = &self.detail else {
+            return Ok(());
+        };
+        let files = detail.target_files();
+        let change = FileChange::Priority(detail.next_priority(&files));
+        self.update_files(files, change)
+    }
+
+    fn update_files(&mut self, files: Vec<usize>, change: FileChange) -> Result<()> {
+        let Some(id) = self.detail.as_ref().map(DetailView::id) else {
+            return Ok(());
+        };
+        self.submit(Request::SetFiles { id, files, change })?;
+        self.submit(Request::Details(id))
+    }
+
+    /// Start the pattern input selecting files in the detail view.
+    pub fn start_file_selection(&mut self) {
+        if self
+            .detail
+            .as_ref()
+            .is_some_and(|d| d.pane == detail::DetailPane::Files)
+        {
+            self.input_handler.clear();
+            self.input_mode = InputMode::SelectFiles;
+        }
+    }
+
+    /// Select the files matching the entered patterns.
+    pub fn apply_file_selection(&mut self) {
+        if let Some(detail) = &mut self.detail {
+            detail.select_matching_files(&self.input_handler.text);
+        }
+        self.clear_input();
+    }
+
+    /// Open the add-torrent dialog.
+    pub fn prepare_add_action(&mut self) {
+        self.add_form.clear();
+        self.input_mode = InputMode::Add;
+    }
+
+    /// Add the torrent described by the add-torrent dialog.
+    ///
+    /// Does nothing while the source field is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn add_torrent(&mut self) -> Result<()> {
+        let source = self.add_form.source.text.clone();
+        if source.trim().is_empty() {
+            return Ok(());
+        }
+        self.submit(Request::Add {
+            source,
+            options: self.add_form.options(),
+        })?;
+        self.add_form.clear();
+        self.clear_input();
+        Ok(())
+    }
+
+    /// Turn alternative speed limits on or off.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn toggle_alt_speed(&mut self) -> Result<()> {
+        let enabled = self.speed_limits.is_some_and(|l| l.alt_speed_enabled);
+        self.submit(Request::SetAltSpeed(!enabled))
+    }
+
+    /// Open the speed limits dialog for the global limits.
+    pub fn prepare_session_limits(&mut self) {
+        let limits = self.speed_limits.unwrap_or_default();
+        let limit =
+            |enabled: bool, value: i32| enabled.then(|| u64::try_from(value).unwrap_or_default());
+        self.limits_form = LimitsForm::new(
+            LimitTarget::Session,
+            limit(limits.speed_limit_down_enabled, limits.speed_limit_down),
+            limit(limits.speed_limit_up_enabled, limits.speed_limit_up),
+            true,
+        );
+        self.input_mode = InputMode::Limits;
+    }
+
+    /// Open the speed limits dialog for the selected torrents and load the
+    /// limits of the highlighted one to prefill it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn prepare_torrent_limits(&mut self) -> Result<()> {
+        let Some(id) = self.get_current_torrent().and_then(|t| t.id) else {
+            return Ok(());
+        };
+        self.limits_form = LimitsForm::loading(LimitTarget::Torrents);
+        self.input_mode = InputMode::Limits;
+        self.submit(Request::Limits(id))
+    }
+
+    /// Prefills the torrent speed limits dialog if it is still waiting.
+    fn show_limits(&mut self, torrent: Option<Torrent>) {
+        if self.input_mode != InputMode::Limits || !self.limits_form.loading {
+            return;
+        }
+        let Some(torrent) = torrent else {
+            self.limits_form.clear();
+            self.clear_input();
+            return;
+        };
+        let limit = |limited: Option<bool>, value: Option<u64>| {
+            limited
+                .unwrap_or_default()
+                .then(|| value.unwrap_or_default())
+        };
+        self.limits_form = LimitsForm::new(
+            LimitTarget::Torrents,
+            limit(torrent.download_limited, torrent.download_limit),
+            limit(torrent.upload_limited, torrent.upload_limit),
+            torrent.honors_session_limits.unwrap_or(true),
+        );
+    }
+
+    /// Apply the limits entered in the speed limits dialog.
+    ///
+    /// Invalid values keep the dialog open with an error message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn apply_limits(&mut self) -> Result<()> {
+        if self.limits_form.loading {
+            return Ok(());
+        }
+        let change = match self.limits_form.change() {
+            Ok(change) => change,
+            Err(err) => {
+                self.limits_form.error = Some(err);
+                return Ok(());
+            }
+        };
+        let request = match self.limits_form.target {
+            LimitTarget::Session => Request::SessionLimits(change),
+            LimitTarget::Torrents => Request::TorrentLimits(self.selected(false), change),
+        };
+        self.submit(request)?;
+        self.limits_form.clear();
+        self.clear_input();
+        Ok(())
+    }
+
+    /// Open the seeding options dialog for the selected or highlighted
+    /// torrent(s) and load their options to prefill the ones they share.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn prepare_options(&mut self) -> Result<()> {
+        let ids: Vec<i64> = self.selected(false).into();
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.options_form = None;
+        self.input_mode = InputMode::Options;
+        self.submit(Request::Options(ids))
+    }
+
+    /// Apply the options changed in the seeding options dialog.
+    ///
+    /// Invalid values keep the dialog open with an error message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn apply_options(&mut self) -> Result<()> {
+        let Some(form) = &mut self.options_form else {
+            return Ok(());
+        };
+        let change = match form.change() {
+            Ok(change) => change,
+            Err(err) => {
+                form.error = Some(err);
+                return Ok(());
+            }
+        };
+        let ids = Selected::List(form.ids().iter().copied().collect());
+        self.options_form = None;
+        self.clear_input();
+        if change.is_empty() {
+            self.notifications.info("No options changed");
+            return Ok(());
+        }
+        self.submit(Request::TorrentOptions(ids, change))
+    }
+
+    /// Open the settings screen and load the daemon settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn open_settings(&mut self) -> Result<()> {
+        self.settings_form = None;
+        self.input_mode = InputMode::Settings;
+        self.submit(Request::Settings)
+    }
+
+    /// Preview the changes on the settings screen, or apply them once the
+    /// preview is confirmed.
+    ///
+    /// Invalid values keep the screen open with an error message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn apply_settings(&mut self) -> Result<()> {
+        let Some(form) = &mut self.settings_form else {
+            return Ok(());
+        };
+        if let Some(changes) = form.preview.take() {
+            self.submit(Request::SetSettings(changes))?;
+            self.settings_form = None;
+            self.clear_input();
+            return Ok(());
+        }
+        match form.changes() {
+            Ok(changes) if changes.is_empty() => {
+                self.notifications.info("No settings changed");
+                self.settings_form = None;
+                self.clear_input();
+            }
+            Ok(changes) => {
+                form.error = None;
+                form.preview = Some(changes);
+            }
+            Err(err) => form.error = Some(err),
+        }
+        Ok(())
+    }
+
     /// Clear input and reset input mode.
     fn clear_input(&mut self) {
         self.input_handler.clear();
@@ -235,14 +1046,124 @@ impl App {
         self.close_help();
     }
 
+    /// Open the edit-labels popup for the selected or highlighted
+    /// torrent(s), prefilled with the labels they share.
+    pub fn prepare_labels(&mut self) {
+        let ids: HashSet<_> = self.selected(false).into();
+        let torrents: Vec<_> = self
+            .torrents
+            .torrents
+            .iter()
+            .filter(|t| t.id.is_some_and(|id| ids.contains(&id)))
+            .collect();
+        if torrents.is_empty() {
+            return;
+        }
+        let known = label_counts(&self.torrents.torrents).into_keys().collect();
+        self.labels_form = Some(LabelsForm::new(&torrents, known));
+        self.input_mode = InputMode::Labels;
+    }
+
+    /// Apply the edit from the labels popup to the torrents it was opened
+    /// for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn apply_labels(&mut self) -> Result<()> {
+        let Some(form) = self.labels_form.take() else {
+            return Ok(());
+        };
+        self.input_mode = InputMode::None;
+        let edit = form.edit();
+        let torrents = &self.torrents.torrents;
+        let changes = edit.changes(form.ids().iter().filter_map(|&id| torrents.get(id)));
+        if changes.is_empty() {
+            self.notifications.info("No labels changed");
+            return Ok(());
+        }
+        self.submit(Request::SetLabels(changes))
+    }
+
+    /// Open the tracker dialog for the torrent in the detail view, else the
+    /// selected or highlighted torrent(s), and load their trackers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn open_trackers(&mut self) -> Result<()> {
+        let ids: Vec<i64> = self
+            .detail
+            .as_ref()
+            .map_or_else(|| self.selected(false).into(), |detail| vec![detail.id()]);
+        if ids.is_empty() {
+            return Ok(());
+        }
+        self.close_help();
+        self.trackers_form = None;
+        self.input_mode = InputMode::Trackers;
+        self.submit(Request::Trackers(ids))
+    }
+
+    /// Apply the tracker edit previewed in the dialog. Invalid input or an
+    /// edit matching no tracker keeps the dialog open with an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker is unavailable.
+    pub fn apply_trackers(&mut self) -> Result<()> {
+        let Some(form) = &mut self.trackers_form else {
+            return Ok(());
+        };
+        let changes = match form.changes() {
+            Ok(changes) if changes.is_empty() => {
+                form.error = Some("No tracker matches".into());
+                return Ok(());
+            }
+            Ok(changes) => changes,
+            Err(err) => {
+                form.error = Some(err);
+                return Ok(());
+            }
+        };
+        self.submit(Request::EditTrackers(changes))?;
+        self.trackers_form = None;
+        self.input_mode = InputMode::None;
+        Ok(())
+    }
+
+    /// Show or hide the label sidebar.
+    pub fn toggle_label_sidebar(&mut self) {
+        self.labels.toggle();
+        self.state.select(Some(0));
+    }
+
+    /// Filter by the next or previous label in the sidebar.
+    pub fn cycle_label(&mut self, forward: bool) {
+        if self.labels.visible {
+            self.labels.cycle(forward, &self.torrents.torrents);
+            self.state.select(Some(0));
+        }
+    }
+
+    /// Filter by the sidebar entry at `idx`.
+    pub fn filter_label(&mut self, idx: usize) {
+        if self.labels.select(idx, &self.torrents.torrents) {
+            self.state.select(Some(0));
+        }
+    }
+
     /// Start filter mode.
     pub fn start_filter(&mut self) {
         self.input_handler.set_text(self.filter_text.clone());
         self.input_mode = InputMode::Filter;
     }
 
-    /// Apply filter from input.
+    /// Apply filter from input. An invalid query keeps the filter popup open.
     pub fn apply_filter(&mut self) {
+        if self.filter_query().is_err() {
+            return;
+        }
         self.filter_text = self.input_handler.text.clone();
         self.input_handler.clear();
         self.input_mode = InputMode::None;
@@ -265,26 +1186,41 @@ impl App {
         }
     }
 
-    /// Get filtered torrents based on current filter text using fuzzy matching.
+    /// Parses the active filter text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first invalid term.
+    pub fn filter_query(&self) -> std::result::Result<Query, QueryError> {
+        Query::parse(self.active_filter())
+    }
+
+    /// Get torrents matching the active filter query, ordered by the active
+    /// tab's sort order.
+    ///
+    /// An invalid query filters nothing. Without a sort order, torrents
+    /// matching bare words are ordered by fuzzy score.
     #[must_use]
     pub fn filtered_torrents(&self) -> Vec<&Torrent> {
-        let filter = self.active_filter();
-        let tab = &self.tabs[self.index];
-        if filter.is_empty() {
-            return self
-                .torrents
-                .torrents
-                .iter()
-                .filter(|torrent| tab.matches(torrent))
-                .collect();
+        let mut torrents = self.matching_torrents();
+        if let Some(sort) = self.tabs[self.index].sort() {
+            torrents.sort_by(|a, b| sort.compare(a, b));
         }
+        torrents
+    }
+
+    fn matching_torrents(&self) -> Vec<&Torrent> {
+        let query = self.filter_query().unwrap_or_default();
+        let tab = &self.tabs[self.index];
+        let torrents = self.torrents.torrents.iter().filter(|torrent| {
+            tab.matches(torrent) && self.labels.filter.matches(torrent) && query.matches(torrent)
+        });
+        let Some(filter) = query.fuzzy() else {
+            return torrents.collect();
+        };
 
         let matcher = SkimMatcherV2::default();
-        let mut scored = self
-            .torrents
-            .torrents
-            .iter()
-            .filter(|torrent| tab.matches(torrent))
+        let mut scored = torrents
             .filter_map(|t| {
                 t.name
                     .as_ref()
@@ -292,7 +1228,7 @@ impl App {
             })
             .collect::<Vec<_>>();
         // Sort by score descending (best matches first)
-        scored.sort_by(|a, b| b.1.cmp(&a.1));
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
         scored.into_iter().map(|(t, _)| t).collect()
     }
 
@@ -305,28 +1241,49 @@ impl App {
     ///
     /// # Errors
     ///
-    /// Returns an error if the RPC call fails.
-    pub async fn confirm_delete(&mut self) -> Result<()> {
+    /// Returns an error if the worker is unavailable.
+    pub fn confirm_delete(&mut self) -> Result<()> {
         let InputMode::ConfirmDelete(delete_local_data) = self.input_mode else {
             return Ok(());
         };
         let ids = self.selected(false);
-        self.torrents.delete(ids, delete_local_data).await?;
+        self.submit(Request::Remove {
+            ids,
+            delete_local_data,
+        })?;
         self.clear_input();
         Ok(())
     }
 
     pub fn select(&mut self) {
-        if let Selected::Current(current_id) = self.selected(true) {
-            if self.torrents.selected.contains(&current_id) {
-                self.torrents.selected.remove(&current_id);
-            } else {
-                self.torrents.selected.insert(current_id);
-            }
-        }
+        self.toggle_highlighted();
         self.next();
     }
 
+    /// Highlight the torrent at `idx` in the filtered list.
+    pub fn highlight(&mut self, idx: usize) {
+        if idx < self.filtered_torrents().len() {
+            self.close_help();
+            self.state.select(Some(idx));
+        }
+    }
+
+    /// Highlight the torrent at `idx` and toggle whether it is selected.
+    pub fn select_row(&mut self, idx: usize) {
+        if idx < self.filtered_torrents().len() {
+            self.state.select(Some(idx));
+            self.toggle_highlighted();
+        }
+    }
+
+    fn toggle_highlighted(&mut self) {
+        if let Selected::Current(current_id) = self.selected(true)
+            && !self.torrents.selected.remove(&current_id)
+        {
+            self.torrents.selected.insert(current_id);
+        }
+    }
+
     fn selected(&self, highlighted: bool) -> Selected {
         let torrents = self.filtered_torrents();
         if (self.torrents.selected.is_empty() || highlighted)
@@ -345,6 +1302,18 @@ impl App {
         )
     }
 
+    /// The torrent the graphs follow: the one in the detail view, else the
+    /// highlighted one.
+    #[must_use]
+    pub fn graph_torrent(&self) -> Option<&Torrent> {
+        if let Some(detail) = &self.detail {
+            return self.torrents.torrents.get(detail.id());
+        }
+        self.state
+            .selected()
+            .and_then(|idx| self.filtered_torrents().get(idx).copied())
+    }
+
     fn get_current_download_dir(&self) -> Option<PathBuf> {
         self.get_current_torrent()
             .and_then(|t| t.download_dir)
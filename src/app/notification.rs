@@ -0,0 +1,135 @@
+use chrono::{DateTime, Local};
+use derive_more::Display;
+use std::{collections::VecDeque, time::Duration};
+
+/// How long the latest notification stays visible as a toast.
+pub const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// Number of notifications kept in the history.
+const HISTORY_CAPACITY: usize = 100;
+
+/// Severity of a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum Severity {
+    #[display("info")]
+    Info,
+    #[display("warning")]
+    Warning,
+    #[display("error")]
+    Error,
+}
+
+/// A message shown to the user, e.g. a failed RPC call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub severity: Severity,
+    pub message: String,
+    /// Time of the latest occurrence.
+    pub timestamp: DateTime<Local>,
+    /// How many times the message was reported in a row.
+    pub count: usize,
+}
+
+/// History of notifications, newest last.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    history: VecDeque<Notification>,
+}
+
+impl Notifications {
+    /// Records a notification. Repeating the latest message bumps its count
+    /// instead of adding an entry, so a daemon that stays down fills the
+    /// history with one line.
+    pub fn push(&mut self, severity: Severity, message: impl Into<String>) {
+        let message = message.into();
+        let timestamp = Local::now();
+        if let Some(latest) = self
+            .history
+            .back_mut()
+            .filter(|n| n.severity == severity && n.message == message)
+        {
+            latest.count += 1;
+            latest.timestamp = timestamp;
+            return;
+        }
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
+        self.history.push_back(Notification {
+            severity,
+            message,
+            timestamp,
+            count: 1,
+        });
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(Severity::Info, message);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(Severity::Warning, message);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(Severity::Error, message);
+    }
+
+    /// Returns the latest notification if it is recent enough to show as a toast.
+    #[must_use]
+    pub fn toast(&self, now: DateTime<Local>) -> Option<&Notification> {
+        self.history
+            .back()
+            .filter(|n| (now - n.timestamp).to_std().unwrap_or_default() < TOAST_DURATION)
+    }
+
+    /// Returns all notifications, newest first.
+    pub fn history(&self) -> impl Iterator<Item = &Notification> {
+        self.history.iter().rev()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.history.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HISTORY_CAPACITY, Notifications, Severity, TOAST_DURATION};
+    use chrono::{Local, TimeDelta};
+
+    #[test]
+    fn repeated_messages_are_counted() {
+        let mut notifications = Notifications::default();
+        notifications.warning("daemon unreachable");
+        notifications.warning("daemon unreachable");
+        notifications.error("daemon unreachable");
+        assert_eq!(notifications.len(), 2);
+        let Some(latest) = notifications.history().nth(1) else {
+            panic!("expected two notifications");
+        };
+        assert_eq!((latest.severity, latest.count), (Severity::Warning, 2));
+    }
+
+    #[test]
+    fn toast_expires_and_history_is_bounded() {
+        let mut notifications = Notifications::default();
+        for idx in 0..=HISTORY_CAPACITY {
+            notifications.info(idx.to_string());
+        }
+        assert_eq!(notifications.len(), HISTORY_CAPACITY);
+
+        let now = Local::now();
+        assert!(notifications.toast(now).is_some());
+        let Ok(later) = TimeDelta::from_std(TOAST_DURATION) else {
+            panic!("toast duration should fit a time delta");
+        };
+        assert!(notifications.toast(now + later).is_none());
+    }
+}
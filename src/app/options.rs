@@ -0,0 +1,347 @@
+use super::input::InputHandler;
+use transmission_rpc::types::{IdleMode, Priority, RatioMode, Torrent, TorrentGetField};
+
+/// Fields the options dialog prefills the shared seeding options from.
+pub const FIELDS: [TorrentGetField; 6] = [
+    TorrentGetField::Id,
+    TorrentGetField::SeedRatioLimit,
+    TorrentGetField::SeedRatioMode,
+    TorrentGetField::SeedIdleLimit,
+    TorrentGetField::SeedIdleMode,
+    TorrentGetField::BandwidthPriority,
+];
+
+const RATIO_MODES: [RatioMode; 3] = [RatioMode::Global, RatioMode::Single, RatioMode::Unlimited];
+const IDLE_MODES: [IdleMode; 3] = [IdleMode::Global, IdleMode::Single, IdleMode::Unlimited];
+const PRIORITIES: [Priority; 3] = [Priority::Low, Priority::Normal, Priority::High];
+
+/// Field of the torrent options dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OptionField {
+    #[default]
+    RatioMode,
+    RatioLimit,
+    IdleMode,
+    IdleLimit,
+    Priority,
+}
+
+impl OptionField {
+    pub const ALL: [Self; 5] = [
+        Self::RatioMode,
+        Self::RatioLimit,
+        Self::IdleMode,
+        Self::IdleLimit,
+        Self::Priority,
+    ];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::RatioMode => "Stop at ratio",
+            Self::RatioLimit => "Ratio limit",
+            Self::IdleMode => "Stop when idle",
+            Self::IdleLimit => "Idle limit (min)",
+            Self::Priority => "Bandwidth priority",
+        }
+    }
+}
+
+#[must_use]
+pub const fn ratio_mode_label(mode: RatioMode) -> &'static str {
+    match mode {
+        RatioMode::Global => "Global setting",
+        RatioMode::Single => "Ratio limit below",
+        RatioMode::Unlimited => "Never",
+    }
+}
+
+#[must_use]
+pub const fn idle_mode_label(mode: IdleMode) -> &'static str {
+    match mode {
+        IdleMode::Global => "Global setting",
+        IdleMode::Single => "Idle limit below",
+        IdleMode::Unlimited => "Never",
+    }
+}
+
+#[must_use]
+pub const fn priority_label(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Normal => "Normal",
+        Priority::High => "High",
+    }
+}
+
+/// Seeding limits and priority to set on torrents; `None` leaves a value
+/// as it is.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OptionsChange {
+    pub ratio_mode: Option<RatioMode>,
+    pub ratio_limit: Option<f64>,
+    pub idle_mode: Option<IdleMode>,
+    /// Minutes without transfers.
+    pub idle_limit: Option<u16>,
+    pub priority: Option<Priority>,
+}
+
+impl OptionsChange {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ratio_mode.is_none()
+            && self.ratio_limit.is_none()
+            && self.idle_mode.is_none()
+            && self.idle_limit.is_none()
+            && self.priority.is_none()
+    }
+}
+
+/// Values shown when the dialog opens; `None` or blank where the torrents
+/// differ.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Shared {
+    ratio_mode: Option<RatioMode>,
+    ratio_limit: String,
+    idle_mode: Option<IdleMode>,
+    idle_limit: String,
+    priority: Option<Priority>,
+}
+
+/// State of the torrent options dialog. Only values the user changes are
+/// sent, so options that differ between the selected torrents are kept.
+#[derive(Debug, Default)]
+pub struct OptionsForm {
+    pub focus: OptionField,
+    pub ratio_mode: Option<RatioMode>,
+    pub ratio_limit: InputHandler,
+    pub idle_mode: Option<IdleMode>,
+    pub idle_limit: InputHandler,
+    pub priority: Option<Priority>,
+    ids: Vec<i64>,
+    shared: Shared,
+    /// Validation error of the last submit.
+    pub error: Option<String>,
+}
+
+impl OptionsForm {
+    #[must_use]
+    pub fn new(torrents: &[&Torrent]) -> Self {
+        let shared = Shared {
+            ratio_mode: common(torrents, |t| t.seed_ratio_mode),
+            ratio_limit: common(torrents, |t| t.seed_ratio_limit.map(|v| v.to_string()))
+                .unwrap_or_default(),
+            idle_mode: common(torrents, |t| t.seed_idle_mode),
+            idle_limit: common(torrents, |t| t.seed_idle_limit.map(|v| v.to_string()))
+                .unwrap_or_default(),
+            priority: common(torrents, |t| t.bandwidth_priority),
+        };
+        let mut form = Self {
+            ratio_mode: shared.ratio_mode,
+            idle_mode: shared.idle_mode,
+            priority: shared.priority,
+            ids: torrents.iter().filter_map(|t| t.id).collect(),
+            ..Self::default()
+        };
+        form.ratio_limit.set_text(shared.ratio_limit.clone());
+        form.idle_limit.set_text(shared.idle_limit.clone());
+        form.shared = shared;
+        form
+    }
+
+    /// Ids of the edited torrents.
+    #[must_use]
+    pub fn ids(&self) -> &[i64] {
+        &self.ids
+    }
+
+    fn position(&self) -> usize {
+        OptionField::ALL
+            .iter()
+            .position(|&field| field == self.focus)
+            .unwrap_or_default()
+    }
+
+    /// Moves focus to the next field, wrapping around.
+    pub fn next_field(&mut self) {
+        self.focus = OptionField::ALL[(self.position() + 1) % OptionField::ALL.len()];
+    }
+
+    /// Moves focus to the previous field, wrapping around.
+    pub fn prev_field(&mut self) {
+        let idx = self
+            .position()
+            .checked_sub(1)
+            .unwrap_or(OptionField::ALL.len() - 1);
+        self.focus = OptionField::ALL[idx];
+    }
+
+    /// Returns the text input of the focused field, if it is a text field.
+    pub const fn focused_input_mut(&mut self) -> Option<&mut InputHandler> {
+        match self.focus {
+            OptionField::RatioLimit => Some(&mut self.ratio_limit),
+            OptionField::IdleLimit => Some(&mut self.idle_limit),
+            OptionField::RatioMode | OptionField::IdleMode | OptionField::Priority => None,
+        }
+    }
+
+    /// Switches the focused choice to its next or previous value. A choice
+    /// the torrents differ in starts from the first or last value.
+    pub fn cycle(&mut self, forward: bool) {
+        match self.focus {
+            OptionField::RatioMode => {
+                self.ratio_mode = Some(cycle(&RATIO_MODES, self.ratio_mode, forward));
+            }
+            OptionField::IdleMode => {
+                self.idle_mode = Some(cycle(&IDLE_MODES, self.idle_mode, forward));
+            }
+            OptionField::Priority => {
+                self.priority = Some(cycle(&PRIORITIES, self.priority, forward));
+            }
+            OptionField::RatioLimit | OptionField::IdleLimit => {}
+        }
+    }
+
+    /// Returns `true` if the torrents differ in the text field and it was
+    /// left blank.
+    #[must_use]
+    pub const fn is_mixed(&self, field: OptionField) -> bool {
+        let (input, shared) = match field {
+            OptionField::RatioLimit => (&self.ratio_limit, &self.shared.ratio_limit),
+            OptionField::IdleLimit => (&self.idle_limit, &self.shared.idle_limit),
+            OptionField::RatioMode | OptionField::IdleMode | OptionField::Priority => {
+                return false;
+            }
+        };
+        self.ids.len() > 1 && shared.is_empty() && input.text.is_empty()
+    }
+
+    /// Parses the values changed since the dialog opened.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the field whose value is invalid, or a limit
+    /// left blank while its mode requires one.
+    pub fn change(&self) -> Result<OptionsChange, String> {
+        let ratio_text = self.ratio_limit.text.trim();
+        let ratio_limit = if ratio_text == self.shared.ratio_limit {
+            None
+        } else {
+            match ratio_text.parse::<f64>() {
+                Ok(ratio) if ratio.is_finite() && ratio >= 0.0 => Some(ratio),
+                _ => return Err("Ratio limit must be a number of at least 0".into()),
+            }
+        };
+        let idle_text = self.idle_limit.text.trim();
+        let idle_limit = if idle_text == self.shared.idle_limit {
+            None
+        } else {
+            match idle_text.parse::<u16>() {
+                Ok(minutes) if minutes > 0 => Some(minutes),
+                _ => return Err("Idle limit must be a whole number of minutes".into()),
+            }
+        };
+        if self.ratio_mode == Some(RatioMode::Single) && ratio_text.is_empty() {
+            return Err("Enter the ratio limit to stop at".into());
+        }
+        if self.idle_mode == Some(IdleMode::Single) && idle_text.is_empty() {
+            return Err("Enter the idle limit to stop at".into());
+        }
+        Ok(OptionsChange {
+            ratio_mode: self
+                .ratio_mode
+                .filter(|&mode| Some(mode) != self.shared.ratio_mode),
+            ratio_limit,
+            idle_mode: self
+                .idle_mode
+                .filter(|&mode| Some(mode) != self.shared.idle_mode),
+            idle_limit,
+            priority: self
+                .priority
+                .filter(|&priority| Some(priority) != self.shared.priority),
+        })
+    }
+}
+
+/// The value every torrent has, if they agree.
+fn common<T: PartialEq>(torrents: &[&Torrent], value: impl Fn(&Torrent) -> Option<T>) -> Option<T> {
+    let mut values = torrents.iter().map(|&t| value(t));
+    let first = values.next()??;
+    values.all(|v| v.as_ref() == Some(&first)).then_some(first)
+}
+
+fn cycle<T: Copy + PartialEq>(all: &[T], current: Option<T>, forward: bool) -> T {
+    let len = all.len();
+    let idx = match current.and_then(|c| all.iter().position(|&v| v == c)) {
+        Some(idx) if forward => (idx + 1) % len,
+        Some(idx) => (idx + len - 1) % len,
+        None if forward => 0,
+        None => len - 1,
+    };
+    all[idx]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OptionField, OptionsForm};
+    use crate::app::test_utils::from_json;
+    use serde_json::json;
+    use transmission_rpc::types::{IdleMode, Priority, RatioMode, Torrent};
+
+    fn torrent(id: i64, ratio_mode: u8, ratio: f32, priority: i8) -> Torrent {
+        from_json(json!({
+            "id": id,
+            "seedRatioMode": ratio_mode,
+            "seedRatioLimit": ratio,
+            "seedIdleMode": 0,
+            "seedIdleLimit": 30,
+            "bandwidthPriority": priority,
+        }))
+    }
+
+    #[test]
+    fn only_changed_values_are_sent_and_differing_ones_kept() {
+        let (a, b) = (torrent(1, 1, 2.0, 0), torrent(2, 1, 1.5, 1));
+        let mut form = OptionsForm::new(&[&a, &b]);
+        assert_eq!(form.ids(), [1, 2]);
+        assert_eq!(form.ratio_mode, Some(RatioMode::Single));
+        assert_eq!(form.priority, None);
+        assert!(form.is_mixed(OptionField::RatioLimit));
+        assert_eq!(form.idle_limit.text, "30");
+
+        // A per-torrent ratio needs a limit once the torrents' ones differ.
+        assert!(form.change().is_err());
+        form.ratio_limit.set_text("3".into());
+        form.focus = OptionField::Priority;
+        form.cycle(false);
+        let change = form.change().unwrap_or_default();
+        assert_eq!(change.ratio_mode, None);
+        assert_eq!(change.ratio_limit, Some(3.0));
+        assert_eq!(change.idle_limit, None);
+        assert_eq!(change.priority, Some(Priority::High));
+    }
+
+    #[test]
+    fn modes_cycle_through_the_enums_and_limits_are_validated() {
+        let a = torrent(1, 0, 2.0, 0);
+        let mut form = OptionsForm::new(&[&a]);
+        assert!(form.change().is_ok_and(|change| change.is_empty()));
+
+        form.focus = OptionField::IdleMode;
+        form.cycle(true);
+        form.cycle(true);
+        assert_eq!(form.idle_mode, Some(IdleMode::Unlimited));
+        form.idle_limit.set_text("0".into());
+        assert_eq!(
+            form.change(),
+            Err("Idle limit must be a whole number of minutes".into())
+        );
+        form.idle_limit.set_text("30".into());
+        form.ratio_limit.set_text("-1".into());
+        assert!(form.change().is_err());
+        form.ratio_limit.set_text("0.5".into());
+        let change = form.change().unwrap_or_default();
+        assert_eq!(change.idle_mode, Some(IdleMode::Unlimited));
+        assert_eq!(change.ratio_limit, Some(0.5));
+    }
+}
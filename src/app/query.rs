@@ -0,0 +1,665 @@
+use super::utils::upload_ratio;
+use crate::config::tabs::parse_status;
+use std::{path::Path, str::FromStr};
+use thiserror::Error;
+use transmission_rpc::types::{ErrorType, Torrent, TorrentGetField, TorrentStatus};
+
+/// Error produced while parsing a filter query.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum QueryError {
+    #[error("unterminated quote")]
+    UnterminatedQuote,
+    #[error("unknown filter `{0}`")]
+    UnknownKey(String),
+    #[error("bare word `{0}` is not allowed here; quote it to match names")]
+    BareWord(String),
+    #[error("missing value for `{0}`")]
+    MissingValue(String),
+    #[error("`{key}` does not support `{op}`")]
+    UnsupportedOperator { key: String, op: Comparison },
+    #[error("invalid value `{value}` for `{key}`: {expected}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+/// Comparison operator of a `key<op>value` term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub enum Comparison {
+    #[display(":")]
+    Is,
+    #[display("=")]
+    Eq,
+    #[display("<")]
+    Lt,
+    #[display("<=")]
+    Le,
+    #[display(">")]
+    Gt,
+    #[display(">=")]
+    Ge,
+}
+
+impl Comparison {
+    fn holds(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Self::Is | Self::Eq => (lhs - rhs).abs() < f64::EPSILON,
+            Self::Lt => lhs < rhs,
+            Self::Le => lhs <= rhs,
+            Self::Gt => lhs > rhs,
+            Self::Ge => lhs >= rhs,
+        }
+    }
+}
+
+/// Numeric torrent property a query can compare against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Ratio,
+    Size,
+    Downloaded,
+    Uploaded,
+    Left,
+    /// Percent done, 0–100.
+    Progress,
+    DownSpeed,
+    UpSpeed,
+    Peers,
+    Seeds,
+    Leeches,
+    /// Time spent seeding, in seconds.
+    SeedingTime,
+}
+
+impl Metric {
+    fn parse(key: &str) -> Option<Self> {
+        Some(match key {
+            "ratio" => Self::Ratio,
+            "size" => Self::Size,
+            "downloaded" => Self::Downloaded,
+            "uploaded" => Self::Uploaded,
+            "left" => Self::Left,
+            "progress" => Self::Progress,
+            "downspeed" => Self::DownSpeed,
+            "upspeed" => Self::UpSpeed,
+            "peers" => Self::Peers,
+            "seeds" => Self::Seeds,
+            "leeches" => Self::Leeches,
+            "seedtime" => Self::SeedingTime,
+            _ => return None,
+        })
+    }
+
+    /// Field the metric is read from.
+    const fn field(self) -> TorrentGetField {
+        match self {
+            Self::Ratio => TorrentGetField::UploadRatio,
+            Self::Size => TorrentGetField::TotalSize,
+            Self::Downloaded => TorrentGetField::DownloadedEver,
+            Self::Uploaded => TorrentGetField::UploadedEver,
+            Self::Left => TorrentGetField::LeftUntilDone,
+            Self::Progress => TorrentGetField::PercentDone,
+            Self::DownSpeed => TorrentGetField::RateDownload,
+            Self::UpSpeed => TorrentGetField::RateUpload,
+            Self::Peers => TorrentGetField::PeersConnected,
+            Self::Seeds => TorrentGetField::PeersSendingToUs,
+            Self::Leeches => TorrentGetField::PeersGettingFromUs,
+            Self::SeedingTime => TorrentGetField::SecondsSeeding,
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn value(self, torrent: &Torrent) -> Option<f64> {
+        match self {
+            Self::Ratio => upload_ratio(torrent),
+            Self::Size => torrent.total_size.map(|v| v as f64),
+            Self::Downloaded => torrent.downloaded_ever.map(|v| v as f64),
+            Self::Uploaded => torrent.uploaded_ever.map(|v| v as f64),
+            Self::Left => torrent.left_until_done.map(|v| v as f64),
+            Self::Progress => torrent.percent_done.map(|v| f64::from(v) * 100.0),
+            Self::DownSpeed => torrent.rate_download.map(|v| v as f64),
+            Self::UpSpeed => torrent.rate_upload.map(|v| v as f64),
+            Self::Peers => torrent.peers_connected.map(|v| v as f64),
+            Self::Seeds => torrent.peers_sending_to_us.map(|v| v as f64),
+            Self::Leeches => torrent.peers_getting_from_us.map(|v| v as f64),
+            Self::SeedingTime => torrent.seconds_seeding.map(|v| v as f64),
+        }
+    }
+
+    fn parse_value(self, value: &str) -> Option<f64> {
+        match self {
+            Self::Size | Self::Downloaded | Self::Uploaded | Self::Left => parse_bytes(value),
+            Self::DownSpeed | Self::UpSpeed => parse_bytes(value.trim_end_matches("/s")),
+            Self::Progress => value.trim_end_matches('%').parse().ok(),
+            Self::SeedingTime => parse_duration(value),
+            Self::Ratio | Self::Peers | Self::Seeds | Self::Leeches => value.parse().ok(),
+        }
+        .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+    }
+
+    const fn expected(self) -> &'static str {
+        match self {
+            Self::Size | Self::Downloaded | Self::Uploaded | Self::Left => {
+                "expected a size such as 700M or 4G"
+            }
+            Self::DownSpeed | Self::UpSpeed => "expected a speed such as 500K or 2M",
+            Self::Progress => "expected a percentage such as 50 or 99.5%",
+            Self::Ratio => "expected a number such as 1 or 0.5",
+            Self::Peers | Self::Seeds | Self::Leeches => "expected a count",
+            Self::SeedingTime => "expected a duration such as 90m, 12h or 7d",
+        }
+    }
+}
+
+/// A single condition of a query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    Status(TorrentStatus),
+    /// Has a label equal to the value, ignoring case.
+    Label(String),
+    /// Has a tracker whose announce URL contains the value.
+    Tracker(String),
+    /// Name contains the value.
+    Name(String),
+    /// Download directory is the value or inside it.
+    Path(String),
+    /// Has a tracker or local error.
+    Error(bool),
+    Private(bool),
+    Compare(Metric, Comparison, f64),
+}
+
+impl Predicate {
+    /// Returns `true` if the torrent satisfies the predicate.
+    #[must_use]
+    pub fn matches(&self, torrent: &Torrent) -> bool {
+        match self {
+            Self::Status(status) => torrent.status == Some(*status),
+            Self::Label(label) => torrent
+                .labels
+                .as_deref()
+                .is_some_and(|labels| labels.iter().any(|l| l.eq_ignore_ascii_case(label))),
+            Self::Tracker(host) => tracker_urls(torrent).any(|url| contains(url, host)),
+            Self::Name(name) => torrent.name.as_deref().is_some_and(|n| contains(n, name)),
+            Self::Path(prefix) => torrent
+                .download_dir
+                .as_deref()
+                .is_some_and(|dir| Path::new(dir).starts_with(prefix)),
+            Self::Error(errored) => {
+                torrent.error.is_some_and(|e| !matches!(e, ErrorType::Ok)) == *errored
+            }
+            Self::Private(private) => torrent.is_private == Some(*private),
+            Self::Compare(metric, op, rhs) => {
+                metric.value(torrent).is_some_and(|lhs| op.holds(lhs, *rhs))
+            }
+        }
+    }
+
+    /// Fields the predicate reads.
+    #[must_use]
+    pub fn fields(&self) -> Vec<TorrentGetField> {
+        match self {
+            Self::Status(_) => vec![TorrentGetField::Status],
+            Self::Label(_) => vec![TorrentGetField::Labels],
+            // Older daemons lack `trackerList`; newer ones list spare trackers only there.
+            Self::Tracker(_) => vec![TorrentGetField::TrackerList, TorrentGetField::Trackers],
+            Self::Name(_) => vec![TorrentGetField::Name],
+            Self::Path(_) => vec![TorrentGetField::DownloadDir],
+            Self::Error(_) => vec![TorrentGetField::Error],
+            Self::Private(_) => vec![TorrentGetField::IsPrivate],
+            Self::Compare(metric, ..) => vec![metric.field()],
+        }
+    }
+}
+
+/// A predicate, optionally negated with a leading `-` or `!`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    pub negated: bool,
+    pub predicate: Predicate,
+}
+
+/// Parsed filter query.
+///
+/// Terms are whitespace-separated and must all match:
+///
+/// - `key:value` or `key<op>value` with `op` one of `= < <= > >=`, e.g.
+///   `status:seeding ratio<1 size>4G label:tv tracker:foo error:yes`
+/// - `"quoted text"` matches names containing the text
+/// - bare words are fuzzy-matched against the name, as a single pattern
+///
+/// Prefix a term with `-` or `!` to negate it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    pub filters: Vec<Filter>,
+    fuzzy: String,
+}
+
+impl Query {
+    /// Parses a query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first invalid term.
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut query = Self::default();
+        for token in tokenize(input)? {
+            let (negated, term) = match token.strip_prefix(['-', '!']) {
+                Some(rest) if !rest.is_empty() => (true, rest),
+                _ => (false, token.as_str()),
+            };
+            let predicate = match split_key(term) {
+                Some((key, op, value)) => parse_term(key, op, value)?,
+                None if term.starts_with('"') || negated => Predicate::Name(unquote(term)),
+                None => {
+                    if !query.fuzzy.is_empty() {
+                        query.fuzzy.push(' ');
+                    }
+                    query.fuzzy.push_str(term);
+                    continue;
+                }
+            };
+            query.filters.push(Filter { negated, predicate });
+        }
+        Ok(query)
+    }
+
+    /// Parses a query that must not contain fuzzy words, as used for saved
+    /// queries where there is no typed pattern to rank by.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first invalid term or bare word.
+    pub fn parse_strict(input: &str) -> Result<Self, QueryError> {
+        let query = Self::parse(input)?;
+        if let Some(word) = query.fuzzy.split_whitespace().next() {
+            return Err(QueryError::BareWord(word.to_owned()));
+        }
+        Ok(query)
+    }
+
+    /// Returns `true` if the torrent satisfies every non-fuzzy term.
+    #[must_use]
+    pub fn matches(&self, torrent: &Torrent) -> bool {
+        self.filters
+            .iter()
+            .all(|filter| filter.predicate.matches(torrent) != filter.negated)
+    }
+
+    /// Fields the query reads; bare words match against the name.
+    #[must_use]
+    pub fn fields(&self) -> Vec<TorrentGetField> {
+        let mut fields: Vec<_> = self
+            .filters
+            .iter()
+            .flat_map(|filter| filter.predicate.fields())
+            .collect();
+        if !self.fuzzy.is_empty() {
+            fields.push(TorrentGetField::Name);
+        }
+        fields
+    }
+
+    /// Returns the bare words to fuzzy-match names against, if any.
+    #[must_use]
+    pub fn fuzzy(&self) -> Option<&str> {
+        (!self.fuzzy.is_empty()).then_some(self.fuzzy.as_str())
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.filters.is_empty() && self.fuzzy.is_empty()
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Conditions a tab or rule picks torrents by. Each must hold; the lists
+/// match when any of their entries does, or when they are empty.
+#[derive(Debug, Clone, Default)]
+pub struct Criteria {
+    statuses: Vec<TorrentStatus>,
+    labels: Vec<Predicate>,
+    trackers: Vec<Predicate>,
+    query: Query,
+}
+
+impl Criteria {
+    #[must_use]
+    pub fn new(
+        statuses: Vec<TorrentStatus>,
+        labels: &[String],
+        trackers: &[String],
+        query: Query,
+    ) -> Self {
+        Self {
+            statuses,
+            labels: labels.iter().cloned().map(Predicate::Label).collect(),
+            trackers: trackers.iter().cloned().map(Predicate::Tracker).collect(),
+            query,
+        }
+    }
+
+    /// Returns `true` if the torrent meets every condition.
+    #[must_use]
+    pub fn matches(&self, torrent: &Torrent) -> bool {
+        let any = |predicates: &[Predicate]| {
+            predicates.is_empty() || predicates.iter().any(|p| p.matches(torrent))
+        };
+        let status = self.statuses.is_empty()
+            || torrent
+                .status
+                .is_some_and(|status| self.statuses.contains(&status));
+        status && any(&self.labels) && any(&self.trackers) && self.query.matches(torrent)
+    }
+
+    /// Fields the conditions read.
+    #[must_use]
+    pub fn fields(&self) -> Vec<TorrentGetField> {
+        let mut fields = Vec::new();
+        if !self.statuses.is_empty() {
+            fields.push(TorrentGetField::Status);
+        }
+        fields.extend(
+            self.labels
+                .iter()
+                .chain(&self.trackers)
+                .flat_map(Predicate::fields),
+        );
+        fields.extend(self.query.fields());
+        fields
+    }
+}
+
+/// Splits on whitespace outside double quotes, keeping the quotes.
+fn tokenize(input: &str) -> Result<Vec<String>, QueryError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => {
+                quoted = !quoted;
+                current.push(ch);
+            }
+            ch if ch.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            ch => current.push(ch),
+        }
+    }
+    if quoted {
+        return Err(QueryError::UnterminatedQuote);
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Splits `key<op>value`, returning `None` for terms without a key.
+fn split_key(term: &str) -> Option<(&str, Comparison, &str)> {
+    let key_len = term
+        .find(|c: char| !c.is_ascii_alphabetic() && c != '_')
+        .filter(|&len| len > 0)?;
+    let (key, rest) = term.split_at(key_len);
+    let (op, value) = [
+        ("<=", Comparison::Le),
+        (">=", Comparison::Ge),
+        ("<", Comparison::Lt),
+        (">", Comparison::Gt),
+        ("=", Comparison::Eq),
+        (":", Comparison::Is),
+    ]
+    .into_iter()
+    .find_map(|(symbol, op)| rest.strip_prefix(symbol).map(|value| (op, value)))?;
+    Some((key, op, value))
+}
+
+fn parse_term(key: &str, op: Comparison, value: &str) -> Result<Predicate, QueryError> {
+    let key = key.to_lowercase();
+    let value = unquote(value);
+    if value.is_empty() {
+        return Err(QueryError::MissingValue(key));
+    }
+    let invalid = |expected| QueryError::InvalidValue {
+        key: key.clone(),
+        value: value.clone(),
+        expected,
+    };
+
+    if let Some(metric) = Metric::parse(&key) {
+        let rhs = metric
+            .parse_value(&value)
+            .ok_or_else(|| invalid(metric.expected()))?;
+        return Ok(Predicate::Compare(metric, op, rhs));
+    }
+
+    if !matches!(op, Comparison::Is | Comparison::Eq) {
+        return match key.as_str() {
+            "status" | "label" | "tracker" | "name" | "path" | "error" | "private" => {
+                Err(QueryError::UnsupportedOperator { key, op })
+            }
+            _ => Err(QueryError::UnknownKey(key)),
+        };
+    }
+
+    Ok(match key.as_str() {
+        "status" => Predicate::Status(
+            parse_status(&value).ok_or_else(|| invalid("expected a torrent status"))?,
+        ),
+        "label" => Predicate::Label(value),
+        "tracker" => Predicate::Tracker(value),
+        "name" => Predicate::Name(value),
+        "path" => Predicate::Path(value),
+        "error" => {
+            Predicate::Error(parse_bool(&value).ok_or_else(|| invalid("expected yes or no"))?)
+        }
+        "private" => {
+            Predicate::Private(parse_bool(&value).ok_or_else(|| invalid("expected yes or no"))?)
+        }
+        _ => return Err(QueryError::UnknownKey(key)),
+    })
+}
+
+fn unquote(value: &str) -> String {
+    value.replace('"', "")
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" | "y" | "true" | "1" => Some(true),
+        "no" | "n" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Parses a byte count with an optional binary suffix (`K`, `M`, `G`, `T`),
+/// optionally followed by `B` or `iB`.
+fn parse_bytes(value: &str) -> Option<f64> {
+    let upper = value.to_uppercase();
+    let digits = upper
+        .trim_end_matches("IB")
+        .trim_end_matches('B')
+        .trim_end();
+    let (number, exponent) = match digits.char_indices().last()? {
+        (idx, 'K') => (&digits[..idx], 1),
+        (idx, 'M') => (&digits[..idx], 2),
+        (idx, 'G') => (&digits[..idx], 3),
+        (idx, 'T') => (&digits[..idx], 4),
+        _ => (digits, 0),
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .map(|n| n * 1024_f64.powi(exponent))
+}
+
+/// Parses a duration in seconds with an optional unit suffix (`s`, `m`, `h`,
+/// `d`, `w`).
+pub(crate) fn parse_duration(value: &str) -> Option<f64> {
+    let value = value.trim().to_lowercase();
+    let (number, unit) = match value.char_indices().last()? {
+        (idx, 's') => (&value[..idx], 1.0),
+        (idx, 'm') => (&value[..idx], 60.0),
+        (idx, 'h') => (&value[..idx], 3600.0),
+        (idx, 'd') => (&value[..idx], 86_400.0),
+        (idx, 'w') => (&value[..idx], 604_800.0),
+        _ => (value.as_str(), 1.0),
+    };
+    number.trim().parse::<f64>().ok().map(|n| n * unit)
+}
+
+fn tracker_urls(torrent: &Torrent) -> impl Iterator<Item = &str> {
+    let listed = torrent.tracker_list.as_deref().unwrap_or_default().lines();
+    let announced = torrent
+        .trackers
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|tracker| tracker.announce.as_str());
+    listed.chain(announced)
+}
+
+fn contains(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Comparison, Filter, Metric, Predicate, Query, QueryError, parse_bytes, parse_duration,
+    };
+    use crate::app::test_utils::from_json;
+    use serde_json::json;
+    use transmission_rpc::types::{Torrent, TorrentStatus};
+
+    #[test]
+    fn parses_keyed_terms_and_fuzzy_words() {
+        let query = match Query::parse(
+            r#"status:seeding ratio<1 size>4G -label:tv "partial name" ubuntu iso"#,
+        ) {
+            Ok(query) => query,
+            Err(err) => panic!("query should parse: {err}"),
+        };
+        assert_eq!(
+            query.filters,
+            vec![
+                Filter {
+                    negated: false,
+                    predicate: Predicate::Status(TorrentStatus::Seeding),
+                },
+                Filter {
+                    negated: false,
+                    predicate: Predicate::Compare(Metric::Ratio, Comparison::Lt, 1.0),
+                },
+                Filter {
+                    negated: false,
+                    predicate: Predicate::Compare(
+                        Metric::Size,
+                        Comparison::Gt,
+                        4.0 * 1024.0 * 1024.0 * 1024.0
+                    ),
+                },
+                Filter {
+                    negated: true,
+                    predicate: Predicate::Label("tv".into()),
+                },
+                Filter {
+                    negated: false,
+                    predicate: Predicate::Name("partial name".into()),
+                },
+            ]
+        );
+        assert_eq!(query.fuzzy(), Some("ubuntu iso"));
+    }
+
+    #[test]
+    fn reports_invalid_terms() {
+        assert_eq!(
+            Query::parse("colour:red"),
+            Err(QueryError::UnknownKey("colour".into()))
+        );
+        assert_eq!(
+            Query::parse("label>tv"),
+            Err(QueryError::UnsupportedOperator {
+                key: "label".into(),
+                op: Comparison::Gt
+            })
+        );
+        assert_eq!(
+            Query::parse("ratio<"),
+            Err(QueryError::MissingValue("ratio".into()))
+        );
+        assert_eq!(Query::parse("\"open"), Err(QueryError::UnterminatedQuote));
+        assert!(matches!(
+            Query::parse("size>big"),
+            Err(QueryError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn path_matches_whole_directory_components() {
+        let torrent = from_json(json!({ "downloadDir": "/mnt/media2/tv" }));
+        let matches = |prefix: &str| Predicate::Path(prefix.into()).matches(&torrent);
+        assert!(!matches("/mnt/media"));
+        assert!(matches("/mnt/media2"));
+        assert!(matches("/mnt/media2/"));
+        assert!(matches("/mnt/media2/tv"));
+    }
+
+    #[test]
+    fn byte_suffixes_are_binary() {
+        assert_eq!(parse_bytes("512"), Some(512.0));
+        assert_eq!(parse_bytes("1.5k"), Some(1536.0));
+        assert_eq!(parse_bytes("2MiB"), Some(2.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_bytes("4 GB"), Some(4.0 * 1024.0 * 1024.0 * 1024.0));
+        assert_eq!(parse_bytes("G"), None);
+    }
+
+    #[test]
+    fn durations_take_unit_suffixes() {
+        assert_eq!(parse_duration("90"), Some(90.0));
+        assert_eq!(parse_duration("90m"), Some(5400.0));
+        assert_eq!(parse_duration("1.5h"), Some(5400.0));
+        assert_eq!(parse_duration("7D"), Some(604_800.0));
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(
+            Query::parse("seedtime>=2w").map(|query| query.filters[0].predicate.clone()),
+            Ok(Predicate::Compare(
+                Metric::SeedingTime,
+                Comparison::Ge,
+                1_209_600.0
+            ))
+        );
+    }
+
+    fn ratio_is(op: Comparison, limit: f64, torrent: &Torrent) -> bool {
+        Predicate::Compare(Metric::Ratio, op, limit).matches(torrent)
+    }
+
+    #[test]
+    fn unavailable_ratio_matches_no_comparison() {
+        let torrent = from_json(json!({ "uploadRatio": -1 }));
+        assert!(!ratio_is(Comparison::Ge, 0.0, &torrent));
+        assert!(!ratio_is(Comparison::Lt, 2.0, &torrent));
+    }
+
+    #[test]
+    fn infinite_ratio_is_above_any_limit() {
+        let torrent = from_json(json!({ "uploadRatio": -2 }));
+        assert!(ratio_is(Comparison::Ge, 1000.0, &torrent));
+        assert!(!ratio_is(Comparison::Lt, 2.0, &torrent));
+    }
+}
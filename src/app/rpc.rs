@@ -0,0 +1,87 @@
+use crate::error::{Result, TraxorError};
+use reqwest::{Client, StatusCode, header::HeaderValue};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Value, json};
+use transmission_rpc::types::BasicAuth;
+use url::Url;
+
+const SESSION_ID_HEADER: &str = "X-Transmission-Session-Id";
+
+/// Minimal JSON-RPC client for methods and fields `transmission-rpc` does not
+/// expose, such as the speed limit settings returned by `session-get`.
+#[derive(Debug, Clone)]
+pub struct RpcClient {
+    http: Client,
+    url: Url,
+    auth: Option<BasicAuth>,
+    session_id: Option<HeaderValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Response<T> {
+    result: String,
+    arguments: Option<T>,
+}
+
+impl RpcClient {
+    #[must_use]
+    pub const fn new(http: Client, url: Url, auth: Option<BasicAuth>) -> Self {
+        Self {
+            http,
+            url,
+            auth,
+            session_id: None,
+        }
+    }
+
+    /// Calls `method` with `arguments` and deserializes the response arguments.
+    ///
+    /// Retries once when the daemon asks for a new session id.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the daemon reports an error.
+    pub async fn call<T: DeserializeOwned>(&mut self, method: &str, arguments: Value) -> Result<T> {
+        let body = json!({ "method": method, "arguments": arguments }).to_string();
+        let mut response = self.send(body.clone()).await?;
+        if response.status() == StatusCode::CONFLICT {
+            self.session_id = response.headers().get(SESSION_ID_HEADER).cloned();
+            response = self.send(body).await?;
+        }
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Err(TraxorError::Unauthorized);
+        }
+        if !status.is_success() {
+            return Err(TraxorError::TransmissionRpc(format!(
+                "{method} failed with HTTP {status}"
+            )));
+        }
+
+        let text = response.text().await?;
+        let response = serde_json::from_str::<Response<T>>(&text).map_err(|err| {
+            TraxorError::TransmissionRpc(format!("invalid {method} response: {err}"))
+        })?;
+        if response.result != "success" {
+            return Err(TraxorError::TransmissionRpc(response.result));
+        }
+        response.arguments.ok_or_else(|| {
+            TraxorError::TransmissionRpc(format!("{method} response has no arguments"))
+        })
+    }
+
+    async fn send(&self, body: String) -> Result<reqwest::Response> {
+        let mut request = self
+            .http
+            .post(self.url.clone())
+            .header("Content-Type", "application/json")
+            .body(body);
+        if let Some(auth) = &self.auth {
+            request = request.basic_auth(&auth.user, Some(&auth.password));
+        }
+        if let Some(session_id) = &self.session_id {
+            request = request.header(SESSION_ID_HEADER, session_id.clone());
+        }
+        Ok(request.send().await?)
+    }
+}
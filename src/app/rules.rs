@@ -0,0 +1,407 @@
+use super::{query::Criteria, store::TorrentStore};
+use crate::config::rules::{AutomationConfig, RuleAction, RuleConfig};
+use chrono::Local;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{OpenOptions, create_dir_all},
+    io::{self, Write},
+    path::PathBuf,
+};
+use transmission_rpc::types::{Priority, Torrent, TorrentGetField};
+
+/// A configured rule, parsed.
+#[derive(Debug, Clone)]
+struct Rule {
+    name: String,
+    action: RuleAction,
+    dry_run: bool,
+    criteria: Criteria,
+}
+
+impl Rule {
+    fn new(config: &RuleConfig, dry_run: bool) -> Self {
+        Self {
+            name: config.name.clone(),
+            action: config.action(),
+            dry_run: dry_run || config.dry_run,
+            criteria: Criteria::new(
+                config.statuses(),
+                &config.labels,
+                &config.trackers,
+                config.query(),
+            ),
+        }
+    }
+
+    fn fields(&self) -> Vec<TorrentGetField> {
+        let mut fields = self.criteria.fields();
+        if matches!(self.action, RuleAction::Label(_)) {
+            fields.push(TorrentGetField::Labels);
+        }
+        fields
+    }
+}
+
+/// Torrents a rule started matching in one evaluation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Firing {
+    pub rule: String,
+    pub action: RuleAction,
+    /// Only log what would happen.
+    pub dry_run: bool,
+    /// Ids and names of the matched torrents.
+    pub torrents: Vec<(i64, String)>,
+    /// Position of the rule in the list.
+    index: usize,
+}
+
+impl Firing {
+    /// Ids of the matched torrents.
+    #[must_use]
+    pub fn ids(&self) -> Vec<i64> {
+        self.torrents.iter().map(|&(id, _)| id).collect()
+    }
+
+    /// One-line summary for notifications.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let names = match self.torrents.as_slice() {
+            [(_, name)] => name.clone(),
+            torrents => format!("{} torrents", torrents.len()),
+        };
+        let verb = describe(&self.action);
+        if self.dry_run {
+            format!("Rule {:?} would {verb}: {names}", self.rule)
+        } else {
+            format!("Rule {:?}: {verb}: {names}", self.rule)
+        }
+    }
+}
+
+fn describe(action: &RuleAction) -> String {
+    match action {
+        RuleAction::Stop => "stop".into(),
+        RuleAction::Remove {
+            delete_local_data: false,
+        } => "remove".into(),
+        RuleAction::Remove {
+            delete_local_data: true,
+        } => "remove with data".into(),
+        RuleAction::Move(location) => format!("move to {location}"),
+        RuleAction::Label(label) => format!("label {label:?}"),
+        RuleAction::Priority(priority) => format!(
+            "set priority {}",
+            match priority {
+                Priority::Low => "low",
+                Priority::Normal => "normal",
+                Priority::High => "high",
+            }
+        ),
+    }
+}
+
+/// Appends what the rules did to a file.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    #[must_use]
+    pub const fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+
+    /// Writes one line per matched torrent, ending in `error` if the action
+    /// failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log file cannot be created or written.
+    pub fn record(&self, firing: &Firing, error: Option<&str>) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
+        let mode = if firing.dry_run { " [dry run]" } else { "" };
+        let verb = describe(&firing.action);
+        let outcome = error
+            .map(|err| format!(" failed: {err}"))
+            .unwrap_or_default();
+        for (id, name) in &firing.torrents {
+            writeln!(
+                file,
+                "{timestamp}{mode} rule {:?}: {verb}: {name} (#{id}){outcome}",
+                firing.rule
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Housekeeping rules evaluated on every update.
+///
+/// A rule fires once for a torrent when the torrent starts matching it, and
+/// again only after it stopped matching in between, so actions that leave a
+/// torrent matching (or dry runs) are not repeated on every update.
+#[derive(Debug, Default)]
+pub struct Rules {
+    list: Vec<Rule>,
+    /// Rule positions and ids of torrents the rule already fired for.
+    fired: HashSet<(usize, i64)>,
+    /// Firings whose action is still running, by job id.
+    running: HashMap<u64, Firing>,
+    pub audit: AuditLog,
+}
+
+impl Rules {
+    #[must_use]
+    pub fn new(rules: &[RuleConfig], automation: &AutomationConfig) -> Self {
+        Self {
+            list: rules
+                .iter()
+                .map(|rule| Rule::new(rule, automation.dry_run))
+                .collect(),
+            fired: HashSet::new(),
+            running: HashMap::new(),
+            audit: AuditLog::new(automation.audit_log_path()),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Fields the rules read, and names for the audit log.
+    #[must_use]
+    pub fn fields(&self) -> Vec<TorrentGetField> {
+        if self.list.is_empty() {
+            return Vec::new();
+        }
+        let mut fields = vec![TorrentGetField::Name];
+        fields.extend(self.list.iter().flat_map(Rule::fields));
+        fields
+    }
+
+    /// Remembers the firing whose action runs as job `id`.
+    pub fn start(&mut self, id: u64, firing: Firing) {
+        self.running.insert(id, firing);
+    }
+
+    /// Returns the firing whose action ran as job `id`, if any.
+    pub fn finish(&mut self, id: u64) -> Option<Firing> {
+        self.running.remove(&id)
+    }
+
+    /// Lets a firing whose action failed fire again on the next evaluation
+    /// if its torrents still match.
+    pub fn retry(&mut self, firing: &Firing) {
+        let ids = firing.ids();
+        self.fired
+            .retain(|&(idx, id)| idx != firing.index || !ids.contains(&id));
+    }
+
+    /// Runs the rules in order against the torrents and returns what newly
+    /// fired. Torrents a rule removes are skipped by the rules after it.
+    pub fn evaluate(&mut self, torrents: &TorrentStore) -> Vec<Firing> {
+        let mut removed: HashSet<i64> = HashSet::new();
+        let mut firings = Vec::new();
+        for (idx, rule) in self.list.iter().enumerate() {
+            let matching: Vec<&Torrent> = torrents
+                .iter()
+                .filter(|t| t.id.is_some_and(|id| !removed.contains(&id)))
+                .filter(|t| rule.criteria.matches(t))
+                .collect();
+            let ids: HashSet<i64> = matching.iter().filter_map(|t| t.id).collect();
+            // Until the daemon drops them, removed torrents still show up.
+            if matches!(rule.action, RuleAction::Remove { .. }) && !rule.dry_run {
+                removed.extend(&ids);
+            }
+            self.fired
+                .retain(|&(fired, id)| fired != idx || ids.contains(&id));
+
+            let fresh: Vec<(i64, String)> = matching
+                .iter()
+                .filter_map(|t| t.id.map(|id| (id, t.name.clone().unwrap_or_default())))
+                .filter(|&(id, _)| self.fired.insert((idx, id)))
+                .collect();
+            if fresh.is_empty() {
+                continue;
+            }
+            firings.push(Firing {
+                rule: rule.name.clone(),
+                action: rule.action.clone(),
+                dry_run: rule.dry_run,
+                torrents: fresh,
+                index: idx,
+            });
+        }
+        firings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditLog, Rules};
+    use crate::app::test_utils::from_json;
+    use crate::{
+        app::store::TorrentStore,
+        config::rules::{AutomationConfig, RuleAction, RuleConfig},
+    };
+    use serde_json::json;
+    use transmission_rpc::types::Torrent;
+
+    fn torrent(id: i64, status: u8, ratio: f32, seeding: i64) -> Torrent {
+        from_json(json!({
+            "id": id,
+            "name": format!("t{id}"),
+            "status": status,
+            "uploadRatio": ratio,
+            "secondsSeeding": seeding,
+            "labels": ["tv"],
+        }))
+    }
+
+    fn rule(toml: &str) -> RuleConfig {
+        let Ok(rule) = toml::from_str::<RuleConfig>(toml) else {
+            panic!("rule should deserialize");
+        };
+        if let Err(err) = rule.validate(0) {
+            panic!("rule should be valid: {err}");
+        }
+        rule
+    }
+
+    #[test]
+    fn rules_fire_once_per_torrent_until_it_stops_matching() {
+        let rules = [
+            rule(
+                r#"
+                name = "Done seeding"
+                statuses = ["seeding"]
+                min_ratio = 2.0
+                min_seeding_time = "1d"
+                action = "remove"
+                "#,
+            ),
+            rule(
+                r#"
+                name = "Tag"
+                labels = ["TV"]
+                action = "label"
+                label = "seen"
+                "#,
+            ),
+        ];
+        let mut engine = Rules::new(&rules, &AutomationConfig::default());
+        let store = TorrentStore::from(vec![
+            torrent(1, 6, 2.5, 90_000),
+            torrent(2, 6, 2.5, 3_600),
+            torrent(3, 0, 3.0, 90_000),
+        ]);
+
+        let firings = engine.evaluate(&store);
+        assert_eq!(firings.len(), 2);
+        assert_eq!(firings[0].ids(), [1]);
+        assert_eq!(firings[0].summary(), "Rule \"Done seeding\": remove: t1");
+        // The removed torrent is left alone by later rules.
+        assert_eq!(firings[1].ids(), [2, 3]);
+        assert!(engine.evaluate(&store).is_empty());
+
+        let store = TorrentStore::from(vec![torrent(2, 6, 2.5, 3_600)]);
+        assert!(engine.evaluate(&store).is_empty());
+        let store = TorrentStore::from(vec![torrent(2, 6, 2.5, 90_000)]);
+        let firings = engine.evaluate(&store);
+        assert_eq!(firings[0].ids(), [2]);
+    }
+
+    #[test]
+    fn failed_actions_fire_again() {
+        let rules = [
+            rule("name = \"Stop\"\naction = \"stop\""),
+            rule("name = \"Low\"\naction = \"priority\"\npriority = \"low\""),
+        ];
+        let mut engine = Rules::new(&rules, &AutomationConfig::default());
+        let store = TorrentStore::from(vec![torrent(1, 6, 0.0, 0), torrent(2, 6, 0.0, 0)]);
+
+        let firings = engine.evaluate(&store);
+        assert_eq!(firings.len(), 2);
+        engine.start(7, firings[0].clone());
+        let Some(failed) = engine.finish(7) else {
+            panic!("the running firing should be returned");
+        };
+        engine.retry(&failed);
+        let firings = engine.evaluate(&store);
+        assert_eq!(firings.len(), 1);
+        assert_eq!(firings[0].rule, "Stop");
+        assert_eq!(firings[0].ids(), [1, 2]);
+        assert!(engine.evaluate(&store).is_empty());
+    }
+
+    #[test]
+    fn dry_run_keeps_later_rules_and_writes_the_audit_log() {
+        let rules = [
+            rule("name = \"Errored\"\nerror = false\naction = \"remove-with-data\""),
+            rule("name = \"Low\"\naction = \"priority\"\npriority = \"LOW\""),
+        ];
+        let path = std::env::temp_dir().join(format!("traxor-rules-{}.log", std::process::id()));
+        let automation = AutomationConfig {
+            dry_run: true,
+            audit_log: Some(path.clone()),
+        };
+        let mut engine = Rules::new(&rules, &automation);
+        let store = TorrentStore::from(vec![torrent(1, 6, 0.0, 0)]);
+
+        let firings = engine.evaluate(&store);
+        assert_eq!(firings.len(), 2);
+        assert!(firings.iter().all(|firing| firing.dry_run));
+        assert_eq!(
+            firings[0].summary(),
+            "Rule \"Errored\" would remove with data: t1"
+        );
+        for firing in &firings {
+            assert!(engine.audit.record(firing, None).is_ok());
+        }
+        assert!(engine.audit.record(&firings[1], Some("refused")).is_ok());
+        let log = std::fs::read_to_string(&path).unwrap_or_default();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].ends_with("[dry run] rule \"Low\": set priority low: t1 (#1)"));
+        assert!(lines[2].ends_with("(#1) failed: refused"));
+        assert!(AuditLog::default().record(&firings[0], None).is_ok());
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        let err = |toml: &str| {
+            toml::from_str::<RuleConfig>(toml)
+                .map_err(|err| err.to_string())
+                .and_then(|rule| rule.validate(1))
+                .err()
+                .unwrap_or_default()
+        };
+        assert_eq!(
+            err("name = \"x\"\naction = \"move\""),
+            "invalid action \"move\"; it needs `location` in rules[1] (\"x\")"
+        );
+        assert!(err("name = \"x\"\naction = \"explode\"").contains("expected \"stop\""));
+        assert!(
+            err("name = \"x\"\naction = \"stop\"\nmin_seeding_time = \"soon\"")
+                .contains("min_seeding_time")
+        );
+        assert!(
+            err("name = \"x\"\naction = \"priority\"\npriority = \"urgent\"")
+                .contains("priority \"urgent\"")
+        );
+        assert_eq!(
+            rule("name = \"x\"\naction = \"Move\"\nlocation = \" /done \"").action(),
+            RuleAction::Move("/done".into())
+        );
+    }
+}
@@ -0,0 +1,572 @@
+use super::input::InputHandler;
+use crate::error::Result;
+use derive_more::Display;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+/// Daemon setting shown on the settings screen, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SettingField {
+    DownloadDir,
+    IncompleteDirEnabled,
+    IncompleteDir,
+    PeerLimitGlobal,
+    PeerLimitPerTorrent,
+    DownloadQueueEnabled,
+    DownloadQueueSize,
+    SeedQueueEnabled,
+    SeedQueueSize,
+    SeedRatioLimited,
+    SeedRatioLimit,
+    IdleSeedingLimitEnabled,
+    IdleSeedingLimit,
+    PeerPort,
+    Encryption,
+    Dht,
+    Pex,
+    Lpd,
+    Utp,
+}
+
+/// How a setting is entered and validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKind {
+    /// Directory on the daemon's machine.
+    Path,
+    /// Non-negative whole number.
+    Count,
+    /// TCP/UDP port, 1-65535.
+    Port,
+    /// Non-negative decimal number.
+    Ratio,
+    Toggle,
+    Encryption,
+}
+
+impl SettingField {
+    pub const ALL: [Self; 19] = [
+        Self::DownloadDir,
+        Self::IncompleteDirEnabled,
+        Self::IncompleteDir,
+        Self::PeerLimitGlobal,
+        Self::PeerLimitPerTorrent,
+        Self::DownloadQueueEnabled,
+        Self::DownloadQueueSize,
+        Self::SeedQueueEnabled,
+        Self::SeedQueueSize,
+        Self::SeedRatioLimited,
+        Self::SeedRatioLimit,
+        Self::IdleSeedingLimitEnabled,
+        Self::IdleSeedingLimit,
+        Self::PeerPort,
+        Self::Encryption,
+        Self::Dht,
+        Self::Pex,
+        Self::Lpd,
+        Self::Utp,
+    ];
+
+    /// Argument name used by `session-get` and `session-set`.
+    #[must_use]
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::DownloadDir => "download-dir",
+            Self::IncompleteDirEnabled => "incomplete-dir-enabled",
+            Self::IncompleteDir => "incomplete-dir",
+            Self::PeerLimitGlobal => "peer-limit-global",
+            Self::PeerLimitPerTorrent => "peer-limit-per-torrent",
+            Self::DownloadQueueEnabled => "download-queue-enabled",
+            Self::DownloadQueueSize => "download-queue-size",
+            Self::SeedQueueEnabled => "seed-queue-enabled",
+            Self::SeedQueueSize => "seed-queue-size",
+            Self::SeedRatioLimited => "seedRatioLimited",
+            Self::SeedRatioLimit => "seedRatioLimit",
+            Self::IdleSeedingLimitEnabled => "idle-seeding-limit-enabled",
+            Self::IdleSeedingLimit => "idle-seeding-limit",
+            Self::PeerPort => "peer-port",
+            Self::Encryption => "encryption",
+            Self::Dht => "dht-enabled",
+            Self::Pex => "pex-enabled",
+            Self::Lpd => "lpd-enabled",
+            Self::Utp => "utp-enabled",
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::DownloadDir => "Download dir",
+            Self::IncompleteDirEnabled => "Use incomplete dir",
+            Self::IncompleteDir => "Incomplete dir",
+            Self::PeerLimitGlobal => "Peers (global)",
+            Self::PeerLimitPerTorrent => "Peers per torrent",
+            Self::DownloadQueueEnabled => "Download queue",
+            Self::DownloadQueueSize => "Active downloads",
+            Self::SeedQueueEnabled => "Seed queue",
+            Self::SeedQueueSize => "Active seeds",
+            Self::SeedRatioLimited => "Stop at ratio",
+            Self::SeedRatioLimit => "Seed ratio",
+            Self::IdleSeedingLimitEnabled => "Stop when idle",
+            Self::IdleSeedingLimit => "Idle minutes",
+            Self::PeerPort => "Peer port",
+            Self::Encryption => "Encryption",
+            Self::Dht => "DHT",
+            Self::Pex => "PEX",
+            Self::Lpd => "Local discovery",
+            Self::Utp => "uTP",
+        }
+    }
+
+    /// Heading the setting is grouped under.
+    #[must_use]
+    pub const fn section(self) -> &'static str {
+        match self {
+            Self::DownloadDir | Self::IncompleteDirEnabled | Self::IncompleteDir => "Locations",
+            Self::PeerLimitGlobal | Self::PeerLimitPerTorrent => "Peers",
+            Self::DownloadQueueEnabled
+            | Self::DownloadQueueSize
+            | Self::SeedQueueEnabled
+            | Self::SeedQueueSize => "Queue",
+            Self::SeedRatioLimited
+            | Self::SeedRatioLimit
+            | Self::IdleSeedingLimitEnabled
+            | Self::IdleSeedingLimit => "Seeding",
+            Self::PeerPort | Self::Encryption | Self::Dht | Self::Pex | Self::Lpd | Self::Utp => {
+                "Network"
+            }
+        }
+    }
+
+    #[must_use]
+    pub const fn kind(self) -> SettingKind {
+        match self {
+            Self::DownloadDir | Self::IncompleteDir => SettingKind::Path,
+            Self::PeerLimitGlobal
+            | Self::PeerLimitPerTorrent
+            | Self::DownloadQueueSize
+            | Self::SeedQueueSize
+            | Self::IdleSeedingLimit => SettingKind::Count,
+            Self::PeerPort => SettingKind::Port,
+            Self::SeedRatioLimit => SettingKind::Ratio,
+            Self::Encryption => SettingKind::Encryption,
+            Self::IncompleteDirEnabled
+            | Self::DownloadQueueEnabled
+            | Self::SeedQueueEnabled
+            | Self::SeedRatioLimited
+            | Self::IdleSeedingLimitEnabled
+            | Self::Dht
+            | Self::Pex
+            | Self::Lpd
+            | Self::Utp => SettingKind::Toggle,
+        }
+    }
+
+    /// Parses text entered for this setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the setting when the text is not a valid value
+    /// of its kind.
+    pub fn parse(self, text: &str) -> std::result::Result<SettingValue, String> {
+        let text = text.trim();
+        let label = self.label();
+        match self.kind() {
+            SettingKind::Path if text.is_empty() => Err(format!("{label}: must not be empty")),
+            SettingKind::Path => Ok(SettingValue::Text(text.into())),
+            SettingKind::Count => text
+                .parse()
+                .map(SettingValue::Count)
+                .map_err(|_| format!("{label}: expected a whole number")),
+            SettingKind::Port => match text.parse::<u16>() {
+                Ok(port) if port > 0 => Ok(SettingValue::Count(u64::from(port))),
+                _ => Err(format!("{label}: expected a port between 1 and 65535")),
+            },
+            SettingKind::Ratio => match text.parse::<f64>() {
+                Ok(ratio) if ratio.is_finite() && ratio >= 0.0 => Ok(SettingValue::Ratio(ratio)),
+                _ => Err(format!("{label}: expected a number such as 2 or 1.5")),
+            },
+            SettingKind::Toggle | SettingKind::Encryption => {
+                Err(format!("{label}: is not a text setting"))
+            }
+        }
+    }
+
+    fn read(self, value: &Value) -> Option<SettingValue> {
+        match self.kind() {
+            SettingKind::Path => value.as_str().map(|s| SettingValue::Text(s.into())),
+            SettingKind::Count | SettingKind::Port => value.as_u64().map(SettingValue::Count),
+            SettingKind::Ratio => value.as_f64().map(SettingValue::Ratio),
+            SettingKind::Toggle => value.as_bool().map(SettingValue::Toggle),
+            SettingKind::Encryption => match value.as_str()? {
+                "required" => Some(SettingValue::Encryption(Encryption::Required)),
+                "preferred" => Some(SettingValue::Encryption(Encryption::Preferred)),
+                "tolerated" => Some(SettingValue::Encryption(Encryption::Tolerated)),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Peer connection encryption policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum Encryption {
+    #[display("required")]
+    Required,
+    #[display("preferred")]
+    Preferred,
+    #[display("tolerated")]
+    Tolerated,
+}
+
+impl Encryption {
+    /// The next policy, wrapping around.
+    #[must_use]
+    pub const fn cycle(self, forward: bool) -> Self {
+        match (self, forward) {
+            (Self::Required, true) | (Self::Tolerated, false) => Self::Preferred,
+            (Self::Preferred, true) | (Self::Required, false) => Self::Tolerated,
+            (Self::Tolerated, true) | (Self::Preferred, false) => Self::Required,
+        }
+    }
+}
+
+/// Value of a daemon setting.
+#[derive(Debug, Clone, PartialEq, Display)]
+pub enum SettingValue {
+    #[display("{_0}")]
+    Text(String),
+    #[display("{_0}")]
+    Count(u64),
+    #[display("{_0}")]
+    Ratio(f64),
+    #[display("{}", if *_0 { "on" } else { "off" })]
+    Toggle(bool),
+    #[display("{_0}")]
+    Encryption(Encryption),
+}
+
+impl SettingValue {
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Text(text) => json!(text),
+            Self::Count(count) => json!(count),
+            Self::Ratio(ratio) => json!(ratio),
+            Self::Toggle(enabled) => json!(enabled),
+            Self::Encryption(encryption) => json!(encryption.to_string()),
+        }
+    }
+}
+
+/// Settings reported by the daemon. Settings an older daemon does not know
+/// are left out and cannot be edited.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DaemonSettings(BTreeMap<SettingField, SettingValue>);
+
+impl DaemonSettings {
+    /// `session-get` fields to request.
+    #[must_use]
+    pub fn keys() -> Vec<&'static str> {
+        SettingField::ALL.iter().map(|field| field.key()).collect()
+    }
+
+    /// Picks the known settings out of a `session-get` response.
+    #[must_use]
+    pub fn from_response(response: &Map<String, Value>) -> Self {
+        Self(
+            SettingField::ALL
+                .into_iter()
+                .filter_map(|field| {
+                    let value = field.read(response.get(field.key())?)?;
+                    Some((field, value))
+                })
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    pub fn get(&self, field: SettingField) -> Option<&SettingValue> {
+        self.0.get(&field)
+    }
+
+    /// Settings present, in display order.
+    pub fn fields(&self) -> impl Iterator<Item = SettingField> + '_ {
+        self.0.keys().copied()
+    }
+}
+
+/// A setting changed on the settings screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingChange {
+    pub field: SettingField,
+    pub old: SettingValue,
+    pub new: SettingValue,
+}
+
+/// `session-set` arguments applying `changes`.
+#[must_use]
+pub fn set_arguments(changes: &[SettingChange]) -> Value {
+    Value::Object(
+        changes
+            .iter()
+            .map(|change| (change.field.key().to_owned(), change.new.to_json()))
+            .collect(),
+    )
+}
+
+/// How a setting is edited on the form.
+#[derive(Debug)]
+enum Entry {
+    Input(InputHandler),
+    Value(SettingValue),
+}
+
+/// State of the settings screen: edits to the loaded settings and, after the
+/// first submit, the changes waiting for confirmation.
+#[derive(Debug)]
+pub struct SettingsForm {
+    original: DaemonSettings,
+    entries: BTreeMap<SettingField, Entry>,
+    focus: usize,
+    /// Changes shown for confirmation before they are applied.
+    pub preview: Option<Vec<SettingChange>>,
+    /// Validation error of the last submit.
+    pub error: Option<String>,
+}
+
+impl SettingsForm {
+    /// Creates a form prefilled with `settings`.
+    #[must_use]
+    pub fn new(settings: DaemonSettings) -> Self {
+        let entries = settings
+            .0
+            .iter()
+            .map(|(&field, value)| {
+                let entry = match value {
+                    SettingValue::Toggle(_) | SettingValue::Encryption(_) => {
+                        Entry::Value(value.clone())
+                    }
+                    _ => {
+                        let mut input = InputHandler::new();
+                        input.set_text(value.to_string());
+                        Entry::Input(input)
+                    }
+                };
+                (field, entry)
+            })
+            .collect();
+        Self {
+            original: settings,
+            entries,
+            focus: 0,
+            preview: None,
+            error: None,
+        }
+    }
+
+    /// Editable settings, in display order.
+    pub fn fields(&self) -> impl Iterator<Item = SettingField> + '_ {
+        self.original.fields()
+    }
+
+    /// The setting that has focus.
+    #[must_use]
+    pub fn focus(&self) -> Option<SettingField> {
+        self.fields().nth(self.focus)
+    }
+
+    /// Moves focus to the next setting, wrapping around.
+    pub fn next_field(&mut self) {
+        self.focus = (self.focus + 1) % self.entries.len().max(1);
+    }
+
+    /// Moves focus to the previous setting, wrapping around.
+    pub fn prev_field(&mut self) {
+        self.focus = self
+            .focus
+            .checked_sub(1)
+            .unwrap_or_else(|| self.entries.len().saturating_sub(1));
+    }
+
+    /// Text of a setting edited as text, or its value otherwise.
+    #[must_use]
+    pub fn display(&self, field: SettingField) -> String {
+        match self.entries.get(&field) {
+            Some(Entry::Input(input)) => input.text.clone(),
+            Some(Entry::Value(value)) => value.to_string(),
+            None => String::new(),
+        }
+    }
+
+    /// Returns the text input of the focused setting, if it is edited as text.
+    pub fn focused_input_mut(&mut self) -> Option<&mut InputHandler> {
+        if self.preview.is_some() {
+            return None;
+        }
+        let field = self.focus()?;
+        match self.entries.get_mut(&field) {
+            Some(Entry::Input(input)) => Some(input),
+            _ => None,
+        }
+    }
+
+    /// Returns the text input of the focused setting, if it is edited as text.
+    #[must_use]
+    pub fn focused_input(&self) -> Option<&InputHandler> {
+        match self.entries.get(&self.focus()?) {
+            Some(Entry::Input(input)) => Some(input),
+            _ => None,
+        }
+    }
+
+    /// Returns the text input of the focused setting if it is a directory.
+    pub fn focused_path_mut(&mut self) -> Option<&mut InputHandler> {
+        if self.focus()?.kind() != SettingKind::Path {
+            return None;
+        }
+        self.focused_input_mut()
+    }
+
+    /// Flips the focused toggle or steps through encryption policies.
+    pub fn cycle(&mut self, forward: bool) {
+        let Some(field) = self.focus() else {
+            return;
+        };
+        if let Some(Entry::Value(value)) = self.entries.get_mut(&field) {
+            match value {
+                SettingValue::Toggle(enabled) => *enabled = !*enabled,
+                SettingValue::Encryption(encryption) => *encryption = encryption.cycle(forward),
+                _ => {}
+            }
+        }
+    }
+
+    /// Validates the form and lists the settings that differ from the
+    /// loaded ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first setting with an invalid value.
+    pub fn changes(&self) -> std::result::Result<Vec<SettingChange>, String> {
+        let mut changes = Vec::new();
+        for (&field, entry) in &self.entries {
+            let new = match entry {
+                Entry::Input(input) => field.parse(&input.text)?,
+                Entry::Value(value) => value.clone(),
+            };
+            if let Some(old) = self.original.get(field)
+                && *old != new
+            {
+                changes.push(SettingChange {
+                    field,
+                    old: old.clone(),
+                    new,
+                });
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Completes the focused directory from the local file system.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read.
+    pub async fn complete(&mut self) -> Result<()> {
+        match self.focused_path_mut() {
+            Some(input) => input.complete().await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DaemonSettings, Encryption, SettingField, SettingValue, SettingsForm, set_arguments,
+    };
+    use serde_json::{Value, json};
+
+    fn settings() -> DaemonSettings {
+        let Value::Object(response) = json!({
+            "download-dir": "/downloads",
+            "peer-limit-global": 200,
+            "peer-port": 51413,
+            "seedRatioLimit": 2.0,
+            "encryption": "preferred",
+            "dht-enabled": true,
+            "version": "4.0.5",
+        }) else {
+            panic!("response should be an object");
+        };
+        DaemonSettings::from_response(&response)
+    }
+
+    #[test]
+    fn only_settings_the_daemon_reports_are_editable() {
+        let form = SettingsForm::new(settings());
+        let fields: Vec<_> = form.fields().collect();
+        assert_eq!(
+            fields,
+            [
+                SettingField::DownloadDir,
+                SettingField::PeerLimitGlobal,
+                SettingField::SeedRatioLimit,
+                SettingField::PeerPort,
+                SettingField::Encryption,
+                SettingField::Dht,
+            ]
+        );
+        assert_eq!(form.display(SettingField::SeedRatioLimit), "2");
+    }
+
+    #[test]
+    fn changes_list_edited_settings_and_build_session_set_arguments() {
+        let mut form = SettingsForm::new(settings());
+        let Ok(changes) = form.changes() else {
+            panic!("prefilled settings should be valid");
+        };
+        assert!(changes.is_empty());
+
+        form.next_field();
+        if let Some(input) = form.focused_input_mut() {
+            input.set_text("300".into());
+        }
+        form.prev_field();
+        form.prev_field();
+        form.cycle(true);
+        form.prev_field();
+        form.cycle(true);
+        let Ok(changes) = form.changes() else {
+            panic!("edited settings should be valid");
+        };
+        let edited: Vec<_> = changes.iter().map(|c| (c.field, c.new.clone())).collect();
+        assert_eq!(
+            edited,
+            [
+                (SettingField::PeerLimitGlobal, SettingValue::Count(300)),
+                (
+                    SettingField::Encryption,
+                    SettingValue::Encryption(Encryption::Tolerated)
+                ),
+                (SettingField::Dht, SettingValue::Toggle(false)),
+            ]
+        );
+        assert_eq!(
+            set_arguments(&changes),
+            json!({ "peer-limit-global": 300, "encryption": "tolerated", "dht-enabled": false })
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_rejected_by_kind() {
+        assert!(SettingField::PeerPort.parse("0").is_err());
+        assert!(SettingField::PeerPort.parse("70000").is_err());
+        assert!(SettingField::SeedRatioLimit.parse("-1").is_err());
+        assert!(SettingField::PeerLimitGlobal.parse("many").is_err());
+        assert!(SettingField::DownloadDir.parse("  ").is_err());
+        assert_eq!(
+            SettingField::SeedRatioLimit.parse("1.5"),
+            Ok(SettingValue::Ratio(1.5))
+        );
+    }
+}
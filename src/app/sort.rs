@@ -0,0 +1,251 @@
+use super::utils::upload_ratio;
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use std::cmp::Ordering;
+use transmission_rpc::types::{
+    ErrorType, IdleMode, Priority, RatioMode, Torrent, TorrentGetField, TorrentStatus,
+};
+
+/// Direction a column is sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display)]
+pub enum SortDirection {
+    #[default]
+    #[display("asc")]
+    Ascending,
+    #[display("desc")]
+    Descending,
+}
+
+impl SortDirection {
+    #[must_use]
+    pub const fn reversed(self) -> Self {
+        match self {
+            Self::Ascending => Self::Descending,
+            Self::Descending => Self::Ascending,
+        }
+    }
+
+    /// Arrow shown next to the sorted column header.
+    #[must_use]
+    pub const fn indicator(self) -> &'static str {
+        match self {
+            Self::Ascending => "▲",
+            Self::Descending => "▼",
+        }
+    }
+}
+
+/// Column and direction the torrent table is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortOrder {
+    pub field: TorrentGetField,
+    pub direction: SortDirection,
+}
+
+impl SortOrder {
+    #[must_use]
+    pub const fn new(field: TorrentGetField, direction: SortDirection) -> Self {
+        Self { field, direction }
+    }
+
+    /// Compares two torrents by the sorted column.
+    ///
+    /// Torrents missing the value always sort last, whatever the direction.
+    #[must_use]
+    pub fn compare(&self, a: &Torrent, b: &Torrent) -> Ordering {
+        match (SortKey::of(self.field, a), SortKey::of(self.field, b)) {
+            (Some(a), Some(b)) => {
+                let ordering = a.cmp(&b);
+                match self.direction {
+                    SortDirection::Ascending => ordering,
+                    SortDirection::Descending => ordering.reverse(),
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+/// Typed value of a column, so sizes, speeds, dates and ratios compare
+/// numerically instead of by their formatted text.
+#[derive(Debug, PartialEq)]
+enum SortKey {
+    Int(i128),
+    Float(f64),
+    Text(String),
+}
+
+impl Eq for SortKey {}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => a.cmp(b),
+            (Self::Float(a), Self::Float(b)) => a.total_cmp(b),
+            (Self::Text(a), Self::Text(b)) => a.cmp(b),
+            // A field always maps to the same variant.
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+impl SortKey {
+    #[allow(clippy::too_many_lines)]
+    fn of(field: TorrentGetField, torrent: &Torrent) -> Option<Self> {
+        type F = TorrentGetField;
+        let float = |value: Option<f32>| value.map(|v| Self::Float(f64::from(v)));
+        let text = |value: Option<&str>| value.map(|v| Self::Text(v.to_lowercase()));
+        let date = |value: Option<DateTime<Utc>>| {
+            value
+                .filter(|&v| v != DateTime::<Utc>::UNIX_EPOCH)
+                .map(|v| Self::Int(v.timestamp().into()))
+        };
+        let flag = |value: Option<bool>| value.map(|v| Self::Int(v.into()));
+        let count = |len: Option<usize>| len.map(|v| Self::Int(v.try_into().unwrap_or(i128::MAX)));
+
+        match field {
+            F::ActivityDate => date(torrent.activity_date),
+            F::AddedDate => date(torrent.added_date),
+            F::DateCreated => date(torrent.date_created),
+            F::DoneDate => date(torrent.done_date),
+            F::EditDate => date(torrent.edit_date),
+            F::ManualAnnounceTime => date(torrent.manual_announce_time),
+            F::StartDate => date(torrent.start_date),
+
+            F::CorruptEver => torrent.corrupt_ever.map(int),
+            F::DesiredAvailable => torrent.desired_available.map(int),
+            F::DownloadedEver => torrent.downloaded_ever.map(int),
+            F::HaveUnchecked => torrent.have_unchecked.map(int),
+            F::HaveValid => torrent.have_valid.map(int),
+            F::LeftUntilDone => torrent.left_until_done.map(int),
+            F::PieceSize => torrent.piece_size.map(int),
+            F::SizeWhenDone => torrent.size_when_done.map(int),
+            F::TotalSize => torrent.total_size.map(int),
+            F::UploadedEver => torrent.uploaded_ever.map(int),
+            F::RateDownload => torrent.rate_download.map(int),
+            F::RateUpload => torrent.rate_upload.map(int),
+            F::DownloadLimit => torrent.download_limit.map(int),
+            F::UploadLimit => torrent.upload_limit.map(int),
+
+            // Negative values mean "not available" or "unknown".
+            F::Eta => torrent.eta.filter(|&v| v >= 0).map(int),
+            F::EtaIdle => torrent.eta_idle.filter(|&v| v >= 0).map(int),
+
+            F::Id => torrent.id.map(int),
+            F::FileCount => count(torrent.file_count),
+            F::MaxConnectedPeers => torrent.max_connected_peers.map(int),
+            F::PeerLimit => torrent.peer_limit.map(int),
+            F::PeersConnected => torrent.peers_connected.map(int),
+            F::PeersGettingFromUs => torrent.peers_getting_from_us.map(int),
+            F::PeersSendingToUs => torrent.peers_sending_to_us.map(int),
+            F::PieceCount => torrent.piece_count.map(int),
+            F::QueuePosition => count(torrent.queue_position),
+            F::SecondsDownloading => torrent.seconds_downloading.map(int),
+            F::SecondsSeeding => torrent.seconds_seeding.map(int),
+            F::SeedIdleLimit => torrent.seed_idle_limit.map(int),
+            F::WebseedsSendingToUs => torrent.webseeds_sending_to_us.map(int),
+
+            F::MetadataPercentComplete => float(torrent.metadata_percent_complete),
+            F::PercentComplete => float(torrent.percent_complete),
+            F::PercentDone => float(torrent.percent_done),
+            F::RecheckProgress => float(torrent.recheck_progress),
+            F::SeedRatioLimit => float(torrent.seed_ratio_limit),
+            F::UploadRatio => upload_ratio(torrent).map(Self::Float),
+
+            F::DownloadLimited => flag(torrent.download_limited),
+            F::HonorsSessionLimits => flag(torrent.honors_session_limits),
+            F::IsFinished => flag(torrent.is_finished),
+            F::IsPrivate => flag(torrent.is_private),
+            F::IsStalled => flag(torrent.is_stalled),
+            F::SequentialDownload => flag(torrent.sequential_download),
+            F::UploadLimited => flag(torrent.upload_limited),
+
+            F::BandwidthPriority => torrent.bandwidth_priority.map(|p| {
+                Self::Int(match p {
+                    Priority::Low => -1,
+                    Priority::Normal => 0,
+                    Priority::High => 1,
+                })
+            }),
+            F::Error => torrent.error.map(|e| {
+                Self::Int(match e {
+                    ErrorType::Ok => 0,
+                    ErrorType::TrackerWarning => 1,
+                    ErrorType::TrackerError => 2,
+                    ErrorType::LocalError => 3,
+                })
+            }),
+            F::SeedIdleMode => torrent.seed_idle_mode.map(|m| {
+                Self::Int(match m {
+                    IdleMode::Global => 0,
+                    IdleMode::Single => 1,
+                    IdleMode::Unlimited => 2,
+                })
+            }),
+            F::SeedRatioMode => torrent.seed_ratio_mode.map(|m| {
+                Self::Int(match m {
+                    RatioMode::Global => 0,
+                    RatioMode::Single => 1,
+                    RatioMode::Unlimited => 2,
+                })
+            }),
+            F::Status => torrent.status.map(|s| Self::Int(status_rank(s))),
+
+            F::Comment => text(torrent.comment.as_deref()),
+            F::Creator => text(torrent.creator.as_deref()),
+            F::DownloadDir => text(torrent.download_dir.as_deref()),
+            F::ErrorString => text(torrent.error_string.as_deref()),
+            F::Group => text(torrent.group.as_deref()),
+            F::HashString => text(torrent.hash_string.as_deref()),
+            F::MagnetLink => text(torrent.magnet_link.as_deref()),
+            F::Name => text(torrent.name.as_deref()),
+            F::PrimaryMimeType => text(torrent.primary_mime_type.as_deref()),
+            F::TorrentFile => text(torrent.torrent_file.as_deref()),
+            F::TrackerList => text(torrent.tracker_list.as_deref()),
+            F::Labels => torrent
+                .labels
+                .as_deref()
+                .map(|labels| Self::Text(labels.join(", ").to_lowercase())),
+            F::Webseeds => torrent
+                .webseeds
+                .as_deref()
+                .map(|seeds| Self::Text(seeds.join(", ").to_lowercase())),
+
+            F::FileStats => count(torrent.file_stats.as_ref().map(Vec::len)),
+            F::Files => count(torrent.files.as_ref().map(Vec::len)),
+            F::Peers => count(torrent.peers.as_ref().map(Vec::len)),
+            F::Pieces => count(torrent.pieces.as_ref().map(Vec::len)),
+            F::Priorities => count(torrent.priorities.as_ref().map(Vec::len)),
+            F::TrackerStats => count(torrent.tracker_stats.as_ref().map(Vec::len)),
+            F::Trackers => count(torrent.trackers.as_ref().map(Vec::len)),
+            F::Wanted => count(torrent.wanted.as_ref().map(Vec::len)),
+
+            F::Availability | F::PeersFrom => None,
+        }
+    }
+}
+
+fn int(value: impl Into<i128>) -> SortKey {
+    SortKey::Int(value.into())
+}
+
+/// Orders statuses by lifecycle rather than by their numeric RPC value.
+const fn status_rank(status: TorrentStatus) -> i128 {
+    match status {
+        TorrentStatus::Downloading => 0,
+        TorrentStatus::QueuedToDownload => 1,
+        TorrentStatus::Seeding => 2,
+        TorrentStatus::QueuedToSeed => 3,
+        TorrentStatus::Verifying => 4,
+        TorrentStatus::QueuedToVerify => 5,
+        TorrentStatus::Stopped => 6,
+    }
+}
@@ -0,0 +1,139 @@
+use super::{constants::SESSION_STATS_INTERVAL_SECS, types::SessionStats};
+use crate::error::Result;
+use std::time::{Duration, Instant};
+
+/// Session stats popup and the totals it shows, which are fetched only while
+/// the popup is open and less often than the torrent list.
+#[derive(Debug, Clone, Default)]
+pub struct StatsView {
+    pub visible: bool,
+    stats: Option<SessionStats>,
+    /// Why the last fetch failed.
+    error: Option<String>,
+    fetched: Option<Instant>,
+    /// Whether a fetch was sent and has not finished yet.
+    pending: bool,
+}
+
+impl StatsView {
+    /// Returns `true` while the popup is open, no fetch is pending and the
+    /// last fetch is older than the stats interval.
+    #[must_use]
+    pub fn due(&self, now: Instant) -> bool {
+        self.visible
+            && !self.pending
+            && self.fetched.is_none_or(|fetched| {
+                now.duration_since(fetched) >= Duration::from_secs(SESSION_STATS_INTERVAL_SECS)
+            })
+    }
+
+    /// Returns whether the stats are due, marking the fetch as pending if so.
+    pub fn request(&mut self, now: Instant) -> bool {
+        self.pending = self.due(now);
+        self.pending
+    }
+
+    /// Forgets a pending fetch whose poll failed, so the next poll retries.
+    pub const fn cancel(&mut self) {
+        self.pending = false;
+    }
+
+    /// Stores the result of a fetch. A failure keeps the previous totals and
+    /// waits for the next interval, as the daemon may not support the calls.
+    pub fn set(&mut self, result: Result<SessionStats>, now: Instant) {
+        self.pending = false;
+        match result {
+            Ok(stats) => {
+                self.stats = Some(stats);
+                self.error = None;
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+        self.fetched = Some(now);
+    }
+
+    #[must_use]
+    pub const fn stats(&self) -> Option<&SessionStats> {
+        self.stats.as_ref()
+    }
+
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// When the shown totals were fetched.
+    #[must_use]
+    pub const fn fetched(&self) -> Option<Instant> {
+        self.fetched
+    }
+
+    pub const fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatsView;
+    use crate::{
+        app::{constants::SESSION_STATS_INTERVAL_SECS, types::SessionStats},
+        error::TraxorError,
+    };
+    use serde_json::json;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn stats_are_due_while_open_until_requested_and_again_after_the_interval() {
+        let mut view = StatsView::default();
+        let now = Instant::now();
+        assert!(!view.due(now));
+        view.toggle();
+        assert!(view.request(now));
+        assert!(!view.due(now));
+
+        let period = json!({
+            "filesAdded": 0,
+            "downloadedBytes": 0,
+            "uploadedBytes": 0,
+            "secondsActive": 0,
+        });
+        let Ok(totals) = serde_json::from_value(json!({
+            "torrentCount": 0,
+            "activeTorrentCount": 0,
+            "pausedTorrentCount": 0,
+            "downloadSpeed": 0,
+            "uploadSpeed": 0,
+            "current-stats": period,
+            "cumulative-stats": period,
+        })) else {
+            panic!("session stats should deserialize");
+        };
+        view.set(
+            Ok(SessionStats {
+                totals,
+                download_dir: "/downloads".into(),
+                free_space: 0,
+            }),
+            now,
+        );
+        assert!(!view.due(now + Duration::from_secs(1)));
+        let later = now + Duration::from_secs(SESSION_STATS_INTERVAL_SECS);
+        assert!(view.due(later));
+        // A failed poll leaves the fetch to the next one.
+        assert!(view.request(later));
+        view.cancel();
+        assert!(view.due(later));
+
+        view.set(
+            Err(TraxorError::TransmissionRpc("no free-space".into())),
+            later,
+        );
+        assert!(!view.due(later));
+        assert_eq!(view.error(), Some("Transmission RPC error: no free-space"));
+        assert!(view.stats().is_some());
+
+        view.toggle();
+        assert!(!view.due(later + Duration::from_secs(SESSION_STATS_INTERVAL_SECS)));
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    collections::{HashMap, HashSet},
+    slice::Iter,
+};
+use transmission_rpc::types::{Torrent, TorrentGetField};
+
+/// Change to the torrent list reported by one poll.
+#[derive(Debug, Clone)]
+pub enum Delta {
+    /// Every torrent; replaces the list.
+    Full(Vec<Torrent>),
+    /// Recently active torrents and ids removed since the previous poll.
+    Changed {
+        torrents: Vec<Torrent>,
+        removed: Vec<i64>,
+    },
+}
+
+/// Torrents indexed by id, in the order the daemon first reported them.
+#[derive(Debug, Clone, Default)]
+pub struct TorrentStore {
+    torrents: Vec<Torrent>,
+    index: HashMap<i64, usize>,
+}
+
+impl TorrentStore {
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.torrents.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.torrents.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Torrent> {
+        self.torrents.iter()
+    }
+
+    /// Returns the torrent with the given id.
+    #[must_use]
+    pub fn get(&self, id: i64) -> Option<&Torrent> {
+        self.index.get(&id).map(|&idx| &self.torrents[idx])
+    }
+
+    /// Replaces all torrents with a full listing.
+    pub fn replace(&mut self, torrents: Vec<Torrent>) {
+        self.torrents = torrents;
+        self.reindex();
+    }
+
+    /// Replaces changed torrents in place and appends new ones.
+    pub fn merge(&mut self, torrents: Vec<Torrent>) {
+        for torrent in torrents {
+            if let Some(&idx) = torrent.id.and_then(|id| self.index.get(&id)) {
+                self.torrents[idx] = torrent;
+                continue;
+            }
+            if let Some(id) = torrent.id {
+                self.index.insert(id, self.torrents.len());
+            }
+            self.torrents.push(torrent);
+        }
+    }
+
+    /// Drops torrents the daemon reported as removed.
+    pub fn remove(&mut self, ids: &[i64]) {
+        if ids.iter().all(|id| !self.index.contains_key(id)) {
+            return;
+        }
+        self.torrents
+            .retain(|torrent| torrent.id.is_none_or(|id| !ids.contains(&id)));
+        self.reindex();
+    }
+
+    /// Applies the result of a poll.
+    pub fn apply(&mut self, delta: Delta) {
+        match delta {
+            Delta::Full(torrents) => self.replace(torrents),
+            Delta::Changed { torrents, removed } => {
+                self.remove(&removed);
+                self.merge(torrents);
+            }
+        }
+    }
+
+    fn reindex(&mut self) {
+        self.index = self
+            .torrents
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, torrent)| torrent.id.map(|id| (id, idx)))
+            .collect();
+    }
+}
+
+impl From<Vec<Torrent>> for TorrentStore {
+    fn from(torrents: Vec<Torrent>) -> Self {
+        let mut store = Self::default();
+        store.replace(torrents);
+        store
+    }
+}
+
+impl FromIterator<Torrent> for TorrentStore {
+    fn from_iter<I: IntoIterator<Item = Torrent>>(iter: I) -> Self {
+        iter.into_iter().collect::<Vec<_>>().into()
+    }
+}
+
+impl<'a> IntoIterator for &'a TorrentStore {
+    type Item = &'a Torrent;
+    type IntoIter = Iter<'a, Torrent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Torrents the app shows, with the ids marked for bulk actions and the
+/// fields polled for them.
+#[derive(Debug, Clone, Default)]
+pub struct TorrentList {
+    pub torrents: TorrentStore,
+    pub selected: HashSet<i64>,
+    pub fields: Option<Vec<TorrentGetField>>,
+}
+
+impl TorrentList {
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.torrents.len()
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.torrents.is_empty()
+    }
+
+    /// Applies the result of a poll and unmarks torrents that are gone.
+    pub fn apply(&mut self, delta: Delta) {
+        self.torrents.apply(delta);
+        let torrents = &self.torrents;
+        self.selected.retain(|&id| torrents.get(id).is_some());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TorrentStore;
+    use crate::app::test_utils::from_json;
+    use serde_json::json;
+    use transmission_rpc::types::Torrent;
+
+    fn torrent(id: i64, name: &str) -> Torrent {
+        from_json(json!({ "id": id, "name": name }))
+    }
+
+    fn names(store: &TorrentStore) -> Vec<&str> {
+        store.iter().filter_map(|t| t.name.as_deref()).collect()
+    }
+
+    #[test]
+    fn merge_updates_in_place_and_appends_new_torrents() {
+        let mut store = TorrentStore::from(vec![torrent(3, "c"), torrent(1, "a")]);
+        store.merge(vec![torrent(1, "a2"), torrent(7, "g")]);
+        assert_eq!(names(&store), ["c", "a2", "g"]);
+        assert_eq!(store.get(7).and_then(|t| t.name.as_deref()), Some("g"));
+    }
+
+    #[test]
+    fn remove_drops_ids_and_keeps_index_consistent() {
+        let mut store = TorrentStore::from(vec![torrent(1, "a"), torrent(2, "b"), torrent(3, "c")]);
+        store.remove(&[2, 99]);
+        assert_eq!(names(&store), ["a", "c"]);
+        assert!(store.get(2).is_none());
+        assert_eq!(store.get(3).and_then(|t| t.name.as_deref()), Some("c"));
+    }
+}
@@ -1,13 +1,18 @@
+use super::{
+    query::Criteria,
+    sort::{SortDirection, SortOrder},
+};
 use crate::config::tabs::TabConfig;
 use std::fmt::Display;
-use transmission_rpc::types::{Torrent, TorrentGetField, TorrentStatus};
+use transmission_rpc::types::{Torrent, TorrentGetField};
 
 /// A tab with name and column configuration.
 #[derive(Debug, Clone)]
 pub struct Tab {
     config: TabConfig,
     fields: Vec<TorrentGetField>,
-    statuses: Vec<TorrentStatus>,
+    criteria: Criteria,
+    sort: Option<SortOrder>,
 }
 
 impl Tab {
@@ -15,11 +20,18 @@ impl Tab {
     #[must_use]
     pub fn new(config: TabConfig) -> Self {
         let fields = config.fields();
-        let statuses = config.statuses();
+        let sort = config.sort_order();
+        let criteria = Criteria::new(
+            config.statuses(),
+            &config.labels,
+            &config.trackers,
+            config.query(),
+        );
         Self {
             config,
             fields,
-            statuses,
+            criteria,
+            sort,
         }
     }
 
@@ -29,6 +41,69 @@ impl Tab {
         &self.fields
     }
 
+    /// Fields needed to show, filter and sort this tab.
+    #[must_use]
+    pub fn required_fields(&self) -> Vec<TorrentGetField> {
+        let mut fields = self.fields.clone();
+        if fields.contains(&TorrentGetField::Status) {
+            // The status column shows the progress while verifying.
+            fields.push(TorrentGetField::RecheckProgress);
+        }
+        fields.extend(self.sort.map(|sort| sort.field));
+        fields.extend(self.criteria.fields());
+        fields
+    }
+
+    /// Returns the active sort order, or `None` for daemon order.
+    #[must_use]
+    pub const fn sort(&self) -> Option<SortOrder> {
+        self.sort
+    }
+
+    /// Sorts by the next column, keeping the direction. After the last
+    /// column the tab goes back to daemon order.
+    pub fn cycle_sort(&mut self) {
+        let direction = self.sort.map(|sort| sort.direction).unwrap_or_default();
+        let next = match self.sort {
+            // A configured default may sort by a hidden column; start over then.
+            Some(sort) => self
+                .fields
+                .iter()
+                .position(|&f| f == sort.field)
+                .map_or(Some(0), |idx| {
+                    Some(idx + 1).filter(|&idx| idx < self.fields.len())
+                }),
+            None => Some(0),
+        };
+        self.sort = next
+            .and_then(|idx| self.fields.get(idx))
+            .map(|&field| SortOrder::new(field, direction));
+    }
+
+    /// Sorts by the column at `idx`, flipping the direction if the tab is
+    /// already sorted by it.
+    pub fn sort_by(&mut self, idx: usize) {
+        let Some(&field) = self.fields.get(idx) else {
+            return;
+        };
+        self.sort = Some(match self.sort {
+            Some(sort) if sort.field == field => SortOrder::new(field, sort.direction.reversed()),
+            sort => SortOrder::new(field, sort.map(|s| s.direction).unwrap_or_default()),
+        });
+    }
+
+    /// Flips the sort direction, sorting by the first column descending if
+    /// the tab is unsorted.
+    pub fn reverse_sort(&mut self) {
+        self.sort = match self.sort {
+            Some(sort) => Some(SortOrder::new(sort.field, sort.direction.reversed())),
+            None => self
+                .fields
+                .first()
+                .map(|&field| SortOrder::new(field, SortDirection::Descending)),
+        };
+    }
+
     /// Returns the tab name.
     #[must_use]
     pub fn name(&self) -> &str {
@@ -36,12 +111,12 @@ impl Tab {
     }
 
     /// Returns `true` if the torrent belongs in this tab.
+    ///
+    /// Each configured criterion must hold; lists (statuses, labels,
+    /// trackers) match when any of their entries does.
     #[must_use]
     pub fn matches(&self, torrent: &Torrent) -> bool {
-        self.statuses.is_empty()
-            || torrent
-                .status
-                .is_some_and(|status| self.statuses.contains(&status))
+        self.criteria.matches(torrent)
     }
 }
 
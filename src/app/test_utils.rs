@@ -0,0 +1,10 @@
+use serde_json::Value;
+use transmission_rpc::types::Torrent;
+
+/// Builds a torrent from `torrent-get` JSON, e.g. `json!({ "id": 1 })`.
+pub fn from_json(value: Value) -> Torrent {
+    match serde_json::from_value(value) {
+        Ok(torrent) => torrent,
+        Err(err) => panic!("torrent should deserialize: {err}"),
+    }
+}
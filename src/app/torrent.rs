@@ -1,95 +1,221 @@
-use crate::{app::constants::DEFAULT_RPC_URL, error::Result};
-use std::{collections::HashSet, fmt::Debug};
+use super::{constants::FULL_REFRESH_EVERY_POLLS, rpc::RpcClient, store::Delta};
+use crate::{
+    config::connection::ConnectionConfig,
+    error::{Result, TraxorError},
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::{fmt::Debug, time::Duration};
 use transmission_rpc::{
     TransClient,
-    types::{Torrent, TorrentGetField},
+    types::{BasicAuth, Id, Torrent, TorrentGetField},
 };
 use url::Url;
 
-/// List of torrents.
+/// Endpoint of the selected connection profile with its credentials
+/// resolved, so the password command runs once per session.
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub url: String,
+    auth: Option<BasicAuth>,
+    timeout: Duration,
+}
+
+impl Connection {
+    /// Resolves the endpoint of the selected connection profile, running its
+    /// password command if one is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the password command fails.
+    pub fn resolve(connection: &ConnectionConfig) -> Result<Self> {
+        let endpoint = connection.endpoint();
+        let auth = match (endpoint.username.clone(), endpoint.password()) {
+            (_, Err(err)) => return Err(TraxorError::Config(err)),
+            (None, Ok(None)) => None,
+            (user, Ok(password)) => Some(BasicAuth {
+                user: user.unwrap_or_default(),
+                password: password.unwrap_or_default(),
+            }),
+        };
+        Ok(Self {
+            url: endpoint.url,
+            auth,
+            timeout: endpoint.timeout,
+        })
+    }
+}
+
+/// RPC client for the torrents of one daemon.
 pub struct Torrents {
     /// Constructs a new instance of [`Torrents`].
     pub client: TransClient,
-    pub torrents: Vec<Torrent>,
-    pub selected: HashSet<i64>,
+    /// Raw client for RPC methods and fields `client` does not cover.
+    pub rpc: RpcClient,
     pub fields: Option<Vec<TorrentGetField>>,
+    auth: Option<BasicAuth>,
+    timeout: Duration,
+    /// Incremental polls since the last full listing; `None` forces one.
+    polls_since_full: Option<u32>,
+}
+
+/// Response of `torrent-get` with `ids` set to `recently-active`.
+#[derive(Debug, Deserialize)]
+struct RecentlyActive {
+    torrents: Vec<Torrent>,
+    /// Ids of torrents removed since the previous poll.
+    #[serde(default)]
+    removed: Vec<i64>,
 }
 
 impl Torrents {
-    /// Constructs a new instance of [`Torrents`].
+    /// Constructs a new instance of [`Torrents`] connected to `connection`.
     ///
     /// # Errors
     ///
-    /// TODO: add error types
-    pub fn new() -> Result<Self> {
-        let url = Url::parse(DEFAULT_RPC_URL)?;
+    /// Returns an error if the URL is invalid or the HTTP client cannot be built.
+    pub fn new(connection: &Connection) -> Result<Self> {
+        let (client, rpc) = clients(
+            &connection.url,
+            connection.auth.as_ref(),
+            connection.timeout,
+        )?;
         Ok(Self {
-            client: TransClient::new(url),
-            torrents: Vec::new(),
-            selected: HashSet::new(),
+            client,
+            rpc,
             fields: None,
+            auth: connection.auth.clone(),
+            timeout: connection.timeout,
+            polls_since_full: None,
         })
     }
 
-    /// Returns the number of [`Torrent`]s in [`Torrents`]
-    #[inline]
-    #[must_use]
-    pub const fn len(&self) -> usize {
-        self.torrents.len()
-    }
-
-    /// Returns `true` if the `torrents` contains no elements.
-    #[inline]
-    #[must_use]
-    pub const fn is_empty(&self) -> bool {
-        self.torrents.is_empty()
-    }
-
-    /// Sets `self.fields`
+    /// Sets `self.fields`. A different field set is fetched in full on the
+    /// next update, since unchanged torrents would otherwise lack the new fields.
     pub fn set_fields(&mut self, fields: Option<Vec<TorrentGetField>>) -> &mut Self {
+        if self.fields != fields {
+            self.polls_since_full = None;
+        }
         self.fields = fields;
         self
     }
 
-    /// Sets
+    /// Points the client at `url`, keeping the configured credentials and timeout.
     ///
     /// # Errors
     ///
-    /// TODO: add error types
+    /// Returns an error if the URL is invalid or the HTTP client cannot be built.
     pub fn url(&mut self, url: &str) -> Result<&mut Self> {
-        self.client = TransClient::new(Url::parse(url)?);
+        (self.client, self.rpc) = clients(url, self.auth.as_ref(), self.timeout)?;
         Ok(self)
     }
 
-    /// Updates [`Torrent`] values.
+    /// Fetches what changed since the previous call.
+    ///
+    /// After a full listing only recently active torrents are fetched, along
+    /// with the ids of removed ones. A full listing is repeated periodically
+    /// and whenever the fields change, to pick up edits the daemon does not
+    /// count as activity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn fetch(&mut self) -> Result<Delta> {
+        let polls = self
+            .polls_since_full
+            .filter(|&polls| polls < FULL_REFRESH_EVERY_POLLS);
+        if let (Some(fields), Some(polls)) = (&self.fields, polls) {
+            let fields: Vec<_> = fields.iter().map(TorrentGetField::to_str).collect();
+            let response: RecentlyActive = self
+                .rpc
+                .call(
+                    "torrent-get",
+                    json!({ "ids": "recently-active", "fields": fields }),
+                )
+                .await?;
+            self.polls_since_full = Some(polls + 1);
+            Ok(Delta::Changed {
+                torrents: response.torrents,
+                removed: response.removed,
+            })
+        } else {
+            let torrents = self
+                .client
+                .torrent_get(self.fields.clone(), None)
+                .await?
+                .arguments
+                .torrents;
+            self.polls_since_full = Some(0);
+            Ok(Delta::Full(torrents))
+        }
+    }
+
+    /// Makes the next update fetch every torrent, e.g. after removing or
+    /// editing torrents.
+    pub const fn invalidate(&mut self) {
+        self.polls_since_full = None;
+    }
+
+    /// Fetches `fields` for the single torrent with the given id.
+    ///
+    /// Returns `None` if the torrent no longer exists.
     ///
     /// # Errors
     ///
-    /// TODO: add error types
-    pub async fn update(&mut self) -> Result<&mut Self> {
-        self.torrents = self
+    /// Returns an error if the RPC call fails.
+    pub async fn get(&mut self, id: i64, fields: Vec<TorrentGetField>) -> Result<Option<Torrent>> {
+        Ok(self
             .client
-            .torrent_get(self.fields.clone(), None)
+            .torrent_get(Some(fields), Some(vec![Id::Id(id)]))
             .await?
             .arguments
-            .torrents;
-        Ok(self)
+            .torrents
+            .pop())
+    }
+
+    /// Fetches `fields` for the torrents with the given ids, e.g. to prefill
+    /// a dialog with fields the list does not poll.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the RPC call fails.
+    pub async fn get_many(
+        &mut self,
+        ids: Vec<i64>,
+        fields: Vec<TorrentGetField>,
+    ) -> Result<Vec<Torrent>> {
+        let ids = ids.into_iter().map(Id::Id).collect();
+        Ok(self
+            .client
+            .torrent_get(Some(fields), Some(ids))
+            .await?
+            .arguments
+            .torrents)
     }
 }
 
+/// Builds both clients for `url`, sharing one HTTP client.
+fn clients(
+    url: &str,
+    auth: Option<&BasicAuth>,
+    timeout: Duration,
+) -> Result<(TransClient, RpcClient)> {
+    let url = Url::parse(url)?;
+    let http = reqwest::Client::builder().timeout(timeout).build()?;
+    let rpc = RpcClient::new(http.clone(), url.clone(), auth.cloned());
+    let mut client = TransClient::new_with_client(url, http);
+    if let Some(auth) = auth {
+        client.set_auth(auth.clone());
+    }
+    Ok((client, rpc))
+}
+
 impl Debug for Torrents {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let fields = self.fields.as_ref().map_or_else(
             || vec!["None".into()],
             |fields| fields.iter().map(TorrentGetField::to_str).collect(),
         );
-        write!(
-            f,
-            "fields:
-{:?};
-
-torrents: {:?}",
-            fields, self.torrents
-        )
+        write!(f, "fields: {fields:?}")
     }
 }
@@ -0,0 +1,503 @@
+use super::input::InputHandler;
+use std::collections::HashSet;
+use transmission_rpc::types::{Torrent, TorrentGetField};
+use url::Url;
+
+/// Fields the tracker dialog needs for each torrent.
+pub const FIELDS: [TorrentGetField; 3] = [
+    TorrentGetField::Id,
+    TorrentGetField::Name,
+    TorrentGetField::Trackers,
+];
+
+/// Edit the tracker dialog applies to every torrent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrackerAction {
+    /// Swap the host or URL of matching trackers.
+    #[default]
+    Replace,
+    Add,
+    Remove,
+}
+
+impl TrackerAction {
+    pub const ALL: [Self; 3] = [Self::Replace, Self::Add, Self::Remove];
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Replace => "Replace",
+            Self::Add => "Add",
+            Self::Remove => "Remove",
+        }
+    }
+
+    #[must_use]
+    fn cycle(self, forward: bool) -> Self {
+        let len = Self::ALL.len();
+        let idx = Self::ALL
+            .iter()
+            .position(|&a| a == self)
+            .unwrap_or_default();
+        Self::ALL[if forward {
+            (idx + 1) % len
+        } else {
+            (idx + len - 1) % len
+        }]
+    }
+}
+
+/// Field of the tracker dialog that currently has focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrackerField {
+    #[default]
+    Action,
+    Find,
+    Replace,
+}
+
+impl TrackerField {
+    #[must_use]
+    pub const fn label(self, action: TrackerAction) -> &'static str {
+        match (self, action) {
+            (Self::Action, _) => "Action",
+            (Self::Find, TrackerAction::Add) => "Announce URL",
+            (Self::Find, TrackerAction::Remove) => "Host or URL",
+            (Self::Find, TrackerAction::Replace) => "Find host/URL",
+            (Self::Replace, _) => "Replace with",
+        }
+    }
+}
+
+/// A tracker of a torrent as the daemon identifies it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tracker {
+    pub id: i64,
+    pub announce: String,
+}
+
+/// Tracker edits of one torrent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackerChange {
+    pub id: i64,
+    pub name: String,
+    /// Announce URLs to add, each in a tier of its own.
+    pub add: Vec<String>,
+    pub remove: Vec<Tracker>,
+    /// Trackers with their new announce URL.
+    pub replace: Vec<(Tracker, String)>,
+}
+
+impl TrackerChange {
+    const fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty() && self.replace.is_empty()
+    }
+}
+
+/// State of the tracker dialog, filled once the trackers of the edited
+/// torrents are loaded.
+#[derive(Debug, Default)]
+pub struct TrackersForm {
+    pub action: TrackerAction,
+    pub focus: TrackerField,
+    pub find: InputHandler,
+    pub replace: InputHandler,
+    torrents: Vec<Torrent>,
+    /// Validation error of the last submit.
+    pub error: Option<String>,
+}
+
+impl TrackersForm {
+    #[must_use]
+    pub fn new(torrents: Vec<Torrent>) -> Self {
+        Self {
+            torrents,
+            ..Self::default()
+        }
+    }
+
+    /// Number of torrents being edited.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.torrents.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.torrents.is_empty()
+    }
+
+    /// Fields shown for the chosen action.
+    #[must_use]
+    pub const fn fields(&self) -> &'static [TrackerField] {
+        match self.action {
+            TrackerAction::Replace => &[
+                TrackerField::Action,
+                TrackerField::Find,
+                TrackerField::Replace,
+            ],
+            TrackerAction::Add | TrackerAction::Remove => {
+                &[TrackerField::Action, TrackerField::Find]
+            }
+        }
+    }
+
+    fn position(&self) -> usize {
+        self.fields()
+            .iter()
+            .position(|&field| field == self.focus)
+            .unwrap_or_default()
+    }
+
+    /// Moves focus to the next field, wrapping around.
+    pub fn next_field(&mut self) {
+        let fields = self.fields();
+        self.focus = fields[(self.position() + 1) % fields.len()];
+    }
+
+    /// Moves focus to the previous field, wrapping around.
+    pub fn prev_field(&mut self) {
+        let fields = self.fields();
+        let idx = self.position().checked_sub(1).unwrap_or(fields.len() - 1);
+        self.focus = fields[idx];
+    }
+
+    /// Returns the text input of the focused field, if it is a text field.
+    pub const fn focused_input_mut(&mut self) -> Option<&mut InputHandler> {
+        match self.focus {
+            TrackerField::Action => None,
+            TrackerField::Find => Some(&mut self.find),
+            TrackerField::Replace => Some(&mut self.replace),
+        }
+    }
+
+    /// Switches the action while the action field has focus.
+    pub fn cycle(&mut self, forward: bool) {
+        if self.focus == TrackerField::Action {
+            self.action = self.action.cycle(forward);
+            self.error = None;
+        }
+    }
+
+    /// Edits of every torrent the entered action changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a field is empty or a URL is not a valid
+    /// announce URL.
+    pub fn changes(&self) -> Result<Vec<TrackerChange>, String> {
+        let find = self.find.text.trim();
+        let with = self.replace.text.trim();
+        match self.action {
+            TrackerAction::Add => validate(find)?,
+            TrackerAction::Remove if find.is_empty() => {
+                return Err("Enter a host or URL to remove".into());
+            }
+            TrackerAction::Replace if find.is_empty() => {
+                return Err("Enter a host or URL to replace".into());
+            }
+            TrackerAction::Replace if with.is_empty() => {
+                return Err("Enter the new host or URL".into());
+            }
+            TrackerAction::Replace if with.contains("://") => validate(with)?,
+            TrackerAction::Replace => validate_host(with)?,
+            TrackerAction::Remove => {}
+        }
+
+        let mut changes = Vec::new();
+        for torrent in &self.torrents {
+            let Some(id) = torrent.id else { continue };
+            let trackers = trackers(torrent);
+            let mut change = TrackerChange {
+                id,
+                name: torrent.name.clone().unwrap_or_default(),
+                ..TrackerChange::default()
+            };
+            match self.action {
+                TrackerAction::Add => {
+                    if !trackers.iter().any(|t| t.announce == find) {
+                        change.add.push(find.to_owned());
+                    }
+                }
+                TrackerAction::Remove => {
+                    change.remove = trackers
+                        .into_iter()
+                        .filter(|t| matches(&t.announce, find))
+                        .collect();
+                }
+                TrackerAction::Replace => {
+                    // Announce URLs the torrent ends up with so far.
+                    let mut produced: HashSet<String> =
+                        trackers.iter().map(|t| t.announce.clone()).collect();
+                    for tracker in trackers.iter().filter(|t| matches(&t.announce, find)) {
+                        // Trackers whose URL cannot take the new host are left alone.
+                        let Some(announce) = replace(&tracker.announce, with) else {
+                            continue;
+                        };
+                        if announce == tracker.announce {
+                            continue;
+                        }
+                        // The daemon rejects duplicates; drop the old URL instead.
+                        if produced.insert(announce.clone()) {
+                            change.replace.push((tracker.clone(), announce));
+                        } else {
+                            change.remove.push(tracker.clone());
+                        }
+                        produced.remove(&tracker.announce);
+                    }
+                }
+            }
+            if !change.is_empty() {
+                changes.push(change);
+            }
+        }
+        Ok(changes)
+    }
+}
+
+fn trackers(torrent: &Torrent) -> Vec<Tracker> {
+    torrent
+        .trackers
+        .iter()
+        .flatten()
+        .map(|t| Tracker {
+            id: i64::from(t.id),
+            announce: t.announce.clone(),
+        })
+        .collect()
+}
+
+/// Returns `true` if `pattern`, a full announce URL or a host, names the
+/// tracker. Hosts are compared ignoring case.
+fn matches(announce: &str, pattern: &str) -> bool {
+    if pattern.contains("://") {
+        return announce == pattern;
+    }
+    Url::parse(announce)
+        .ok()
+        .and_then(|url| {
+            url.host_str()
+                .map(|host| host.eq_ignore_ascii_case(pattern))
+        })
+        .unwrap_or(false)
+}
+
+/// Applies `with`, a full announce URL or a new `host[:port]`, to
+/// `announce`. A new host keeps the port and the rest of the URL as written;
+/// `None` if that does not make a valid announce URL.
+fn replace(announce: &str, with: &str) -> Option<String> {
+    if with.contains("://") {
+        return Some(with.to_owned());
+    }
+    let (before, authority, after) = split_authority(announce)?;
+    let port = match split_port(with) {
+        (_, "") => split_port(authority).1,
+        _ => "",
+    };
+    let announce = format!("{before}{with}{port}{after}");
+    validate(&announce).is_ok().then_some(announce)
+}
+
+/// Splits `url` into what precedes the host, the host with its port, and
+/// the path onwards.
+fn split_authority(url: &str) -> Option<(&str, &str, &str)> {
+    let start = url.find("://")? + 3;
+    let end = url[start..]
+        .find(['/', '?', '#'])
+        .map_or(url.len(), |idx| start + idx);
+    let host = url[start..end]
+        .rfind('@')
+        .map_or(start, |idx| start + idx + 1);
+    (host < end).then(|| (&url[..host], &url[host..end], &url[end..]))
+}
+
+/// Splits `host[:port]` into the host and the port with its colon, if any.
+fn split_port(authority: &str) -> (&str, &str) {
+    match authority.rfind(':') {
+        Some(idx)
+            if idx + 1 < authority.len()
+                && authority[idx + 1..].bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            authority.split_at(idx)
+        }
+        _ => (authority, ""),
+    }
+}
+
+/// Checks that `host` is a host with an optional port and nothing else.
+fn validate_host(host: &str) -> Result<(), String> {
+    let parsed = (!host.contains(['/', '?', '#', '@']))
+        .then(|| Url::parse(&format!("http://{host}")).ok())
+        .flatten();
+    match parsed {
+        Some(url) if url.host_str().is_some() => Ok(()),
+        _ => Err(format!("Invalid host: {host}")),
+    }
+}
+
+fn validate(url: &str) -> Result<(), String> {
+    match Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https" | "udp") => Ok(()),
+        _ => Err(format!("Invalid announce URL: {url}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tracker, TrackerAction, TrackersForm};
+    use crate::app::test_utils::from_json;
+    use serde_json::json;
+    use transmission_rpc::types::Torrent;
+
+    fn torrent(id: i64, announces: &[&str]) -> Torrent {
+        let trackers: Vec<_> = announces
+            .iter()
+            .zip(0..)
+            .map(|(announce, tracker)| {
+                json!({ "id": tracker, "announce": announce, "scrape": "", "tier": tracker })
+            })
+            .collect();
+        from_json(json!({
+            "id": id,
+            "name": format!("t{id}"),
+            "trackers": trackers,
+        }))
+    }
+
+    fn form(action: TrackerAction, find: &str, with: &str) -> TrackersForm {
+        let mut form = TrackersForm::new(vec![
+            torrent(
+                1,
+                &["https://old.example/a/key1", "udp://open.tracker:1337"],
+            ),
+            torrent(
+                2,
+                &["https://OLD.example/a/key2", "https://new.example/a/key2"],
+            ),
+            torrent(3, &["udp://open.tracker:1337"]),
+        ]);
+        form.action = action;
+        form.find.set_text(find.into());
+        form.replace.set_text(with.into());
+        form
+    }
+
+    #[test]
+    fn replace_swaps_hosts_and_drops_duplicates() {
+        let changes = form(TrackerAction::Replace, "old.example", "new.example")
+            .changes()
+            .unwrap_or_default();
+        assert_eq!(changes.len(), 2);
+        let old = Tracker {
+            id: 0,
+            announce: "https://old.example/a/key1".into(),
+        };
+        assert_eq!(
+            changes[0].replace,
+            [(old, "https://new.example/a/key1".to_owned())]
+        );
+        assert!(changes[1].replace.is_empty());
+        assert_eq!(changes[1].remove[0].announce, "https://OLD.example/a/key2");
+    }
+
+    #[test]
+    fn replace_keeps_ports_and_accepts_a_new_one() {
+        let mut form = TrackersForm::new(vec![
+            torrent(1, &["https://user@old.example:443/announce?k=1"]),
+            torrent(2, &["udp://old.example:6969"]),
+        ]);
+        form.find.set_text("old.example".into());
+        form.replace.set_text("new.example".into());
+        let announces = |form: &TrackersForm| -> Vec<String> {
+            form.changes()
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|change| change.replace)
+                .map(|(_, announce)| announce)
+                .collect()
+        };
+        assert_eq!(
+            announces(&form),
+            [
+                "https://user@new.example:443/announce?k=1",
+                "udp://new.example:6969"
+            ]
+        );
+        form.replace.set_text("new.example:8080".into());
+        assert_eq!(
+            announces(&form),
+            [
+                "https://user@new.example:8080/announce?k=1",
+                "udp://new.example:8080"
+            ]
+        );
+    }
+
+    #[test]
+    fn trackers_that_cannot_take_the_host_are_skipped() {
+        let mut form = TrackersForm::new(vec![
+            torrent(1, &["ftp://old.example/x", "https://old.example/a"]),
+            torrent(2, &["https://old.example/b"]),
+        ]);
+        form.find.set_text("old.example".into());
+        form.replace.set_text("new.example".into());
+        let changes = form.changes().unwrap_or_default();
+        let ids: Vec<i64> = changes.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [1, 2]);
+        assert_eq!(changes[0].replace[0].1, "https://new.example/a");
+    }
+
+    #[test]
+    fn add_skips_torrents_having_the_tracker_and_remove_matches_urls() {
+        let changes = form(TrackerAction::Add, "udp://open.tracker:1337", "")
+            .changes()
+            .unwrap_or_default();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].id, 2);
+
+        let changes = form(TrackerAction::Remove, "udp://open.tracker:1337", "")
+            .changes()
+            .unwrap_or_default();
+        let ids: Vec<i64> = changes.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [1, 3]);
+    }
+
+    #[test]
+    fn replacing_several_trackers_with_one_url_sends_it_once() {
+        let mut form = TrackersForm::new(vec![torrent(
+            1,
+            &["https://old.example/a", "https://old.example/b"],
+        )]);
+        form.find.set_text("old.example".into());
+        form.replace.set_text("https://new.example/announce".into());
+        let changes = form.changes().unwrap_or_default();
+        assert_eq!(changes[0].replace.len(), 1);
+        assert_eq!(changes[0].replace[0].1, "https://new.example/announce");
+        assert_eq!(changes[0].remove[0].announce, "https://old.example/b");
+    }
+
+    #[test]
+    fn invalid_input_is_rejected() {
+        let err = |form: TrackersForm| form.changes().err().unwrap_or_default();
+        assert_eq!(
+            err(form(TrackerAction::Add, "ftp://x", "")),
+            "Invalid announce URL: ftp://x"
+        );
+        assert_eq!(
+            err(form(TrackerAction::Replace, "old.example", "")),
+            "Enter the new host or URL"
+        );
+        assert_eq!(
+            err(form(TrackerAction::Replace, "old.example", "bad host")),
+            "Invalid host: bad host"
+        );
+        assert_eq!(
+            err(form(
+                TrackerAction::Replace,
+                "old.example",
+                "new.example/path"
+            )),
+            "Invalid host: new.example/path"
+        );
+    }
+}
@@ -1,9 +1,122 @@
+use derive_more::Display;
+use serde::Deserialize;
 use std::{
     collections::{HashSet, hash_set::IntoIter},
     hash::BuildHasher,
     iter::Once,
 };
-use transmission_rpc::types::Id;
+use transmission_rpc::types::{Id, Priority};
+
+/// Change applied to a set of files within a torrent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChange {
+    Wanted(bool),
+    Priority(Priority),
+}
+
+/// Direction torrents are moved in the daemon's download/seed queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum QueueMove {
+    #[display("top")]
+    Top,
+    #[display("up")]
+    Up,
+    #[display("down")]
+    Down,
+    #[display("bottom")]
+    Bottom,
+}
+
+impl QueueMove {
+    /// RPC method performing the move.
+    #[must_use]
+    pub const fn method(self) -> &'static str {
+        match self {
+            Self::Top => "queue-move-top",
+            Self::Up => "queue-move-up",
+            Self::Down => "queue-move-down",
+            Self::Bottom => "queue-move-bottom",
+        }
+    }
+}
+
+/// Options applied to a newly added torrent.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddOptions {
+    /// Download directory; the daemon default is used when `None`.
+    pub download_dir: Option<String>,
+    pub paused: bool,
+    pub labels: Vec<String>,
+    pub priority: Option<Priority>,
+}
+
+/// Global speed limits of the daemon, in kB/s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SpeedLimits {
+    pub speed_limit_down: i32,
+    pub speed_limit_down_enabled: bool,
+    pub speed_limit_up: i32,
+    pub speed_limit_up_enabled: bool,
+    pub alt_speed_down: i32,
+    pub alt_speed_up: i32,
+    /// Alternative speed limits ("turtle mode") are active.
+    pub alt_speed_enabled: bool,
+}
+
+impl SpeedLimits {
+    /// `session-get` fields needed to fill [`SpeedLimits`].
+    pub const FIELDS: [&str; 7] = [
+        "speed-limit-down",
+        "speed-limit-down-enabled",
+        "speed-limit-up",
+        "speed-limit-up-enabled",
+        "alt-speed-down",
+        "alt-speed-up",
+        "alt-speed-enabled",
+    ];
+
+    /// Returns the limits in effect as `(down, up)`, `None` meaning unlimited.
+    #[must_use]
+    pub const fn active(&self) -> (Option<i32>, Option<i32>) {
+        if self.alt_speed_enabled {
+            return (Some(self.alt_speed_down), Some(self.alt_speed_up));
+        }
+        (
+            if self.speed_limit_down_enabled {
+                Some(self.speed_limit_down)
+            } else {
+                None
+            },
+            if self.speed_limit_up_enabled {
+                Some(self.speed_limit_up)
+            } else {
+                None
+            },
+        )
+    }
+}
+
+/// Transfer totals kept by the daemon, which unlike sums over the listed
+/// torrents include torrents removed since.
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    /// Totals since the daemon was started and across all sessions.
+    pub totals: Box<transmission_rpc::types::SessionStats>,
+    /// Default download directory.
+    pub download_dir: String,
+    /// Free space in the default download directory, in bytes.
+    pub free_space: i64,
+}
+
+/// Speed limits entered in the limits dialog, in kB/s. `None` is unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LimitChange {
+    pub down: Option<u32>,
+    pub up: Option<u32>,
+    /// Whether torrents also obey the global limits. Ignored for the session.
+    pub honors_session_limits: bool,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Selected {
@@ -186,7 +186,7 @@ impl Wrapper for TorrentGetField {
             Self::SequentialDownload => format_option(torrent.sequential_download),
             Self::SizeWhenDone => FileSize::from(torrent.size_when_done).to_string(),
             Self::StartDate => format_datetime(torrent.start_date, time),
-            Self::Status => torrent.status.format(),
+            Self::Status => format_status(torrent),
             Self::TorrentFile => torrent.torrent_file.clone().unwrap_or_default(),
             Self::TotalSize => FileSize::from(torrent.total_size).to_string(),
             Self::TrackerList => torrent.tracker_list.clone().unwrap_or_default(),
@@ -289,10 +289,31 @@ impl Wrapper for TorrentGetField {
     }
 }
 
+/// Reads the upload ratio, which the daemon reports as -1 when it is not
+/// available and as -2 when it is infinite.
+#[must_use]
+pub fn upload_ratio(torrent: &Torrent) -> Option<f64> {
+    match torrent.upload_ratio? {
+        ratio if ratio <= -2.0 => Some(f64::INFINITY),
+        ratio if ratio < 0.0 => None,
+        ratio => Some(f64::from(ratio)),
+    }
+}
+
 fn format_option<T: Display>(value: Option<T>) -> String {
     value.map_or_else(String::new, |v| v.to_string())
 }
 
+/// Formats the status, with the verification progress while verifying.
+fn format_status(torrent: &Torrent) -> String {
+    match (torrent.status, torrent.recheck_progress) {
+        (Some(TorrentStatus::Verifying), Some(progress)) => {
+            format!("Verifying {:.0}%", progress * 100.0)
+        }
+        (status, _) => status.format(),
+    }
+}
+
 fn format_eta(value: Option<i64>, time: &TimeConfig) -> String {
     match value {
         Some(-2) => "?".into(),
@@ -302,7 +323,7 @@ fn format_eta(value: Option<i64>, time: &TimeConfig) -> String {
     }
 }
 
-fn format_datetime(value: Option<DateTime<Utc>>, time: &TimeConfig) -> String {
+pub(crate) fn format_datetime(value: Option<DateTime<Utc>>, time: &TimeConfig) -> String {
     let Some(value) = value else {
         return String::new();
     };
@@ -315,7 +336,7 @@ fn format_datetime(value: Option<DateTime<Utc>>, time: &TimeConfig) -> String {
         .to_string()
 }
 
-fn format_duration(seconds: i64) -> String {
+pub(crate) fn format_duration(seconds: i64) -> String {
     const SECONDS_PER_MINUTE: i64 = 60;
     const SECONDS_PER_HOUR: i64 = 60 * SECONDS_PER_MINUTE;
     const SECONDS_PER_DAY: i64 = 24 * SECONDS_PER_HOUR;
@@ -0,0 +1,471 @@
+use super::{
+    Connection, Torrents,
+    detail::DetailView,
+    labels::LabelChange,
+    limits,
+    options::{self, OptionsChange},
+    settings::{DaemonSettings, SettingChange},
+    store::Delta,
+    trackers::{self, TrackerChange},
+    types::{AddOptions, FileChange, LimitChange, QueueMove, Selected, SessionStats, SpeedLimits},
+};
+use crate::error::{Result, TraxorError};
+use std::{path::Path, time::Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use transmission_rpc::types::{Torrent, TorrentGetField};
+
+/// RPC work run by the [`Worker`].
+#[derive(Debug, Clone)]
+pub enum Request {
+    /// Refreshes the speed limits, the torrent list and the torrent shown in
+    /// the detail view, if any, and the session stats when `stats` is set.
+    Poll {
+        fields: Vec<TorrentGetField>,
+        detail: Option<i64>,
+        stats: bool,
+    },
+    Details(i64),
+    Start(Selected),
+    StartNow(Selected),
+    Stop(Selected),
+    Verify(Selected),
+    Reannounce(Selected),
+    Queue(Selected, QueueMove),
+    Move {
+        ids: Selected,
+        location: String,
+    },
+    Rename {
+        torrent: Box<Torrent>,
+        name: String,
+    },
+    Remove {
+        ids: Selected,
+        delete_local_data: bool,
+    },
+    Add {
+        source: String,
+        options: AddOptions,
+    },
+    SetFiles {
+        id: i64,
+        files: Vec<usize>,
+        change: FileChange,
+    },
+    SetAltSpeed(bool),
+    SessionLimits(LimitChange),
+    /// Loads the speed limits of a torrent for the limits dialog.
+    Limits(i64),
+    TorrentLimits(Selected, LimitChange),
+    /// Loads the seeding options of torrents for the options dialog.
+    Options(Vec<i64>),
+    TorrentOptions(Selected, OptionsChange),
+    SetLabels(Vec<LabelChange>),
+    /// Loads the trackers of torrents for the tracker dialog.
+    Trackers(Vec<i64>),
+    EditTrackers(Vec<TrackerChange>),
+    Settings,
+    SetSettings(Vec<SettingChange>),
+}
+
+impl Request {
+    /// Returns `true` for requests the app issues on its own, which are not
+    /// shown as in flight.
+    #[must_use]
+    pub const fn is_background(&self) -> bool {
+        matches!(self, Self::Poll { .. })
+    }
+
+    /// Describes the request while it is in flight.
+    #[must_use]
+    pub fn label(&self) -> String {
+        match self {
+            Self::Poll { .. } => "Refreshing".into(),
+            Self::Details(_) => "Loading details".into(),
+            Self::Start(ids) => format!("Starting {}", count(ids)),
+            Self::StartNow(ids) => format!("Starting {} now", count(ids)),
+            Self::Stop(ids) => format!("Stopping {}", count(ids)),
+            Self::Verify(ids) => format!("Verifying {}", count(ids)),
+            Self::Reannounce(ids) => format!("Reannouncing {}", count(ids)),
+            Self::Queue(ids, direction) => {
+                let place = match direction {
+                    QueueMove::Top => "to the top of the queue",
+                    QueueMove::Up => "up the queue",
+                    QueueMove::Down => "down the queue",
+                    QueueMove::Bottom => "to the bottom of the queue",
+                };
+                format!("Moving {} {place}", count(ids))
+            }
+            Self::Move { ids, .. } => format!("Moving {}", count(ids)),
+            Self::Rename { .. } => "Renaming torrent".into(),
+            Self::Remove { ids, .. } => format!("Removing {}", count(ids)),
+            Self::Add { .. } => "Adding torrent".into(),
+            Self::SetFiles { files, .. } => match files.len() {
+                1 => "Updating 1 file".into(),
+                n => format!("Updating {n} files"),
+            },
+            Self::SetAltSpeed(true) => "Enabling alternative speed limits".into(),
+            Self::SetAltSpeed(false) => "Disabling alternative speed limits".into(),
+            Self::SessionLimits(_) => "Setting speed limits".into(),
+            Self::Limits(_) => "Loading limits".into(),
+            Self::TorrentLimits(ids, _) => format!("Setting limits of {}", count(ids)),
+            Self::Options(_) => "Loading options".into(),
+            Self::TorrentOptions(ids, _) => format!("Setting options of {}", count(ids)),
+            Self::SetLabels(changes) => match changes.iter().map(|c| c.ids.len()).sum() {
+                1 => "Labeling 1 torrent".into(),
+                n => format!("Labeling {n} torrents"),
+            },
+            Self::Trackers(_) => "Loading trackers".into(),
+            Self::EditTrackers(changes) => match changes.len() {
+                1 => "Updating trackers of 1 torrent".into(),
+                n => format!("Updating trackers of {n} torrents"),
+            },
+            Self::Settings => "Loading settings".into(),
+            Self::SetSettings(changes) => match changes.len() {
+                1 => "Applying 1 setting".into(),
+                n => format!("Applying {n} settings"),
+            },
+        }
+    }
+}
+
+fn count(ids: &Selected) -> String {
+    match ids.len() {
+        1 => "1 torrent".into(),
+        n => format!("{n} torrents"),
+    }
+}
+
+/// Result of a [`Request`].
+#[derive(Debug)]
+pub enum Outcome {
+    Done,
+    Polled {
+        limits: SpeedLimits,
+        delta: Delta,
+        /// Id and data of the torrent shown in the detail view.
+        detail: Option<(i64, Option<Torrent>)>,
+        /// Fetched separately, so older daemons still get polled.
+        stats: Option<Result<SessionStats>>,
+    },
+    Details(i64, Option<Torrent>),
+    /// Name of the added torrent.
+    Added(String),
+    SpeedLimits(SpeedLimits),
+    /// Id and speed limits of a torrent.
+    Limits(i64, Option<Torrent>),
+    /// Torrents with their seeding options.
+    Options(Vec<Torrent>),
+    /// Torrents with their trackers.
+    Trackers(Vec<Torrent>),
+    Settings(DaemonSettings),
+}
+
+async fn execute(torrents: &mut Torrents, request: Request) -> Result<Outcome> {
+    let done = |result: Result<()>| result.map(|()| Outcome::Done);
+    match request {
+        Request::Poll {
+            fields,
+            detail,
+            stats,
+        } => {
+            // The raw client tells rejected credentials apart, so it goes first.
+            let limits = torrents.speed_limits().await?;
+            torrents.set_fields(Some(fields));
+            let delta = torrents.fetch().await?;
+            let detail = if let Some(id) = detail {
+                Some((id, torrents.get(id, DetailView::fields()).await?))
+            } else {
+                None
+            };
+            let stats = if stats {
+                Some(torrents.session_stats().await)
+            } else {
+                None
+            };
+            Ok(Outcome::Polled {
+                limits,
+                delta,
+                detail,
+                stats,
+            })
+        }
+        Request::Details(id) => Ok(Outcome::Details(
+            id,
+            torrents.get(id, DetailView::fields()).await?,
+        )),
+        Request::Start(ids) => done(torrents.start(ids).await),
+        Request::StartNow(ids) => done(torrents.start_now(ids).await),
+        Request::Stop(ids) => done(torrents.stop(ids).await),
+        Request::Verify(ids) => done(torrents.verify(ids).await),
+        Request::Reannounce(ids) => done(torrents.reannounce(ids).await),
+        Request::Queue(ids, direction) => done(torrents.queue_move(ids, direction).await),
+        Request::Move { ids, location } => done(torrents.move_torrents(ids, &location).await),
+        Request::Rename { torrent, name } => {
+            done(torrents.rename(&torrent, Path::new(&name)).await)
+        }
+        Request::Remove {
+            ids,
+            delete_local_data,
+        } => done(torrents.delete(ids, delete_local_data).await),
+        Request::Add { source, options } => {
+            torrents.add(&source, options).await.map(Outcome::Added)
+        }
+        Request::SetFiles { id, files, change } => {
+            done(torrents.set_files(id, files, change).await)
+        }
+        Request::SetAltSpeed(enabled) => {
+            torrents.set_alt_speed(enabled).await?;
+            Ok(Outcome::SpeedLimits(torrents.speed_limits().await?))
+        }
+        Request::SessionLimits(change) => {
+            torrents.set_session_limits(change).await?;
+            Ok(Outcome::SpeedLimits(torrents.speed_limits().await?))
+        }
+        Request::Limits(id) => Ok(Outcome::Limits(
+            id,
+            torrents.get(id, limits::FIELDS.to_vec()).await?,
+        )),
+        Request::TorrentLimits(ids, change) => done(torrents.set_torrent_limits(ids, change).await),
+        Request::Options(ids) => torrents
+            .get_many(ids, options::FIELDS.to_vec())
+            .await
+            .map(Outcome::Options),
+        Request::TorrentOptions(ids, change) => {
+            done(torrents.set_torrent_options(ids, change).await)
+        }
+        Request::SetLabels(changes) => done(torrents.set_labels(&changes).await),
+        Request::Trackers(ids) => torrents
+            .get_many(ids, trackers::FIELDS.to_vec())
+            .await
+            .map(Outcome::Trackers),
+        Request::EditTrackers(changes) => done(torrents.edit_trackers(&changes).await),
+        Request::Settings => torrents.settings().await.map(Outcome::Settings),
+        Request::SetSettings(changes) => done(torrents.set_settings(&changes).await),
+    }
+}
+
+/// Handle to a task running [`Request`]s one at a time on its own
+/// connection, so a slow daemon never blocks rendering or key handling.
+#[derive(Debug)]
+pub struct Worker {
+    sender: UnboundedSender<(u64, Request)>,
+    receiver: UnboundedReceiver<(u64, Result<Outcome>)>,
+}
+
+impl Worker {
+    /// Spawns the worker task on the current tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Transmission client cannot be configured.
+    pub fn spawn(connection: &Connection) -> Result<Self> {
+        let mut torrents = Torrents::new(connection)?;
+        let (sender, mut requests) = mpsc::unbounded_channel::<(u64, Request)>();
+        let (results, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some((id, request)) = requests.recv().await {
+                let background = request.is_background();
+                let result = execute(&mut torrents, request).await;
+                if background && result.is_err() {
+                    // Removals reported while unreachable are lost; relist on recovery.
+                    torrents.invalidate();
+                }
+                if results.send((id, result)).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { sender, receiver })
+    }
+}
+
+/// A request sent to the [`Worker`] and not answered yet.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: u64,
+    pub label: String,
+    pub background: bool,
+    pub started: Instant,
+}
+
+/// Requests in flight and the [`Worker`] running them.
+#[derive(Debug)]
+pub struct Jobs {
+    connection: Connection,
+    worker: Option<Worker>,
+    next_id: u64,
+    pending: Vec<Job>,
+    /// Whether a poll should be sent once none is in flight.
+    poll_due: bool,
+}
+
+impl Jobs {
+    /// Constructs a new instance of [`Jobs`] for `connection`. The worker is
+    /// spawned by [`Jobs::start`] or the first request.
+    #[must_use]
+    pub const fn new(connection: Connection) -> Self {
+        Self {
+            connection,
+            worker: None,
+            next_id: 0,
+            pending: Vec::new(),
+            poll_due: true,
+        }
+    }
+
+    /// Returns the resolved connection the worker uses.
+    #[must_use]
+    pub const fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Spawns the worker unless it is running.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Transmission client cannot be configured.
+    pub fn start(&mut self) -> Result<()> {
+        if self.worker.is_none() {
+            self.worker = Some(Worker::spawn(&self.connection)?);
+        }
+        Ok(())
+    }
+
+    /// Sends `request` to the worker and returns the id of its job.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker cannot be spawned or has stopped.
+    pub fn submit(&mut self, request: Request) -> Result<u64> {
+        self.start()?;
+        let Some(worker) = &self.worker else {
+            return Err(TraxorError::Disconnected);
+        };
+        let job = Job {
+            id: self.next_id,
+            label: request.label(),
+            background: request.is_background(),
+            started: Instant::now(),
+        };
+        worker
+            .sender
+            .send((job.id, request))
+            .map_err(|_| TraxorError::Disconnected)?;
+        self.next_id += 1;
+        self.poll_due &= !job.background;
+        let id = job.id;
+        self.pending.push(job);
+        Ok(id)
+    }
+
+    /// Waits for the next finished request. Returns `None` right away when
+    /// nothing is in flight. If the worker has stopped, its jobs fail one by
+    /// one and the next request spawns a new worker.
+    pub async fn recv(&mut self) -> Option<(Job, Result<Outcome>)> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        if let Some(worker) = &mut self.worker {
+            if let Some((id, result)) = worker.receiver.recv().await {
+                return self.finish(id, result);
+            }
+            self.worker = None;
+        }
+        Some((self.pending.remove(0), Err(TraxorError::Disconnected)))
+    }
+
+    fn finish(&mut self, id: u64, result: Result<Outcome>) -> Option<(Job, Result<Outcome>)> {
+        let idx = self.pending.iter().position(|job| job.id == id)?;
+        Some((self.pending.remove(idx), result))
+    }
+
+    /// Returns `true` while a background poll is in flight.
+    #[must_use]
+    pub fn polling(&self) -> bool {
+        self.pending.iter().any(|job| job.background)
+    }
+
+    /// Marks a poll as due; it is sent once no poll is in flight.
+    pub const fn mark_poll_due(&mut self) {
+        self.poll_due = true;
+    }
+
+    /// Returns `true` if a poll is due and none is in flight.
+    #[must_use]
+    pub fn poll_ready(&self) -> bool {
+        self.poll_due && !self.polling()
+    }
+
+    /// Requests started by the user, oldest first.
+    pub fn in_flight(&self) -> impl Iterator<Item = &Job> {
+        self.pending.iter().filter(|job| !job.background)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Jobs, Request, Worker};
+    use crate::{
+        app::{
+            Connection,
+            labels::LabelChange,
+            types::{QueueMove, Selected},
+        },
+        config::connection::ConnectionConfig,
+        error::TraxorError,
+    };
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn labels_count_torrents_and_hide_polls() {
+        let ids = Selected::List([1, 2].into());
+        assert_eq!(Request::Verify(ids.clone()).label(), "Verifying 2 torrents");
+        assert_eq!(
+            Request::Queue(Selected::Current(1), QueueMove::Top).label(),
+            "Moving 1 torrent to the top of the queue"
+        );
+        let labels = |ids: Vec<i64>| LabelChange {
+            ids,
+            labels: Vec::new(),
+        };
+        assert_eq!(
+            Request::SetLabels(vec![labels(vec![1]), labels(vec![2, 3])]).label(),
+            "Labeling 3 torrents"
+        );
+        assert!(!Request::Stop(ids).is_background());
+        let poll = Request::Poll {
+            fields: Vec::new(),
+            detail: None,
+            stats: false,
+        };
+        assert!(poll.is_background());
+    }
+
+    #[tokio::test]
+    async fn stopped_worker_fails_pending_jobs() {
+        let Ok(connection) = Connection::resolve(&ConnectionConfig::default()) else {
+            panic!("connection should resolve");
+        };
+        let mut jobs = Jobs::new(connection);
+        let (sender, _requests) = mpsc::unbounded_channel();
+        let (_, receiver) = mpsc::unbounded_channel();
+        jobs.worker = Some(Worker { sender, receiver });
+        let poll = Request::Poll {
+            fields: Vec::new(),
+            detail: None,
+            stats: false,
+        };
+        assert!(jobs.submit(poll).is_ok());
+        assert!(jobs.submit(Request::Stop(Selected::Current(1))).is_ok());
+        assert!(jobs.polling());
+        for _ in 0..2 {
+            let Some((_, Err(TraxorError::Disconnected))) = jobs.recv().await else {
+                panic!("pending jobs should fail once the worker stops");
+            };
+        }
+        assert!(jobs.recv().await.is_none());
+        assert!(!jobs.polling());
+        assert!(jobs.worker.is_none());
+    }
+}
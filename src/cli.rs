@@ -0,0 +1,467 @@
+use crate::{
+    app::{
+        App, Tab, Torrents,
+        types::{AddOptions, Selected},
+        utils::Wrapper,
+    },
+    config::Config,
+    error::{Result, TraxorError},
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::{Map, Value};
+use std::{collections::HashSet, io::Write, path::Path};
+use transmission_rpc::types::{Torrent, TorrentGetField};
+
+/// A terminal UI for managing Transmission torrents.
+///
+/// Without a subcommand the interactive UI is started.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Connection profile to use instead of the configured default.
+    #[arg(short, long, global = true)]
+    pub profile: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Non-interactive subcommands for scripting.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// List torrents with the columns of the selected tab.
+    List {
+        #[command(flatten)]
+        selector: Selector,
+        /// Output format.
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Add torrents from magnet links, URLs or local `.torrent` files.
+    Add {
+        #[arg(required = true)]
+        sources: Vec<String>,
+        /// Download directory; the daemon default is used when omitted.
+        #[arg(short, long)]
+        dir: Option<String>,
+        /// Add without starting.
+        #[arg(long)]
+        paused: bool,
+        /// Label to attach; may be repeated.
+        #[arg(short, long = "label")]
+        labels: Vec<String>,
+    },
+    /// Start torrents.
+    Start {
+        #[command(flatten)]
+        selector: Selector,
+    },
+    /// Stop torrents.
+    Stop {
+        #[command(flatten)]
+        selector: Selector,
+    },
+    /// Remove torrents, keeping their data unless `--delete-data` is given.
+    Remove {
+        #[command(flatten)]
+        selector: Selector,
+        /// Also delete the downloaded data.
+        #[arg(long)]
+        delete_data: bool,
+    },
+    /// Move the data of torrents to a new location.
+    Move {
+        /// New download directory.
+        location: String,
+        #[command(flatten)]
+        selector: Selector,
+    },
+    /// Rename a torrent.
+    Rename {
+        /// Id of the torrent.
+        id: i64,
+        /// New name.
+        name: String,
+    },
+    /// Verify the local data of torrents.
+    Verify {
+        #[command(flatten)]
+        selector: Selector,
+    },
+}
+
+/// Torrents a subcommand works on.
+#[derive(Debug, Clone, Default, Args)]
+pub struct Selector {
+    /// Torrent ids, looked up among all torrents; every torrent of the tab
+    /// matching the filter when omitted.
+    pub ids: Vec<i64>,
+    /// Tab whose torrents, columns and sort order are used, by name or number.
+    #[arg(short, long)]
+    pub tab: Option<String>,
+    /// Filter query, with the same syntax as the search popup.
+    #[arg(short, long)]
+    pub filter: Option<String>,
+}
+
+impl Selector {
+    /// Returns `true` if nothing narrows the selection down. A blank filter
+    /// matches everything, so it does not count.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+            && self.tab.is_none()
+            && self
+                .filter
+                .as_deref()
+                .is_none_or(|filter| filter.trim().is_empty())
+    }
+}
+
+/// Output format of `list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// Aligned columns for humans.
+    #[default]
+    Table,
+    /// Array of objects keyed by RPC field name.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+impl Command {
+    /// Runs the subcommand and writes its output to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the selector is invalid, an RPC call fails or the
+    /// output cannot be written.
+    pub async fn run(self, config: Config, out: &mut impl Write) -> Result<()> {
+        let mut app = App::new(config)?;
+        let mut client = Torrents::new(app.jobs.connection())?;
+        match self {
+            Self::List { selector, format } => {
+                let torrents = select(&mut app, &mut client, &selector).await?;
+                let fields = output_fields(&app.tabs()[app.index()]);
+                let rows = torrents
+                    .iter()
+                    .map(|torrent| {
+                        fields
+                            .iter()
+                            .map(|field| field.value(torrent, &app.config.time))
+                            .collect()
+                    })
+                    .collect::<Vec<Vec<_>>>();
+                match format {
+                    Format::Table => write_table(out, &fields, &rows)?,
+                    Format::Json => write_json(out, &fields, &rows)?,
+                    Format::Csv => write_csv(out, &fields, &rows)?,
+                }
+            }
+            Self::Add {
+                sources,
+                dir,
+                paused,
+                labels,
+            } => {
+                for source in &sources {
+                    let options = AddOptions {
+                        download_dir: dir.clone(),
+                        paused,
+                        labels: labels.clone(),
+                        priority: None,
+                    };
+                    let name = client.add(source, options).await?;
+                    writeln!(out, "added {name}")?;
+                }
+            }
+            Self::Start { selector } => {
+                let ids = targets(&mut app, &mut client, &selector).await?;
+                let count = ids.len();
+                client.start(ids).await?;
+                writeln!(out, "started {count} torrent(s)")?;
+            }
+            Self::Stop { selector } => {
+                let ids = targets(&mut app, &mut client, &selector).await?;
+                let count = ids.len();
+                client.stop(ids).await?;
+                writeln!(out, "stopped {count} torrent(s)")?;
+            }
+            Self::Remove {
+                selector,
+                delete_data,
+            } => {
+                let ids = targets(&mut app, &mut client, &selector).await?;
+                let count = ids.len();
+                client.delete(ids, delete_data).await?;
+                writeln!(out, "removed {count} torrent(s)")?;
+            }
+            Self::Move { location, selector } => {
+                let ids = targets(&mut app, &mut client, &selector).await?;
+                let count = ids.len();
+                client.move_torrents(ids, &location).await?;
+                writeln!(out, "moved {count} torrent(s) to {location}")?;
+            }
+            Self::Rename { id, name } => {
+                let fields = vec![TorrentGetField::Id, TorrentGetField::Name];
+                let torrent = client
+                    .get(id, fields)
+                    .await?
+                    .ok_or(TraxorError::InvalidTorrentId(id))?;
+                client.rename(&torrent, Path::new(&name)).await?;
+                writeln!(out, "renamed {id} to {name}")?;
+            }
+            Self::Verify { selector } => {
+                let ids = targets(&mut app, &mut client, &selector).await?;
+                let count = ids.len();
+                client.verify(ids).await?;
+                writeln!(out, "verifying {count} torrent(s)")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fetches the torrents with the given ids, or else those of the selected
+/// tab that match the filter, in the tab's sort order.
+async fn select(app: &mut App, client: &mut Torrents, selector: &Selector) -> Result<Vec<Torrent>> {
+    if let Some(tab) = &selector.tab {
+        let idx = tab_index(app.tabs(), tab)
+            .ok_or_else(|| TraxorError::InvalidArgument(format!("unknown tab {tab:?}")))?;
+        app.switch_tab(idx);
+    }
+    if let Some(filter) = &selector.filter {
+        app.filter_text.clone_from(filter);
+        app.filter_query()
+            .map_err(|err| TraxorError::InvalidArgument(format!("invalid filter: {err}")))?;
+    }
+    client.set_fields(Some(app.required_fields()));
+    let delta = client.fetch().await?;
+    app.torrents.apply(delta);
+    if !selector.ids.is_empty() {
+        return by_ids(app, &selector.ids);
+    }
+    Ok(app.filtered_torrents().into_iter().cloned().collect())
+}
+
+/// Looks up torrents by id regardless of tab and filter, in the given order.
+fn by_ids(app: &App, ids: &[i64]) -> Result<Vec<Torrent>> {
+    let mut seen = HashSet::new();
+    ids.iter()
+        .filter(|&&id| seen.insert(id))
+        .map(|&id| {
+            app.torrents
+                .torrents
+                .get(id)
+                .cloned()
+                .ok_or(TraxorError::InvalidTorrentId(id))
+        })
+        .collect()
+}
+
+/// Resolves the torrents a mutating subcommand acts on. Refuses to act on
+/// every torrent unless ids, a tab or a filter were given.
+async fn targets(app: &mut App, client: &mut Torrents, selector: &Selector) -> Result<Selected> {
+    if selector.is_empty() {
+        return Err(TraxorError::NoSelection);
+    }
+    let torrents = select(app, client, selector).await?;
+    Ok(Selected::List(
+        torrents.iter().filter_map(|t| t.id).collect(),
+    ))
+}
+
+/// Finds a tab by case-insensitive name or 1-based number.
+fn tab_index(tabs: &[Tab], tab: &str) -> Option<usize> {
+    tabs.iter()
+        .position(|t| t.name().eq_ignore_ascii_case(tab))
+        .or_else(|| {
+            tab.parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .filter(|&idx| idx < tabs.len())
+        })
+}
+
+/// Columns of `tab`, with the id first so scripts can refer back to torrents.
+fn output_fields(tab: &Tab) -> Vec<TorrentGetField> {
+    let mut fields = tab.fields().to_vec();
+    if !fields.contains(&TorrentGetField::Id) {
+        fields.insert(0, TorrentGetField::Id);
+    }
+    fields
+}
+
+fn write_table(
+    out: &mut impl Write,
+    fields: &[TorrentGetField],
+    rows: &[Vec<String>],
+) -> Result<()> {
+    let header: Vec<_> = fields.iter().map(Wrapper::title).collect();
+    let widths: Vec<_> = header
+        .iter()
+        .enumerate()
+        .map(|(idx, title)| {
+            rows.iter()
+                .map(|row| row[idx].chars().count())
+                .chain([title.chars().count()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    for row in std::iter::once(&header).chain(rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(value, &width)| format!("{value:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(out, "{}", line.trim_end())?;
+    }
+    Ok(())
+}
+
+fn write_json(
+    out: &mut impl Write,
+    fields: &[TorrentGetField],
+    rows: &[Vec<String>],
+) -> Result<()> {
+    let objects: Vec<_> = rows
+        .iter()
+        .map(|row| {
+            fields
+                .iter()
+                .zip(row)
+                .map(|(field, value)| (field.to_str(), Value::String(value.clone())))
+                .collect::<Map<_, _>>()
+        })
+        .collect();
+    serde_json::to_writer_pretty(&mut *out, &objects).map_err(std::io::Error::from)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn write_csv(out: &mut impl Write, fields: &[TorrentGetField], rows: &[Vec<String>]) -> Result<()> {
+    let header: Vec<_> = fields.iter().map(TorrentGetField::to_str).collect();
+    for row in std::iter::once(header).chain(rows.iter().cloned()) {
+        let line = row
+            .iter()
+            .map(|value| csv_field(value))
+            .collect::<Vec<_>>()
+            .join(",");
+        writeln!(out, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cli, Command, Format, by_ids, csv_field, tab_index};
+    use crate::{
+        app::{App, Tab, test_utils::from_json},
+        config::Config,
+        error::TraxorError,
+    };
+    use clap::{CommandFactory, Parser};
+    use serde_json::json;
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_selector_and_format() {
+        let Ok(cli) = Cli::try_parse_from([
+            "traxor", "list", "--tab", "peers", "--filter", "ratio<1", "--format", "csv", "-p",
+            "seedbox",
+        ]) else {
+            panic!("list arguments should parse");
+        };
+        assert_eq!(cli.profile.as_deref(), Some("seedbox"));
+        let Some(Command::List { selector, format }) = cli.command else {
+            panic!("expected the list subcommand");
+        };
+        assert_eq!(format, Format::Csv);
+        assert_eq!(selector.tab.as_deref(), Some("peers"));
+        assert_eq!(selector.filter.as_deref(), Some("ratio<1"));
+
+        let Ok(cli) = Cli::try_parse_from(["traxor", "move", "/mnt/tv", "3", "4"]) else {
+            panic!("move arguments should parse");
+        };
+        let Some(Command::Move { location, selector }) = cli.command else {
+            panic!("expected the move subcommand");
+        };
+        assert_eq!(location, "/mnt/tv");
+        assert_eq!(selector.ids, [3, 4]);
+    }
+
+    #[test]
+    fn blank_filter_is_no_selection() {
+        let selector = |args: &[&str]| {
+            let Ok(cli) = Cli::try_parse_from([&["traxor", "stop"], args].concat()) else {
+                panic!("stop arguments should parse");
+            };
+            let Some(Command::Stop { selector }) = cli.command else {
+                panic!("expected the stop subcommand");
+            };
+            selector
+        };
+        assert!(selector(&["--filter", ""]).is_empty());
+        assert!(selector(&["--filter", "  "]).is_empty());
+        assert!(!selector(&["--filter", "ratio<1"]).is_empty());
+    }
+
+    #[test]
+    fn tabs_are_found_by_name_or_number() {
+        let Ok(config) = Config::load() else {
+            panic!("default config should load");
+        };
+        let tabs: Vec<_> = config.tabs.into_iter().map(Tab::new).collect();
+        assert_eq!(tab_index(&tabs, "peers"), Some(2));
+        assert_eq!(tab_index(&tabs, "1"), Some(0));
+        assert_eq!(tab_index(&tabs, "99"), None);
+        assert_eq!(tab_index(&tabs, "0"), None);
+    }
+
+    #[test]
+    fn ids_are_found_outside_the_tab_and_unknown_ones_rejected() {
+        let Ok(mut config) = Config::load() else {
+            panic!("default config should load");
+        };
+        // A first tab that shows nothing, to prove ids are looked up among
+        // all torrents.
+        config.tabs[0].statuses = vec!["verifying".into()];
+        let Ok(mut app) = App::new(config) else {
+            panic!("app should build");
+        };
+        app.torrents.torrents = vec![from_json(json!({ "id": 42, "status": 0 }))].into();
+        assert!(app.filtered_torrents().is_empty());
+
+        let Ok(torrents) = by_ids(&app, &[42, 42]) else {
+            panic!("known id should be found");
+        };
+        assert_eq!(torrents.len(), 1);
+        assert!(matches!(
+            by_ids(&app, &[42, 7]),
+            Err(TraxorError::InvalidTorrentId(7))
+        ));
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
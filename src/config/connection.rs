@@ -0,0 +1,250 @@
+use crate::app::constants::{DEFAULT_RPC_TIMEOUT_SECS, DEFAULT_RPC_URL};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, process::Command, time::Duration};
+use url::Url;
+
+/// Transmission daemon connection settings.
+///
+/// The top-level values form the default connection. Named entries in
+/// `profiles` only need to specify what differs from it.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConnectionConfig {
+    #[serde(default = "default_url")]
+    pub url: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    #[serde(default)]
+    pub password_command: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Profile used when none is given on the command line.
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+}
+
+/// A named connection profile overriding parts of [`ConnectionConfig`].
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct ProfileConfig {
+    pub url: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub password_command: Option<String>,
+    pub timeout_secs: Option<u64>,
+}
+
+/// Connection settings after applying the active profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub password_command: Option<String>,
+    pub timeout: Duration,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            url: default_url(),
+            username: None,
+            password: None,
+            password_command: None,
+            timeout_secs: default_timeout_secs(),
+            profile: None,
+            profiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl ConnectionConfig {
+    /// Select the profile to connect with.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when no profile with that name is configured.
+    pub fn use_profile(&mut self, name: &str) -> Result<(), String> {
+        if !self.profiles.contains_key(name) {
+            return Err(format!(
+                "unknown connection profile {name:?}; available: {}",
+                self.profile_names()
+            ));
+        }
+        self.profile = Some(name.to_owned());
+        Ok(())
+    }
+
+    /// Validate the default connection and every profile.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when a URL is invalid, both `password` and
+    /// `password_command` are set, or the selected profile does not exist.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(name) = &self.profile
+            && !self.profiles.contains_key(name)
+        {
+            return Err(format!(
+                "connection.profile {name:?} is not defined; available: {}",
+                self.profile_names()
+            ));
+        }
+        validate_endpoint("connection", &self.resolve(None))?;
+        for name in self.profiles.keys() {
+            validate_endpoint(
+                &format!("connection.profiles.{name}"),
+                &self.resolve(Some(name)),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the connection settings for the selected profile.
+    #[must_use]
+    pub fn endpoint(&self) -> Endpoint {
+        self.resolve(self.profile.as_deref())
+    }
+
+    fn resolve(&self, profile: Option<&str>) -> Endpoint {
+        let profile = profile
+            .and_then(|name| self.profiles.get(name))
+            .cloned()
+            .unwrap_or_default();
+
+        // A profile that sets either credential source replaces both.
+        let (password, password_command) =
+            if profile.password.is_some() || profile.password_command.is_some() {
+                (profile.password, profile.password_command)
+            } else {
+                (self.password.clone(), self.password_command.clone())
+            };
+
+        Endpoint {
+            url: profile.url.unwrap_or_else(|| self.url.clone()),
+            username: profile.username.or_else(|| self.username.clone()),
+            password,
+            password_command,
+            timeout: Duration::from_secs(profile.timeout_secs.unwrap_or(self.timeout_secs)),
+        }
+    }
+
+    fn profile_names(&self) -> String {
+        if self.profiles.is_empty() {
+            return "none".into();
+        }
+        self.profiles
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl Endpoint {
+    /// Returns the password, running `password_command` if configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the password command cannot be run or exits
+    /// unsuccessfully.
+    pub fn password(&self) -> Result<Option<String>, String> {
+        let Some(command) = &self.password_command else {
+            return Ok(self.password.clone());
+        };
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .output()
+            .map_err(|err| format!("failed to run password_command: {err}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "password_command exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let password = String::from_utf8_lossy(&output.stdout);
+        Ok(Some(password.trim_end_matches(['\r', '\n']).to_owned()))
+    }
+}
+
+fn validate_endpoint(section: &str, endpoint: &Endpoint) -> Result<(), String> {
+    Url::parse(&endpoint.url)
+        .map_err(|err| format!("invalid {section}.url {:?}: {err}", endpoint.url))?;
+    if endpoint.password.is_some() && endpoint.password_command.is_some() {
+        return Err(format!(
+            "{section} sets both password and password_command; use only one"
+        ));
+    }
+    if endpoint.timeout.is_zero() {
+        return Err(format!("{section}.timeout_secs must be greater than 0"));
+    }
+    Ok(())
+}
+
+fn default_url() -> String {
+    DEFAULT_RPC_URL.into()
+}
+
+const fn default_timeout_secs() -> u64 {
+    DEFAULT_RPC_TIMEOUT_SECS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectionConfig, ProfileConfig};
+    use std::time::Duration;
+
+    fn config() -> ConnectionConfig {
+        let mut config = ConnectionConfig {
+            username: Some("local".into()),
+            password: Some("local-secret".into()),
+            ..ConnectionConfig::default()
+        };
+        config.profiles.insert(
+            "seedbox".into(),
+            ProfileConfig {
+                url: Some("https://seedbox.example.com/transmission/rpc".into()),
+                password_command: Some("echo remote-secret".into()),
+                timeout_secs: Some(30),
+                ..ProfileConfig::default()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn profile_overrides_only_what_it_sets() {
+        let mut config = config();
+        assert!(config.use_profile("seedbox").is_ok());
+        let endpoint = config.endpoint();
+        assert_eq!(endpoint.url, "https://seedbox.example.com/transmission/rpc");
+        assert_eq!(endpoint.username.as_deref(), Some("local"));
+        assert_eq!(endpoint.password, None);
+        assert_eq!(endpoint.timeout, Duration::from_secs(30));
+        assert_eq!(endpoint.password(), Ok(Some("remote-secret".into())));
+    }
+
+    #[test]
+    fn unknown_profile_is_rejected() {
+        let mut config = config();
+        let Err(err) = config.use_profile("nas") else {
+            panic!("unknown profile should be rejected");
+        };
+        assert!(err.contains("nas"));
+        assert!(err.contains("seedbox"));
+    }
+
+    #[test]
+    fn rejects_both_password_sources() {
+        let config = ConnectionConfig {
+            password: Some("secret".into()),
+            password_command: Some("echo secret".into()),
+            ..ConnectionConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
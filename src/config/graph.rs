@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// Transfer speed graphs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GraphConfig {
+    /// Samples kept per graph; one is taken on every update.
+    #[serde(default = "default_history")]
+    pub history: usize,
+    /// Show the graphs on startup.
+    #[serde(default)]
+    pub visible: bool,
+}
+
+impl Default for GraphConfig {
+    fn default() -> Self {
+        Self {
+            history: default_history(),
+            visible: false,
+        }
+    }
+}
+
+impl GraphConfig {
+    /// Validate the history length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when `history` keeps fewer than two samples.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.history < 2 {
+            return Err(format!(
+                "invalid graph.history {}; expected at least 2 samples",
+                self.history
+            ));
+        }
+        Ok(())
+    }
+}
+
+const fn default_history() -> usize {
+    150
+}
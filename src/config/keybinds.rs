@@ -23,8 +23,34 @@ pub struct KeybindsConfig {
     pub delete_force: String,
     pub select: String,
     pub toggle_help: String,
+    pub notifications: String,
+    pub graphs: String,
+    pub session_stats: String,
     pub move_torrent: String,
     pub rename_torrent: String,
+    pub add_torrent: String,
+    pub details: String,
+    pub file_wanted: String,
+    pub file_priority: String,
+    pub verify: String,
+    pub reannounce: String,
+    pub start_now: String,
+    pub queue_top: String,
+    pub queue_up: String,
+    pub queue_down: String,
+    pub queue_bottom: String,
+    pub sort_column: String,
+    pub sort_reverse: String,
+    pub alt_speed: String,
+    pub speed_limits: String,
+    pub torrent_limits: String,
+    pub torrent_options: String,
+    pub settings: String,
+    pub trackers: String,
+    pub edit_labels: String,
+    pub label_sidebar: String,
+    pub next_label: String,
+    pub prev_label: String,
     pub filter: String,
     pub clear_filter: String,
 }
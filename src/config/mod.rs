@@ -1,6 +1,9 @@
 pub mod color;
+pub mod connection;
+pub mod graph;
 pub mod keybinds;
 pub mod log;
+pub mod rules;
 pub mod tabs;
 pub mod time;
 
@@ -9,8 +12,11 @@ use color_eyre::{
     Result,
     eyre::{Context, ContextCompat, eyre},
 };
+use connection::ConnectionConfig;
+use graph::GraphConfig;
 use keybinds::KeybindsConfig;
 use log::LogConfig;
+use rules::{AutomationConfig, RuleConfig};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::read_to_string,
@@ -30,9 +36,17 @@ pub struct Config {
     pub colors: ColorConfig,
     pub log: LogConfig,
     #[serde(default)]
+    pub connection: ConnectionConfig,
+    #[serde(default)]
     pub time: TimeConfig,
     #[serde(default)]
+    pub graph: GraphConfig,
+    #[serde(default)]
     pub tabs: Vec<TabConfig>,
+    #[serde(default)]
+    pub automation: AutomationConfig,
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
 }
 
 impl Config {
@@ -78,9 +92,14 @@ impl Config {
 
     fn validate(&self) -> Result<()> {
         self.time.validate().map_err(|err| eyre!(err))?;
+        self.connection.validate().map_err(|err| eyre!(err))?;
+        self.graph.validate().map_err(|err| eyre!(err))?;
         for (idx, tab) in self.tabs.iter().enumerate() {
             tab.validate(idx).map_err(|err| eyre!(err))?;
         }
+        for (idx, rule) in self.rules.iter().enumerate() {
+            rule.validate(idx).map_err(|err| eyre!(err))?;
+        }
         Ok(())
     }
 }
@@ -128,77 +147,34 @@ fn deep_merge(base: &mut Value, other: Value) {
 
 #[cfg(test)]
 mod tests {
-    use super::Config;
+    use super::{Config, DEFAULT_CONFIG, deep_merge};
     use color_eyre::Result;
     use toml::Value;
 
-    fn config_value(input: &str) -> Result<Value> {
-        toml::from_str(input).map_err(Into::into)
+    /// The embedded defaults with `overrides` merged in, as a user config
+    /// would be.
+    fn config_value(overrides: &str) -> Result<Value> {
+        let mut value: Value = toml::from_str(DEFAULT_CONFIG)?;
+        deep_merge(&mut value, toml::from_str(overrides)?);
+        Ok(value)
+    }
+
+    fn validation_error(overrides: &str) -> Result<String> {
+        match Config::from_value(config_value(overrides)?) {
+            Ok(_) => panic!("config should fail validation:\n{overrides}"),
+            Err(err) => Ok(err.to_string()),
+        }
     }
 
     #[test]
     fn rejects_invalid_tab_column() -> Result<()> {
-        let value = config_value(
-            r##"
-            [keybinds]
-            prev_torrent = "k"
-            next_torrent = "j"
-            prev_tab = "h"
-            next_tab = "l"
-            switch_tab_1 = "1"
-            switch_tab_2 = "2"
-            switch_tab_3 = "3"
-            switch_tab_4 = "4"
-            switch_tab_5 = "5"
-            switch_tab_6 = "6"
-            switch_tab_7 = "7"
-            switch_tab_8 = "8"
-            switch_tab_9 = "9"
-            switch_tab_10 = "0"
-            toggle_torrent = "enter"
-            toggle_all = "a"
-            select = " "
-            move_torrent = "m"
-            rename_torrent = "r"
-            delete = "d"
-            delete_force = "D"
-            filter = "/"
-            clear_filter = "escape"
-            toggle_help = "?"
-            quit = "q"
-
-            [colors]
-            highlight_background = "#3a3a5a"
-            highlight_foreground = "white"
-            selected_background = "#245d5a"
-            selected_foreground = "white"
-            header_foreground = "yellow"
-            info_foreground = "blue"
-            status_downloading = "cyan"
-            status_seeding = "white"
-            status_stopped = "dark_gray"
-            status_verifying = "yellow"
-            status_queued = "light_blue"
-
-            [log]
-            traxor = "info"
-            ratatui = "warn"
-            transmission_rpc = "warn"
-
-            [time]
-            date_format = "%Y-%m-%d %H:%M"
-            eta_format = "compact"
-
+        let err = validation_error(
+            r#"
             [[tabs]]
             name = "Broken"
             columns = ["name", "bogus"]
-            "##,
+            "#,
         )?;
-
-        let err = match Config::from_value(value) {
-            Ok(_) => panic!("invalid tab column should fail validation"),
-            Err(err) => err.to_string(),
-        };
         assert!(err.contains("invalid columns"));
         assert!(err.contains("bogus"));
         Ok(())
@@ -206,140 +182,64 @@ mod tests {
 
     #[test]
     fn rejects_invalid_tab_status() -> Result<()> {
-        let value = config_value(
-            r##"
-            [keybinds]
-            prev_torrent = "k"
-            next_torrent = "j"
-            prev_tab = "h"
-            next_tab = "l"
-            switch_tab_1 = "1"
-            switch_tab_2 = "2"
-            switch_tab_3 = "3"
-            switch_tab_4 = "4"
-            switch_tab_5 = "5"
-            switch_tab_6 = "6"
-            switch_tab_7 = "7"
-            switch_tab_8 = "8"
-            switch_tab_9 = "9"
-            switch_tab_10 = "0"
-            toggle_torrent = "enter"
-            toggle_all = "a"
-            select = " "
-            move_torrent = "m"
-            rename_torrent = "r"
-            delete = "d"
-            delete_force = "D"
-            filter = "/"
-            clear_filter = "escape"
-            toggle_help = "?"
-            quit = "q"
-
-            [colors]
-            highlight_background = "#3a3a5a"
-            highlight_foreground = "white"
-            selected_background = "#245d5a"
-            selected_foreground = "white"
-            header_foreground = "yellow"
-            info_foreground = "blue"
-            status_downloading = "cyan"
-            status_seeding = "white"
-            status_stopped = "dark_gray"
-            status_verifying = "yellow"
-            status_queued = "light_blue"
-
-            [log]
-            traxor = "info"
-            ratatui = "warn"
-            transmission_rpc = "warn"
-
-            [time]
-            date_format = "%Y-%m-%d %H:%M"
-            eta_format = "compact"
-
+        let err = validation_error(
+            r#"
             [[tabs]]
             name = "Broken"
             columns = ["name"]
             statuses = ["Flying"]
-            "##,
+            "#,
         )?;
-
-        let err = match Config::from_value(value) {
-            Ok(_) => panic!("invalid tab status should fail validation"),
-            Err(err) => err.to_string(),
-        };
         assert!(err.contains("invalid statuses"));
         assert!(err.contains("Flying"));
         Ok(())
     }
 
     #[test]
-    fn rejects_invalid_eta_format() -> Result<()> {
-        let value = config_value(
-            r##"
-            [keybinds]
-            prev_torrent = "k"
-            next_torrent = "j"
-            prev_tab = "h"
-            next_tab = "l"
-            switch_tab_1 = "1"
-            switch_tab_2 = "2"
-            switch_tab_3 = "3"
-            switch_tab_4 = "4"
-            switch_tab_5 = "5"
-            switch_tab_6 = "6"
-            switch_tab_7 = "7"
-            switch_tab_8 = "8"
-            switch_tab_9 = "9"
-            switch_tab_10 = "0"
-            toggle_torrent = "enter"
-            toggle_all = "a"
-            select = " "
-            move_torrent = "m"
-            rename_torrent = "r"
-            delete = "d"
-            delete_force = "D"
-            filter = "/"
-            clear_filter = "escape"
-            toggle_help = "?"
-            quit = "q"
-
-            [colors]
-            highlight_background = "#3a3a5a"
-            highlight_foreground = "white"
-            selected_background = "#245d5a"
-            selected_foreground = "white"
-            header_foreground = "yellow"
-            info_foreground = "blue"
-            status_downloading = "cyan"
-            status_seeding = "white"
-            status_stopped = "dark_gray"
-            status_verifying = "yellow"
-            status_queued = "light_blue"
+    fn rejects_invalid_tab_sort() -> Result<()> {
+        let err = validation_error(
+            r#"
+            [[tabs]]
+            name = "Broken"
+            columns = ["name"]
+            sort = "name sideways"
+            "#,
+        )?;
+        assert!(err.contains("invalid sort"));
+        assert!(err.contains("name sideways"));
+        Ok(())
+    }
 
-            [log]
-            traxor = "info"
-            ratatui = "warn"
-            transmission_rpc = "warn"
+    #[test]
+    fn rejects_invalid_tab_query() -> Result<()> {
+        let err = validation_error(
+            r#"
+            [[tabs]]
+            name = "Broken"
+            columns = ["name"]
+            query = "ratio<1 ubuntu"
+            "#,
+        )?;
+        assert!(err.contains("invalid query"));
+        assert!(err.contains("ubuntu"));
+        Ok(())
+    }
 
+    #[test]
+    fn rejects_invalid_eta_format() -> Result<()> {
+        let err = validation_error(
+            r#"
             [time]
-            date_format = "%Y-%m-%d %H:%M"
             eta_format = "verbose"
-            "##,
+            "#,
         )?;
-
-        let err = match Config::from_value(value) {
-            Ok(_) => panic!("invalid eta format should fail validation"),
-            Err(err) => err.to_string(),
-        };
         assert!(err.contains("time.eta_format"));
         Ok(())
     }
 
     #[test]
     fn accepts_valid_config() -> Result<()> {
-        let value: Value = toml::from_str(include_str!("../../config/default.toml"))?;
-        Config::from_value(value)?;
+        Config::from_value(config_value("")?)?;
         Ok(())
     }
 }
@@ -0,0 +1,231 @@
+use super::tabs::parse_status;
+use crate::app::query::{Comparison, Filter, Metric, Predicate, Query, parse_duration};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use transmission_rpc::types::{Priority, TorrentStatus};
+
+/// Settings shared by all rules.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AutomationConfig {
+    /// Log what every rule would do without doing it.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// File the audit log is appended to; defaults to `rules.log` in the
+    /// data directory.
+    #[serde(default)]
+    pub audit_log: Option<PathBuf>,
+}
+
+impl AutomationConfig {
+    /// Path of the audit log, if one can be determined.
+    #[must_use]
+    pub fn audit_log_path(&self) -> Option<PathBuf> {
+        self.audit_log
+            .clone()
+            .or_else(|| dirs::data_local_dir().map(|dir| dir.join("traxor").join("rules.log")))
+    }
+}
+
+/// What a rule does to the torrents it matches.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleAction {
+    Stop,
+    Remove { delete_local_data: bool },
+    Move(String),
+    Label(String),
+    Priority(Priority),
+}
+
+/// A housekeeping rule applied on every update to the torrents matching all
+/// of its conditions.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RuleConfig {
+    pub name: String,
+    /// Only torrents in any of these statuses.
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    /// Only torrents with at least this upload ratio.
+    #[serde(default)]
+    pub min_ratio: Option<f64>,
+    /// Only torrents seeding for at least this long, e.g. `"7d"` or `"12h"`.
+    #[serde(default)]
+    pub min_seeding_time: Option<String>,
+    /// Only torrents with any of these labels (case-insensitive).
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only torrents with a tracker whose announce URL contains any of these.
+    #[serde(default)]
+    pub trackers: Vec<String>,
+    /// Only torrents with (`true`) or without (`false`) an error.
+    #[serde(default)]
+    pub error: Option<bool>,
+    /// Further conditions as a filter query, e.g. `"size>4G"`.
+    #[serde(default)]
+    pub query: Option<String>,
+    /// One of `stop`, `remove`, `remove-with-data`, `move`, `label` or
+    /// `priority`.
+    pub action: String,
+    /// Directory to move to, for `move`.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Label to add, for `label`.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// `low`, `normal` or `high`, for `priority`.
+    #[serde(default)]
+    pub priority: Option<String>,
+    /// Log what the rule would do without doing it.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl RuleConfig {
+    /// Validate the conditions and the action with its argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the rule and the invalid setting.
+    pub fn validate(&self, idx: usize) -> Result<(), String> {
+        let invalid = |what: String| format!("invalid {what} in rules[{idx}] ({:?})", self.name);
+
+        let invalid_statuses = self
+            .statuses
+            .iter()
+            .filter(|status| parse_status(status).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        if !invalid_statuses.is_empty() {
+            return Err(invalid(format!("statuses {}", invalid_statuses.join(", "))));
+        }
+
+        for (key, values) in [("labels", &self.labels), ("trackers", &self.trackers)] {
+            if values.iter().any(|value| value.trim().is_empty()) {
+                return Err(invalid(format!("empty value in {key}")));
+            }
+        }
+
+        if let Some(ratio) = self.min_ratio
+            && !(ratio.is_finite() && ratio >= 0.0)
+        {
+            return Err(invalid(format!("min_ratio {ratio}; expected at least 0")));
+        }
+
+        if let Some(time) = &self.min_seeding_time
+            && parse_duration(time).is_none()
+        {
+            return Err(invalid(format!(
+                "min_seeding_time {time:?}; expected a duration such as \"90m\", \"12h\" or \"7d\""
+            )));
+        }
+
+        if let Some(query) = &self.query
+            && let Err(err) = Query::parse_strict(query)
+        {
+            return Err(invalid(format!("query: {err}")));
+        }
+
+        self.parse_action().map(|_| ()).map_err(invalid)
+    }
+
+    /// Parse the action with its argument.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Self::validate`] succeeds for this rule.
+    #[must_use]
+    pub fn action(&self) -> RuleAction {
+        self.parse_action()
+            .expect("rule action should be validated before use")
+    }
+
+    /// Build the conditions besides statuses, labels and trackers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Self::validate`] succeeds for this rule.
+    #[must_use]
+    pub fn query(&self) -> Query {
+        let mut query = self
+            .query
+            .as_deref()
+            .map(|query| {
+                Query::parse_strict(query).expect("rule query should be validated before use")
+            })
+            .unwrap_or_default();
+        let seeding_time = self.min_seeding_time.as_deref().map(|time| {
+            parse_duration(time).expect("rule seeding time should be validated before use")
+        });
+        let predicates = [
+            self.min_ratio
+                .map(|ratio| Predicate::Compare(Metric::Ratio, Comparison::Ge, ratio)),
+            seeding_time.map(|secs| Predicate::Compare(Metric::SeedingTime, Comparison::Ge, secs)),
+            self.error.map(Predicate::Error),
+        ];
+        query
+            .filters
+            .extend(predicates.into_iter().flatten().map(|predicate| Filter {
+                negated: false,
+                predicate,
+            }));
+        query
+    }
+
+    /// Parse status strings into `TorrentStatus` variants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Self::validate`] succeeds for this rule.
+    #[must_use]
+    pub fn statuses(&self) -> Vec<TorrentStatus> {
+        self.statuses
+            .iter()
+            .map(|status| {
+                parse_status(status).expect("rule statuses should be validated before use")
+            })
+            .collect()
+    }
+
+    fn parse_action(&self) -> Result<RuleAction, String> {
+        let argument = |key: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_owned)
+                .ok_or_else(|| format!("action {:?}; it needs `{key}`", self.action))
+        };
+        Ok(match self.action.to_lowercase().as_str() {
+            "stop" => RuleAction::Stop,
+            "remove" => RuleAction::Remove {
+                delete_local_data: false,
+            },
+            "remove-with-data" => RuleAction::Remove {
+                delete_local_data: true,
+            },
+            "move" => RuleAction::Move(argument("location", &self.location)?),
+            "label" => RuleAction::Label(argument("label", &self.label)?),
+            "priority" => {
+                let priority = argument("priority", &self.priority)?;
+                RuleAction::Priority(parse_priority(&priority).ok_or_else(|| {
+                    format!("priority {priority:?}; expected \"low\", \"normal\" or \"high\"")
+                })?)
+            }
+            _ => {
+                return Err(format!(
+                    "action {:?}; expected \"stop\", \"remove\", \"remove-with-data\", \
+                     \"move\", \"label\" or \"priority\"",
+                    self.action
+                ));
+            }
+        })
+    }
+}
+
+fn parse_priority(priority: &str) -> Option<Priority> {
+    Some(match priority.to_lowercase().as_str() {
+        "low" => Priority::Low,
+        "normal" => Priority::Normal,
+        "high" => Priority::High,
+        _ => return None,
+    })
+}
@@ -1,3 +1,7 @@
+use crate::app::{
+    query::{Filter, Predicate, Query},
+    sort::{SortDirection, SortOrder},
+};
 use serde::{Deserialize, Serialize};
 use transmission_rpc::types::{TorrentGetField, TorrentStatus};
 
@@ -7,6 +11,29 @@ pub struct TabConfig {
     pub columns: Vec<String>,
     #[serde(default)]
     pub statuses: Vec<String>,
+    /// Default sort order, e.g. `"ratio desc"`. The column does not have to
+    /// be shown in the tab.
+    #[serde(default)]
+    pub sort: Option<String>,
+    /// Only torrents with any of these labels (case-insensitive).
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Only torrents with a tracker whose announce URL contains any of these.
+    #[serde(default)]
+    pub trackers: Vec<String>,
+    /// Only torrents in this download directory or its subdirectories.
+    #[serde(default)]
+    pub download_dir: Option<String>,
+    /// Only torrents with (`true`) or without (`false`) an error.
+    #[serde(default)]
+    pub error: Option<bool>,
+    /// Only private (`true`) or public (`false`) torrents.
+    #[serde(default)]
+    pub private: Option<bool>,
+    /// Saved filter query, e.g. `"ratio<1 size>4G"`. Bare words are not
+    /// allowed; quote them to match names.
+    #[serde(default)]
+    pub query: Option<String>,
 }
 
 impl TabConfig {
@@ -44,9 +71,59 @@ impl TabConfig {
             ));
         }
 
+        for (key, values) in [("labels", &self.labels), ("trackers", &self.trackers)] {
+            if values.iter().any(|value| value.trim().is_empty()) {
+                return Err(format!(
+                    "empty value in tabs[{idx}].{key} ({:?})",
+                    self.name
+                ));
+            }
+        }
+
+        if self
+            .download_dir
+            .as_ref()
+            .is_some_and(|dir| dir.trim().is_empty())
+        {
+            return Err(format!(
+                "empty download_dir in tabs[{idx}] ({:?})",
+                self.name
+            ));
+        }
+
+        if let Some(query) = &self.query
+            && let Err(err) = Query::parse_strict(query)
+        {
+            return Err(format!(
+                "invalid query in tabs[{idx}] ({:?}): {err}",
+                self.name
+            ));
+        }
+
+        if let Some(sort) = &self.sort
+            && parse_sort(sort).is_none()
+        {
+            return Err(format!(
+                "invalid sort in tabs[{idx}] ({:?}): {sort:?}; expected \"<column> [asc|desc]\"",
+                self.name
+            ));
+        }
+
         Ok(())
     }
 
+    /// Parse the default sort order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Self::validate`] succeeds for this tab.
+    #[must_use]
+    pub fn sort_order(&self) -> Option<SortOrder> {
+        self.sort
+            .as_deref()
+            .map(|sort| parse_sort(sort).expect("tab sort should be validated before use"))
+    }
+
     /// Parse column strings into `TorrentGetField` variants.
     ///
     /// # Panics
@@ -60,6 +137,35 @@ impl TabConfig {
             .collect()
     }
 
+    /// Build the predicates that must all match, from the directory, error,
+    /// privacy and saved query settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called before [`Self::validate`] succeeds for this tab.
+    #[must_use]
+    pub fn query(&self) -> Query {
+        let mut query = self
+            .query
+            .as_deref()
+            .map(|query| {
+                Query::parse_strict(query).expect("tab query should be validated before use")
+            })
+            .unwrap_or_default();
+        let predicates = [
+            self.download_dir.clone().map(Predicate::Path),
+            self.error.map(Predicate::Error),
+            self.private.map(Predicate::Private),
+        ];
+        query
+            .filters
+            .extend(predicates.into_iter().flatten().map(|predicate| Filter {
+                negated: false,
+                predicate,
+            }));
+        query
+    }
+
     /// Parse status strings into `TorrentStatus` variants.
     ///
     /// # Panics
@@ -76,6 +182,20 @@ impl TabConfig {
     }
 }
 
+fn parse_sort(s: &str) -> Option<SortOrder> {
+    let mut parts = s.split_whitespace();
+    let field = parse_field(parts.next()?)?;
+    let direction = match parts.next().map(str::to_lowercase).as_deref() {
+        None | Some("asc" | "ascending") => SortDirection::Ascending,
+        Some("desc" | "descending") => SortDirection::Descending,
+        Some(_) => return None,
+    };
+    parts
+        .next()
+        .is_none()
+        .then_some(SortOrder::new(field, direction))
+}
+
 fn parse_field(s: &str) -> Option<TorrentGetField> {
     Some(match s.to_lowercase().as_str() {
         "name" => TorrentGetField::Name,
@@ -110,7 +230,7 @@ fn parse_field(s: &str) -> Option<TorrentGetField> {
     })
 }
 
-fn parse_status(status: &str) -> Option<TorrentStatus> {
+pub(crate) fn parse_status(status: &str) -> Option<TorrentStatus> {
     Some(match status.to_lowercase().as_str() {
         "stopped" => TorrentStatus::Stopped,
         "queuedtoverify" | "queued_to_verify" | "queued-verify" => TorrentStatus::QueuedToVerify,
@@ -11,19 +11,67 @@ pub enum TraxorError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("HTTP client error: {0}")]
+    Http(#[from] reqwest::Error),
+
     #[error("URL parse error: {0}")]
     UrlParse(#[from] url::ParseError),
 
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Transmission rejected the credentials")]
+    Unauthorized,
+
+    #[error("Not connected to Transmission")]
+    Disconnected,
+
     #[error("No torrent selected")]
     NoSelection,
 
     #[error("Invalid torrent ID: {0}")]
     InvalidTorrentId(i64),
+
+    /// Some torrents of a per-torrent edit were changed before others failed.
+    #[error("{}", partial_failure(applied, failed))]
+    PartialFailure {
+        applied: Vec<String>,
+        failed: Vec<(String, String)>,
+    },
+}
+
+fn partial_failure(applied: &[String], failed: &[(String, String)]) -> String {
+    let failed = failed
+        .iter()
+        .map(|(name, err)| format!("{name} ({err})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if applied.is_empty() {
+        format!("failed for {failed}")
+    } else {
+        format!("applied to {}; failed for {failed}", applied.join(", "))
+    }
+}
+
+impl TraxorError {
+    /// Returns `true` if the daemon could not be reached or refused the
+    /// credentials, as opposed to rejecting a request.
+    #[must_use]
+    pub fn is_transport(&self) -> bool {
+        match self {
+            Self::Http(err) => !err.is_decode(),
+            Self::Unauthorized => true,
+            _ => false,
+        }
+    }
 }
 
 impl From<Box<dyn std::error::Error + Send + Sync>> for TraxorError {
     fn from(e: Box<dyn std::error::Error + Send + Sync>) -> Self {
-        Self::TransmissionRpc(e.to_string())
+        match e.downcast::<reqwest::Error>() {
+            Ok(err) => Self::Http(*err),
+            Err(e) => Self::TransmissionRpc(e.to_string()),
+        }
     }
 }
 
@@ -1,17 +1,16 @@
-use color_eyre::Result;
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent};
-use std::{
-    sync::mpsc,
-    thread,
-    time::{Duration, Instant},
-};
-use tracing::error;
+use crate::app::constants::TORRENT_UPDATE_INTERVAL_SECS;
+use color_eyre::{Result, eyre::eyre};
+use crossterm::event::{self, Event as CrosstermEvent, EventStream, KeyEvent, MouseEvent};
+use futures_util::StreamExt;
+use tokio::time::{self, Duration, Instant};
 
 /// Terminal events.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Event {
     /// Terminal tick.
     Tick,
+    /// Time to poll the daemon for torrent updates.
+    Update,
     /// Key press.
     Key(KeyEvent),
     /// Mouse click/scroll.
@@ -21,66 +20,70 @@ pub enum Event {
 }
 
 /// Terminal event handler.
+///
+/// Reads terminal input from crossterm's async [`EventStream`] and
+/// interleaves it with the tick and torrent update timers.
 #[derive(Debug)]
 pub struct EventHandler {
-    receiver: mpsc::Receiver<Event>,
-    #[allow(dead_code)]
-    handler: thread::JoinHandle<()>,
+    /// Opened on the first call to [`EventHandler::next`].
+    events: Option<EventStream>,
+    tick_rate: Duration,
+    update_rate: Duration,
+    next_tick: Instant,
+    next_update: Instant,
 }
 
 impl EventHandler {
-    /// Constructs a new instance of [`EventHandler`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if event polling or sending fails.
+    /// Constructs a new instance of [`EventHandler`]. The first update is due
+    /// right away.
     #[must_use]
     pub fn new(tick_rate_ms: u64) -> Self {
         let tick_rate = Duration::from_millis(tick_rate_ms);
-        let (sender, receiver) = mpsc::channel();
-
-        let handler = thread::spawn(move || {
-            let mut last_tick = Instant::now();
-            loop {
-                let timeout = tick_rate.saturating_sub(last_tick.elapsed());
-
-                if event::poll(timeout).expect("event polling failed") {
-                    let send_result = match event::read() {
-                        Ok(CrosstermEvent::Key(e)) => sender.send(Event::Key(e)),
-                        Ok(CrosstermEvent::Mouse(e)) => sender.send(Event::Mouse(e)),
-                        Ok(CrosstermEvent::Resize(w, h)) => sender.send(Event::Resize(w, h)),
-                        Ok(_) => Ok(()),
-                        Err(e) => {
-                            error!("Error reading event: {e:?}");
-                            break;
-                        }
-                    };
-                    if send_result.is_err() {
-                        break;
-                    }
-                }
-
-                if last_tick.elapsed() >= tick_rate {
-                    if sender.send(Event::Tick).is_err() {
-                        break;
-                    }
-                    last_tick = Instant::now();
-                }
-            }
-        });
-
-        Self { receiver, handler }
+        let now = Instant::now();
+        Self {
+            events: None,
+            tick_rate,
+            update_rate: Duration::from_secs(TORRENT_UPDATE_INTERVAL_SECS),
+            next_tick: now + tick_rate,
+            next_update: now,
+        }
     }
 
-    /// Receive the next event from the handler thread.
-    ///
-    /// This function will always block the current thread if
-    /// there is no data available and it's possible for more data to be sent.
+    /// Waits for the next event. Terminal events crossterm does not map to an
+    /// [`Event`], such as focus changes, are skipped.
     ///
     /// # Errors
     ///
-    /// Returns an error if the sender is disconnected.
-    pub fn next(&self) -> Result<Event> {
-        Ok(self.receiver.recv()?)
+    /// Returns an error if reading from the terminal fails or its event
+    /// stream ends.
+    pub async fn next(&mut self) -> Result<Event> {
+        let events = if let Some(events) = &mut self.events {
+            events
+        } else {
+            // Opening the stream panics without a terminal to read from;
+            // a zero-timeout poll reports that as an error instead.
+            event::poll(Duration::ZERO)?;
+            self.events.insert(EventStream::new())
+        };
+        loop {
+            tokio::select! {
+                () = time::sleep_until(self.next_tick) => {
+                    self.next_tick = Instant::now() + self.tick_rate;
+                    return Ok(Event::Tick);
+                }
+                () = time::sleep_until(self.next_update) => {
+                    self.next_update = Instant::now() + self.update_rate;
+                    return Ok(Event::Update);
+                }
+                event = events.next() => match event {
+                    Some(Ok(CrosstermEvent::Key(e))) => return Ok(Event::Key(e)),
+                    Some(Ok(CrosstermEvent::Mouse(e))) => return Ok(Event::Mouse(e)),
+                    Some(Ok(CrosstermEvent::Resize(w, h))) => return Ok(Event::Resize(w, h)),
+                    Some(Ok(_)) => {}
+                    Some(Err(err)) => return Err(err.into()),
+                    None => return Err(eyre!("terminal event stream ended")),
+                },
+            }
+        }
     }
 }
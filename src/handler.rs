@@ -1,9 +1,11 @@
-use crate::app::{App, InputMode, action::Action};
-use crate::error::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::app::{App, InputMode, action::Action, layout::ClickTarget, types::QueueMove};
+use crate::error::{Result, TraxorError};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 use thiserror::Error;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+#[allow(clippy::too_many_lines)]
 #[tracing::instrument(name = "Handling input", skip(app))]
 async fn handle_input(key_event: KeyEvent, app: &mut App) -> Result<Option<Action>> {
     // Handle confirmation dialogs separately
@@ -15,6 +17,125 @@ async fn handle_input(key_event: KeyEvent, app: &mut App) -> Result<Option<Actio
         };
     }
 
+    if app.input_mode == InputMode::Limits {
+        match key_event.code {
+            KeyCode::Down | KeyCode::Tab => {
+                app.limits_form.next_field();
+                return Ok(None);
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                app.limits_form.prev_field();
+                return Ok(None);
+            }
+            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right
+                if app.active_input_mut().is_none() =>
+            {
+                app.limits_form.toggle();
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
+    if app.input_mode == InputMode::Options
+        && let Some(form) = &mut app.options_form
+    {
+        match key_event.code {
+            KeyCode::Down | KeyCode::Tab => {
+                form.next_field();
+                return Ok(None);
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                form.prev_field();
+                return Ok(None);
+            }
+            KeyCode::Right | KeyCode::Char(' ') if form.focused_input_mut().is_none() => {
+                form.cycle(true);
+                return Ok(None);
+            }
+            KeyCode::Left if form.focused_input_mut().is_none() => {
+                form.cycle(false);
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
+    if app.input_mode == InputMode::Trackers
+        && let Some(form) = &mut app.trackers_form
+    {
+        match key_event.code {
+            KeyCode::Down | KeyCode::Tab => {
+                form.next_field();
+                return Ok(None);
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                form.prev_field();
+                return Ok(None);
+            }
+            KeyCode::Right | KeyCode::Char(' ') if form.focused_input_mut().is_none() => {
+                form.cycle(true);
+                return Ok(None);
+            }
+            KeyCode::Left if form.focused_input_mut().is_none() => {
+                form.cycle(false);
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
+    if app.input_mode == InputMode::Settings
+        && let Some(form) = &mut app.settings_form
+    {
+        let editing = form.preview.is_none();
+        match key_event.code {
+            KeyCode::Esc if !editing => {
+                form.preview = None;
+                return Ok(None);
+            }
+            KeyCode::Down if editing => {
+                form.next_field();
+                return Ok(None);
+            }
+            KeyCode::Up | KeyCode::BackTab if editing => {
+                form.prev_field();
+                return Ok(None);
+            }
+            KeyCode::Right | KeyCode::Char(' ') if form.focused_input_mut().is_none() => {
+                form.cycle(true);
+                return Ok(None);
+            }
+            KeyCode::Left if form.focused_input_mut().is_none() => {
+                form.cycle(false);
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
+    if app.input_mode == InputMode::Add {
+        match key_event.code {
+            KeyCode::Down => {
+                app.add_form.next_field();
+                return Ok(None);
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                app.add_form.prev_field();
+                return Ok(None);
+            }
+            KeyCode::Right | KeyCode::Char(' ') if app.active_input_mut().is_none() => {
+                app.add_form.cycle(true);
+                return Ok(None);
+            }
+            KeyCode::Left if app.active_input_mut().is_none() => {
+                app.add_form.cycle(false);
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
     match key_event.code {
         KeyCode::Enter => Ok(Some(Action::Submit)),
         KeyCode::Tab => {
@@ -22,11 +143,15 @@ async fn handle_input(key_event: KeyEvent, app: &mut App) -> Result<Option<Actio
             Ok(None)
         }
         KeyCode::Char(ch) => {
-            app.input_handler.insert_char(ch);
+            if let Some(input) = app.active_input_mut() {
+                input.insert_char(ch);
+            }
             Ok(None)
         }
         KeyCode::Backspace => {
-            app.input_handler.delete_char();
+            if let Some(input) = app.active_input_mut() {
+                input.delete_char();
+            }
             Ok(None)
         }
         KeyCode::Esc => Ok(Some(Action::Cancel)),
@@ -49,11 +174,43 @@ pub async fn get_action(key_event: KeyEvent, app: &mut App) -> Result<Option<Act
     if app.show_help && key_event.code == KeyCode::Esc {
         return Ok(Some(Action::ToggleHelp));
     }
+    if app.show_notifications && key_event.code == KeyCode::Esc {
+        return Ok(Some(Action::ToggleNotifications));
+    }
+    if app.stats.visible && key_event.code == KeyCode::Esc {
+        return Ok(Some(Action::ToggleStats));
+    }
 
     debug!("handling key event: {:?}", key_event);
 
     let keybinds = &app.config.keybinds;
 
+    if app.detail.is_some() {
+        if key_event.code == KeyCode::Esc {
+            return Ok(Some(Action::CloseDetails));
+        }
+        return Ok([
+            (Action::Quit, &keybinds.quit),
+            (Action::CloseDetails, &keybinds.details),
+            (Action::NextPane, &keybinds.next_tab),
+            (Action::PrevPane, &keybinds.prev_tab),
+            (Action::NextTorrent, &keybinds.next_torrent),
+            (Action::PrevTorrent, &keybinds.prev_torrent),
+            (Action::ToggleHelp, &keybinds.toggle_help),
+            (Action::ToggleNotifications, &keybinds.notifications),
+            (Action::ToggleGraphs, &keybinds.graphs),
+            (Action::ToggleStats, &keybinds.session_stats),
+            (Action::Trackers, &keybinds.trackers),
+            (Action::Select, &keybinds.select),
+            (Action::ToggleAll, &keybinds.toggle_all),
+            (Action::SelectFiles, &keybinds.filter),
+            (Action::ToggleFileWanted, &keybinds.file_wanted),
+            (Action::CycleFilePriority, &keybinds.file_priority),
+        ]
+        .into_iter()
+        .find_map(|(action, keybind)| matches_keybind(&key_event, keybind).then_some(action)));
+    }
+
     Ok([
         (Action::Quit, &keybinds.quit),
         (Action::NextTab, &keybinds.next_tab),
@@ -76,8 +233,32 @@ pub async fn get_action(key_event: KeyEvent, app: &mut App) -> Result<Option<Act
         (Action::Delete(true), &keybinds.delete_force),
         (Action::Select, &keybinds.select),
         (Action::ToggleHelp, &keybinds.toggle_help),
+        (Action::ToggleNotifications, &keybinds.notifications),
+        (Action::ToggleGraphs, &keybinds.graphs),
+        (Action::ToggleStats, &keybinds.session_stats),
         (Action::Move, &keybinds.move_torrent),
         (Action::Rename, &keybinds.rename_torrent),
+        (Action::Add, &keybinds.add_torrent),
+        (Action::OpenDetails, &keybinds.details),
+        (Action::Verify, &keybinds.verify),
+        (Action::Reannounce, &keybinds.reannounce),
+        (Action::Trackers, &keybinds.trackers),
+        (Action::StartNow, &keybinds.start_now),
+        (Action::Queue(QueueMove::Top), &keybinds.queue_top),
+        (Action::Queue(QueueMove::Up), &keybinds.queue_up),
+        (Action::Queue(QueueMove::Down), &keybinds.queue_down),
+        (Action::Queue(QueueMove::Bottom), &keybinds.queue_bottom),
+        (Action::SortColumn, &keybinds.sort_column),
+        (Action::SortReverse, &keybinds.sort_reverse),
+        (Action::ToggleAltSpeed, &keybinds.alt_speed),
+        (Action::SessionLimits, &keybinds.speed_limits),
+        (Action::TorrentLimits, &keybinds.torrent_limits),
+        (Action::TorrentOptions, &keybinds.torrent_options),
+        (Action::Settings, &keybinds.settings),
+        (Action::EditLabels, &keybinds.edit_labels),
+        (Action::ToggleLabels, &keybinds.label_sidebar),
+        (Action::NextLabel, &keybinds.next_label),
+        (Action::PrevLabel, &keybinds.prev_label),
         (Action::Filter, &keybinds.filter),
         (Action::ClearFilter, &keybinds.clear_filter),
     ]
@@ -85,14 +266,122 @@ pub async fn get_action(key_event: KeyEvent, app: &mut App) -> Result<Option<Act
     .find_map(|(action, keybind)| matches_keybind(&key_event, keybind).then_some(action)))
 }
 
+/// Handles a key event end to end.
+///
+/// Failures are reported as notifications instead of being returned, so a
+/// failed RPC call does not end the session.
+pub async fn handle_key_event(key_event: KeyEvent, app: &mut App) {
+    let result = match get_action(key_event, app).await {
+        Ok(Some(action)) => update(app, action),
+        Ok(None) => Ok(()),
+        Err(err) => Err(err),
+    };
+    report(app, result);
+}
+
+/// Maps a mouse event to an action using the areas of the last frame.
+///
+/// Clicking outside a popup closes it; while one is open nothing else
+/// reacts. The wheel scrolls the torrent list or the detail view.
+#[must_use]
+pub fn get_mouse_action(mouse_event: MouseEvent, app: &App) -> Option<Action> {
+    let areas = &app.click_areas;
+    let position = Position::new(mouse_event.column, mouse_event.row);
+    match mouse_event.kind {
+        MouseEventKind::ScrollDown if areas.popup.is_none() => Some(Action::NextTorrent),
+        MouseEventKind::ScrollUp if areas.popup.is_none() => Some(Action::PrevTorrent),
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(popup) = areas.popup {
+                if popup.contains(position) {
+                    return None;
+                }
+                return close_popup_action(app);
+            }
+            match areas.target(position)? {
+                ClickTarget::Tab(idx) => u8::try_from(idx).ok().map(Action::SwitchTab),
+                _ if app.detail.is_some() => None,
+                ClickTarget::Header(idx) => Some(Action::SortBy(idx)),
+                ClickTarget::Label(row) => {
+                    Some(Action::FilterLabel(app.labels.state.offset() + row))
+                }
+                ClickTarget::Row(row) => {
+                    let idx = app.state.offset() + row;
+                    if mouse_event.modifiers.contains(KeyModifiers::CONTROL) {
+                        Some(Action::SelectRow(idx))
+                    } else {
+                        Some(Action::Highlight(idx))
+                    }
+                }
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Action closing the topmost popup.
+fn close_popup_action(app: &App) -> Option<Action> {
+    if app.input_mode != InputMode::None {
+        Some(Action::Cancel)
+    } else if app.show_help {
+        Some(Action::ToggleHelp)
+    } else if app.show_notifications {
+        Some(Action::ToggleNotifications)
+    } else if app.stats.visible {
+        Some(Action::ToggleStats)
+    } else {
+        None
+    }
+}
+
+/// Handles a mouse event end to end, reporting failures like
+/// [`handle_key_event`].
+pub fn handle_mouse_event(mouse_event: MouseEvent, app: &mut App) {
+    if let Some(action) = get_mouse_action(mouse_event, app) {
+        // Tabs stay clickable over the detail view, which a click leaves.
+        if matches!(action, Action::SwitchTab(_)) {
+            app.close_details();
+        }
+        let result = update(app, action);
+        report(app, result);
+    }
+}
+
+fn report(app: &mut App, result: Result<()>) {
+    match result {
+        Err(TraxorError::Disconnected) => app
+            .notifications
+            .warning("Not connected to Transmission; action skipped"),
+        Err(err) => {
+            warn!("action failed: {err}");
+            app.notifications.error(err.to_string());
+        }
+        Ok(()) => {}
+    }
+}
+
 /// Handles the updates of [`App`].
 ///
 /// # Errors
 ///
 /// Returns an error if the action fails.
+#[allow(clippy::too_many_lines)]
 #[tracing::instrument(name = "Update", skip(app))]
-pub async fn update(app: &mut App, action: Action) -> Result<()> {
+pub fn update(app: &mut App, action: Action) -> Result<()> {
     info!("updating app with action: {}", action);
+    if !app.connection.is_usable() && mutates(action, app) {
+        return Err(TraxorError::Disconnected);
+    }
+    let view_changed = matches!(
+        action,
+        Action::NextTab
+            | Action::PrevTab
+            | Action::SwitchTab(_)
+            | Action::SortColumn
+            | Action::SortBy(_)
+            | Action::SortReverse
+            | Action::ClearFilter
+            | Action::Submit
+    );
     match action {
         Action::Quit => app.quit(),
         Action::NextTab => app.next_tab(),
@@ -100,32 +389,133 @@ pub async fn update(app: &mut App, action: Action) -> Result<()> {
         Action::NextTorrent => app.next(),
         Action::PrevTorrent => app.previous(),
         Action::SwitchTab(x) => app.switch_tab(x as usize),
+        Action::Highlight(idx) => app.highlight(idx),
+        Action::SelectRow(idx) => app.select_row(idx),
         Action::ToggleHelp => app.toggle_help(),
-        Action::ToggleTorrent => app.toggle_torrents().await?,
-        Action::ToggleAll => app.torrents.toggle_all().await?,
-        Action::PauseAll => app.torrents.stop_all().await?,
-        Action::StartAll => app.torrents.start_all().await?,
+        Action::ToggleNotifications => app.toggle_notifications(),
+        Action::ToggleGraphs => app.history.toggle(),
+        Action::ToggleStats => {
+            app.stats.toggle();
+            // Fetch the totals with the next tick rather than the next update.
+            if app.stats.visible {
+                app.jobs.mark_poll_due();
+            }
+        }
+        Action::ToggleTorrent => app.toggle_torrents()?,
+        Action::ToggleAll => match &mut app.detail {
+            Some(detail) => detail.toggle_all_files(),
+            None => app.toggle_all()?,
+        },
+        Action::PauseAll => app.stop_all()?,
+        Action::StartAll => app.start_all()?,
         Action::Move => app.prepare_move_action(),
         Action::Rename => app.prepare_rename_action(),
+        Action::Add => app.prepare_add_action(),
+        Action::OpenDetails => app.open_details()?,
+        Action::CloseDetails => app.close_details(),
+        Action::NextPane => {
+            if let Some(detail) = &mut app.detail {
+                detail.next_pane();
+            }
+        }
+        Action::ToggleFileWanted => app.toggle_files_wanted()?,
+        Action::CycleFilePriority => app.cycle_files_priority()?,
+        Action::SelectFiles => app.start_file_selection(),
+        Action::PrevPane => {
+            if let Some(detail) = &mut app.detail {
+                detail.prev_pane();
+            }
+        }
+        Action::Verify => app.verify()?,
+        Action::Reannounce => app.reannounce()?,
+        Action::StartNow => app.start_now()?,
+        Action::Queue(direction) => app.queue_move(direction)?,
+        Action::SortColumn => app.cycle_sort(),
+        Action::SortBy(idx) => app.sort_by(idx),
+        Action::SortReverse => app.reverse_sort(),
+        Action::ToggleAltSpeed => app.toggle_alt_speed()?,
+        Action::SessionLimits => app.prepare_session_limits(),
+        Action::TorrentLimits => app.prepare_torrent_limits()?,
+        Action::TorrentOptions => app.prepare_options()?,
+        Action::Settings => app.open_settings()?,
+        Action::Trackers => app.open_trackers()?,
+        Action::EditLabels => app.prepare_labels(),
+        Action::ToggleLabels => app.toggle_label_sidebar(),
+        Action::NextLabel => app.cycle_label(true),
+        Action::PrevLabel => app.cycle_label(false),
+        Action::FilterLabel(idx) => app.filter_label(idx),
         Action::Filter => app.start_filter(),
         Action::ClearFilter => app.clear_filter(),
         Action::Delete(delete_local_data) => app.prepare_delete(delete_local_data),
-        Action::Select => app.select(),
+        Action::Select => match &mut app.detail {
+            Some(detail) => detail.toggle_file_selection(),
+            None => app.select(),
+        },
         Action::Submit => match app.input_mode {
-            InputMode::Move => app.move_torrent().await?,
-            InputMode::Rename => app.rename_torrent().await?,
+            InputMode::Move => app.move_torrent()?,
+            InputMode::Rename => app.rename_torrent()?,
             InputMode::Filter => app.apply_filter(),
+            InputMode::SelectFiles => app.apply_file_selection(),
+            InputMode::Add => app.add_torrent()?,
+            InputMode::Limits => app.apply_limits()?,
+            InputMode::Labels => app.apply_labels()?,
+            InputMode::Options => app.apply_options()?,
+            InputMode::Trackers => app.apply_trackers()?,
+            InputMode::Settings => app.apply_settings()?,
             InputMode::None | InputMode::ConfirmDelete(_) => {}
         },
-        Action::ConfirmYes => app.confirm_delete().await?,
+        Action::ConfirmYes => app.confirm_delete()?,
         Action::Cancel => {
             app.input_handler.clear();
+            app.add_form.clear();
+            app.limits_form.clear();
+            app.settings_form = None;
+            app.labels_form = None;
+            app.options_form = None;
+            app.trackers_form = None;
             app.input_mode = InputMode::None;
         }
     }
+    if view_changed {
+        app.sync_fields();
+    }
     Ok(())
 }
 
+/// Returns `true` if the action changes state on the daemon.
+const fn mutates(action: Action, app: &App) -> bool {
+    match action {
+        Action::ToggleTorrent
+        | Action::PauseAll
+        | Action::StartAll
+        | Action::ConfirmYes
+        | Action::ToggleFileWanted
+        | Action::CycleFilePriority
+        | Action::ToggleAltSpeed
+        | Action::Verify
+        | Action::Reannounce
+        | Action::StartNow
+        | Action::Queue(_) => true,
+        // Selects all files while the detail view is open.
+        Action::ToggleAll => app.detail.is_none(),
+        // The first submit only previews the changes.
+        Action::Submit if matches!(app.input_mode, InputMode::Settings) => {
+            matches!(&app.settings_form, Some(form) if form.preview.is_some())
+        }
+        Action::Submit => matches!(
+            app.input_mode,
+            InputMode::Move
+                | InputMode::Rename
+                | InputMode::Add
+                | InputMode::Limits
+                | InputMode::Labels
+                | InputMode::Options
+                | InputMode::Trackers
+        ),
+        _ => false,
+    }
+}
+
 /// Check if a [`KeyEvent`] matches a configured keybind string
 fn matches_keybind(event: &KeyEvent, config_key: &str) -> bool {
     parse_keybind(config_key).is_ok_and(|parsed| parsed == *event)
@@ -1,19 +1,14 @@
-use color_eyre::Result;
+use clap::Parser;
+use color_eyre::{Result, eyre::eyre};
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::{io, sync::Arc};
-use tokio::{
-    sync::Mutex,
-    time::{self, Duration},
-};
+use std::io;
 use tracing::warn;
 use traxor::{
-    app::{
-        App,
-        constants::{DEFAULT_TICK_RATE_MS, TORRENT_UPDATE_INTERVAL_SECS},
-    },
+    app::{App, constants::DEFAULT_TICK_RATE_MS},
+    cli::Cli,
     config::Config,
     event::{Event, EventHandler},
-    handler::{get_action, update},
+    handler::{handle_key_event, handle_mouse_event},
     telemetry::setup_logger,
     tui::Tui,
 };
@@ -21,11 +16,23 @@ use traxor::{
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
-    let config = Config::load()?;
+    let cli = Cli::parse();
+    let mut config = Config::load()?;
+    if let Some(profile) = &cli.profile {
+        config
+            .connection
+            .use_profile(profile)
+            .map_err(|err| eyre!(err))?;
+    }
     setup_logger(&config)?;
 
-    let app = Arc::new(Mutex::new(App::new(config)?));
-    spawn_torrent_updater(Arc::clone(&app));
+    if let Some(command) = cli.command {
+        command.run(config, &mut io::stdout().lock()).await?;
+        return Ok(());
+    }
+
+    let mut app = App::new(config)?;
+    app.jobs.start()?;
 
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
@@ -33,33 +40,31 @@ async fn main() -> Result<()> {
     let mut tui = Tui::new(terminal, events);
     tui.init()?;
 
-    loop {
-        let mut app_guard = app.lock().await;
-        if !app_guard.running {
-            break;
-        }
-        tui.draw(&mut app_guard)?;
-        drop(app_guard);
-
-        if let Event::Key(key_event) = tui.events.next()? {
-            let mut app_guard = app.lock().await;
-            if let Some(action) = get_action(key_event, &mut app_guard).await? {
-                update(&mut app_guard, action).await?;
-            }
+    while app.running {
+        tui.draw(&mut app)?;
+        tokio::select! {
+            event = tui.events.next() => match event? {
+                Event::Key(key_event) => handle_key_event(key_event, &mut app).await,
+                Event::Update => {
+                    app.jobs.mark_poll_due();
+                    tick(&mut app);
+                }
+                Event::Tick => tick(&mut app),
+                Event::Mouse(mouse_event) => handle_mouse_event(mouse_event, &mut app),
+                Event::Resize(..) => {}
+            },
+            Some((job, result)) = app.jobs.recv() => app.finish(&job, result),
         }
     }
 
     tui.exit()
 }
 
-fn spawn_torrent_updater(app: Arc<Mutex<App>>) {
-    tokio::spawn(async move {
-        let mut interval = time::interval(Duration::from_secs(TORRENT_UPDATE_INTERVAL_SECS));
-        loop {
-            interval.tick().await;
-            if let Err(e) = app.lock().await.torrents.update().await {
-                warn!("Failed to update torrents: {e}");
-            }
-        }
-    });
+/// Sends a due poll, warning if the worker cannot be reached.
+fn tick(app: &mut App) {
+    if let Err(e) = app.tick() {
+        warn!("Failed to update torrents: {e}");
+        app.notifications
+            .warning(format!("Failed to update torrents: {e}"));
+    }
 }
@@ -0,0 +1,232 @@
+use super::table::{header_style, highlighted_row_style, select_style};
+use crate::{
+    app::{
+        App,
+        detail::{DetailPane, GENERAL_FIELDS},
+        utils::{Wrapper, filesize::FileSize, format_datetime, netspeed::NetSpeed},
+    },
+    config::{color::ColorConfig, time::TimeConfig},
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Cell, Paragraph, Row, Table, Tabs},
+};
+use std::collections::HashSet;
+use transmission_rpc::types::{Priority, Torrent, TrackerState};
+
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let colors = &app.config.colors;
+    let time = &app.config.time;
+    let Some(detail) = &mut app.detail else {
+        return;
+    };
+
+    let title = detail
+        .torrent
+        .as_ref()
+        .and_then(|t| t.name.clone())
+        .unwrap_or_else(|| format!("Torrent {}", detail.id()));
+    let block = Block::default()
+        .title(format!(" {title} "))
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).split(inner);
+
+    let selected_pane = DetailPane::ALL
+        .iter()
+        .position(|&pane| pane == detail.pane)
+        .unwrap_or_default();
+    let panes = Tabs::new(DetailPane::ALL.iter().map(ToString::to_string))
+        .select(selected_pane)
+        .style(Style::default().fg(super::to_color(&colors.info_foreground)))
+        .highlight_style(header_style(colors))
+        .divider("|");
+    frame.render_widget(panes, chunks[0]);
+
+    let Some(torrent) = &detail.torrent else {
+        frame.render_widget(
+            Paragraph::new("Loading…").style(Style::default().fg(Color::DarkGray)),
+            chunks[1],
+        );
+        return;
+    };
+
+    let table = match detail.pane {
+        DetailPane::General => general_table(torrent, time),
+        DetailPane::Files => files_table(torrent, &detail.selected_files, colors),
+        DetailPane::Peers => peers_table(torrent, colors),
+        DetailPane::Trackers => trackers_table(torrent, colors, time),
+    };
+    let table = table
+        .row_highlight_style(highlighted_row_style(colors))
+        .highlight_symbol("▶ ")
+        .column_spacing(1);
+    frame.render_stateful_widget(table, chunks[1], &mut detail.state);
+}
+
+fn header(titles: &[&'static str], colors: &ColorConfig) -> Row<'static> {
+    Row::new(titles.to_vec())
+        .style(header_style(colors))
+        .bottom_margin(1)
+}
+
+fn general_table(torrent: &Torrent, time: &TimeConfig) -> Table<'static> {
+    let rows = GENERAL_FIELDS.iter().map(|field| {
+        Row::new(vec![
+            Cell::from(field.title()).style(Style::default().fg(Color::DarkGray)),
+            Cell::from(field.value(torrent, time)),
+        ])
+    });
+    Table::new(rows, [Constraint::Length(18), Constraint::Fill(1)])
+}
+
+fn files_table(
+    torrent: &Torrent,
+    selected: &HashSet<usize>,
+    colors: &ColorConfig,
+) -> Table<'static> {
+    let files = torrent.files.as_deref().unwrap_or_default();
+    let stats = torrent.file_stats.as_deref().unwrap_or_default();
+    let select_style = select_style(colors);
+    let rows = files.iter().enumerate().map(|(idx, file)| {
+        let stat = stats.get(idx);
+        let completed = stat.map_or(file.bytes_completed, |s| s.bytes_completed);
+        let row = Row::new(vec![
+            idx.to_string(),
+            percent(completed, file.length),
+            FileSize::from(file.length).to_string(),
+            stat.map_or_else(String::new, |s| {
+                if s.wanted { "Yes" } else { "No" }.to_string()
+            }),
+            stat.map_or_else(String::new, |s| priority(s.priority).to_string()),
+            file.name.clone(),
+        ]);
+        let unwanted = stat.is_some_and(|s| !s.wanted);
+        if selected.contains(&idx) {
+            row.style(select_style)
+        } else if unwanted {
+            row.style(Style::default().fg(Color::DarkGray))
+        } else {
+            row
+        }
+    });
+    Table::new(
+        rows,
+        [
+            Constraint::Length(5),
+            Constraint::Length(7),
+            Constraint::Length(11),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Fill(1),
+        ],
+    )
+    .header(header(
+        &["#", "Done", "Size", "Wanted", "Priority", "Name"],
+        colors,
+    ))
+}
+
+fn peers_table(torrent: &Torrent, colors: &ColorConfig) -> Table<'static> {
+    let peers = torrent.peers.as_deref().unwrap_or_default();
+    let rows = peers.iter().map(|peer| {
+        Row::new(vec![
+            format!("{}:{}", peer.address, peer.port),
+            peer.client_name.clone(),
+            format!("{:.1}%", peer.progress * 100.0),
+            NetSpeed::new(peer.rate_to_client).to_string(),
+            NetSpeed::new(peer.rate_to_peer).to_string(),
+            peer.flag_str.clone(),
+        ])
+    });
+    Table::new(
+        rows,
+        [
+            Constraint::Min(22),
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(10),
+        ],
+    )
+    .header(header(
+        &["Address", "Client", "Done", "Down", "Up", "Flags"],
+        colors,
+    ))
+}
+
+fn trackers_table(torrent: &Torrent, colors: &ColorConfig, time: &TimeConfig) -> Table<'static> {
+    let trackers = torrent.tracker_stats.as_deref().unwrap_or_default();
+    let rows = trackers.iter().map(|tracker| {
+        let result = if tracker.has_announced {
+            tracker.last_announce_result.clone()
+        } else {
+            String::new()
+        };
+        Row::new(vec![
+            tracker.tier.to_string(),
+            tracker.host.clone(),
+            tracker_state(&tracker.announce_state).to_string(),
+            result,
+            tracker.seeder_count.max(0).to_string(),
+            tracker.leecher_count.max(0).to_string(),
+            format_datetime(Some(tracker.next_announce_time), time),
+        ])
+    });
+    Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(20),
+        ],
+    )
+    .header(header(
+        &[
+            "Tier",
+            "Host",
+            "State",
+            "Result",
+            "Seeds",
+            "Leeches",
+            "Next announce",
+        ],
+        colors,
+    ))
+}
+
+fn percent(done: i64, total: i64) -> String {
+    if total <= 0 {
+        return String::new();
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let ratio = done as f64 / total as f64;
+    format!("{:.1}%", ratio * 100.0)
+}
+
+const fn priority(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Normal => "Normal",
+        Priority::High => "High",
+    }
+}
+
+const fn tracker_state(state: &TrackerState) -> &'static str {
+    match state {
+        TrackerState::Inactive => "Inactive",
+        TrackerState::Waiting => "Waiting",
+        TrackerState::Queued => "Queued",
+        TrackerState::Active => "Active",
+    }
+}
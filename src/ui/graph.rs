@@ -0,0 +1,127 @@
+use crate::app::{App, history::Samples, utils::netspeed::NetSpeed};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Paragraph, Sparkline},
+};
+use transmission_rpc::types::Torrent;
+
+/// Height of the graph panel, borders included.
+pub const HEIGHT: u16 = 8;
+
+/// Width of the speed readout left of each graph.
+const LABEL_WIDTH: u16 = 18;
+
+/// Bytes in the daemon's `kB`, the unit of its speed limits.
+const KILOBYTE: u64 = 1000;
+
+/// Limits in effect as `(down, up)` in kB/s, `None` meaning unlimited.
+type Limits = (Option<u64>, Option<u64>);
+
+/// Renders speed graphs of the session and of the torrent in focus side by
+/// side. A graph reaching its top while a limit is shown is being throttled.
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let [session_area, torrent_area] =
+        Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+
+    let session_limits = app.speed_limits.map_or((None, None), |limits| {
+        let (down, up) = limits.active();
+        (
+            down.map(|v| u64::from(v.unsigned_abs())),
+            up.map(|v| u64::from(v.unsigned_abs())),
+        )
+    });
+    render_samples(
+        frame,
+        session_area,
+        " Session ".into(),
+        Some(app.history.session()),
+        session_limits,
+    );
+
+    match app.graph_torrent() {
+        Some(torrent) => render_samples(
+            frame,
+            torrent_area,
+            format!(" {} ", torrent.name.as_deref().unwrap_or("Unknown")),
+            torrent.id.and_then(|id| app.history.torrent(id)),
+            torrent_limits(torrent),
+        ),
+        None => render_samples(
+            frame,
+            torrent_area,
+            " No torrent highlighted ".into(),
+            None,
+            (None, None),
+        ),
+    }
+}
+
+/// Per-torrent limits, ignoring the session limits it may also honor.
+fn torrent_limits(torrent: &Torrent) -> Limits {
+    let limit = |limited: Option<bool>, limit: Option<u64>| limit.filter(|_| limited == Some(true));
+    (
+        limit(torrent.download_limited, torrent.download_limit),
+        limit(torrent.upload_limited, torrent.upload_limit),
+    )
+}
+
+fn render_samples(
+    frame: &mut Frame,
+    area: Rect,
+    title: String,
+    samples: Option<&Samples>,
+    (down_limit, up_limit): Limits,
+) {
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let [down_area, up_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)]).areas(inner);
+    let width = usize::from(inner.width.saturating_sub(LABEL_WIDTH));
+    let (down, up) = samples.map_or_else(Default::default, |samples| {
+        (samples.down(width), samples.up(width))
+    });
+    render_line(frame, down_area, "↓", &down, down_limit, Color::Green);
+    render_line(frame, up_area, "↑", &up, up_limit, Color::Cyan);
+}
+
+/// One direction: the latest speed, its peak and limit, then the graph.
+fn render_line(
+    frame: &mut Frame,
+    area: Rect,
+    arrow: &str,
+    data: &[u64],
+    limit: Option<u64>,
+    color: Color,
+) {
+    let [label_area, graph_area] =
+        Layout::horizontal([Constraint::Length(LABEL_WIDTH), Constraint::Fill(1)]).areas(area);
+
+    let current = data.last().copied().unwrap_or_default();
+    let peak = data.iter().copied().max().unwrap_or_default();
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::styled(
+            format!("{arrow} {}", NetSpeed::new(current)),
+            Style::default().fg(if current > 0 { color } else { Color::DarkGray }),
+        ),
+        Line::styled(format!("  peak {}", NetSpeed::new(peak)), dim),
+    ];
+    if let Some(limit) = limit {
+        lines.push(Line::styled(format!("  ≤ {limit} kB/s"), dim));
+    }
+    frame.render_widget(Paragraph::new(lines), label_area);
+
+    // Scale to the limit so a throttled transfer runs along the top.
+    let max = limit.map_or(peak, |limit| peak.max(limit * KILOBYTE));
+    let graph = Sparkline::default()
+        .data(data)
+        .max(max)
+        .style(Style::default().fg(color));
+    frame.render_widget(graph, graph_area);
+}
@@ -5,11 +5,13 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Cell, Clear, Row, Table},
 };
 
-pub fn render_help(frame: &mut Frame, app: &App) {
+/// Renders the help popup and returns its area.
+pub fn render_help(frame: &mut Frame, app: &App) -> Rect {
     let kb = &app.config.keybinds;
     let key_style = Style::default().fg(Color::Yellow).bold();
     let select_key = display_key(&kb.select);
     let filter_key = display_key(&kb.filter);
+    let label_keys = format!("{}/{}", kb.prev_label, kb.next_label);
 
     let rows = vec![
         section_row("Navigation"),
@@ -25,8 +27,42 @@ pub fn render_help(frame: &mut Frame, app: &App) {
         key_row(&select_key, "Multi-select", key_style),
         key_row(&kb.move_torrent, "Move torrent", key_style),
         key_row(&kb.rename_torrent, "Rename torrent", key_style),
+        key_row(&kb.add_torrent, "Add torrent", key_style),
+        key_row(&kb.details, "Torrent details", key_style),
+        key_row(&kb.file_wanted, "File: (un)want", key_style),
+        key_row(&kb.file_priority, "File: priority", key_style),
         key_row(&kb.delete, "Remove torrent", key_style),
         key_row(&kb.delete_force, "Delete with data", key_style),
+        key_row(&kb.verify, "Verify data", key_style),
+        key_row(&kb.reannounce, "Reannounce", key_style),
+        key_row(&kb.trackers, "Edit trackers", key_style),
+        Row::default(),
+        section_row("Queue"),
+        key_row(&kb.start_now, "Start now", key_style),
+        key_row(&kb.queue_top, "Queue: top", key_style),
+        key_row(&kb.queue_up, "Queue: up", key_style),
+        key_row(&kb.queue_down, "Queue: down", key_style),
+        key_row(&kb.queue_bottom, "Queue: bottom", key_style),
+        Row::default(),
+        section_row("Speed"),
+        key_row(&kb.alt_speed, "Alt speed (turtle)", key_style),
+        key_row(&kb.speed_limits, "Global speed limits", key_style),
+        key_row(&kb.torrent_limits, "Torrent speed limits", key_style),
+        key_row(
+            &kb.torrent_options,
+            "Seeding limits and priority",
+            key_style,
+        ),
+        key_row(&kb.settings, "Daemon settings", key_style),
+        Row::default(),
+        section_row("Labels"),
+        key_row(&kb.edit_labels, "Edit labels", key_style),
+        key_row(&kb.label_sidebar, "Label sidebar", key_style),
+        key_row(&label_keys, "Filter by label", key_style),
+        Row::default(),
+        section_row("Sort"),
+        key_row(&kb.sort_column, "Sort by next column", key_style),
+        key_row(&kb.sort_reverse, "Reverse sort", key_style),
         Row::default(),
         section_row("Search"),
         key_row(&filter_key, "Search/filter", key_style),
@@ -34,6 +70,9 @@ pub fn render_help(frame: &mut Frame, app: &App) {
         Row::default(),
         section_row("General"),
         key_row(&kb.toggle_help, "Toggle help", key_style),
+        key_row(&kb.notifications, "Notifications", key_style),
+        key_row(&kb.graphs, "Speed graphs", key_style),
+        key_row(&kb.session_stats, "Session stats", key_style),
         key_row(&kb.quit, "Quit", key_style),
     ];
 
@@ -61,6 +100,7 @@ pub fn render_help(frame: &mut Frame, app: &App) {
 
     frame.render_widget(Clear, popup_area);
     frame.render_widget(table, popup_area);
+    popup_area
 }
 
 fn key_row<'a>(key: &'a str, desc: &'a str, key_style: Style) -> Row<'a> {
@@ -1,28 +1,59 @@
-use crate::app::{App, InputMode};
+use crate::app::{
+    AddField, App, InputMode, LimitField, LimitTarget,
+    labels::LabelsForm,
+    options::{self, OptionField, OptionsForm},
+    settings::{SettingChange, SettingKind},
+    trackers::{TrackerField, TrackersForm},
+};
 use ratatui::{
     prelude::*,
     text::Line,
     widgets::{Block, BorderType, Borders, Clear, Paragraph},
 };
 use tracing::warn;
+use transmission_rpc::types::Priority;
 
-pub fn render(f: &mut Frame, app: &App) {
+/// Renders the popup of the active input mode and returns its area.
+pub fn render(f: &mut Frame, app: &App) -> Option<Rect> {
     match app.input_mode {
-        InputMode::Move | InputMode::Rename => render_text_input(f, app),
-        InputMode::Filter => render_filter_input(f, app),
-        InputMode::ConfirmDelete(delete_local_data) => render_confirm_delete(f, delete_local_data),
-        InputMode::None => {}
+        InputMode::Move | InputMode::Rename | InputMode::SelectFiles => render_text_input(f, app),
+        InputMode::Filter => Some(render_filter_input(f, app)),
+        InputMode::Add => Some(render_add_dialog(f, app)),
+        InputMode::Limits if app.limits_form.loading => Some(render_message(
+            f,
+            " Torrent speed limits ",
+            "Loading limits…",
+        )),
+        InputMode::Limits => Some(render_limits_dialog(f, app)),
+        InputMode::Trackers => Some(match &app.trackers_form {
+            Some(form) => render_trackers_dialog(f, form),
+            None => render_message(f, " Trackers ", "Loading trackers…"),
+        }),
+        InputMode::Options => Some(match &app.options_form {
+            Some(form) => render_options_dialog(f, form),
+            None => render_message(f, " Seeding options ", "Loading options…"),
+        }),
+        InputMode::Labels => app
+            .labels_form
+            .as_ref()
+            .map(|form| render_labels_dialog(f, form)),
+        InputMode::Settings => Some(render_settings_dialog(f, app)),
+        InputMode::ConfirmDelete(delete_local_data) => {
+            Some(render_confirm_delete(f, delete_local_data))
+        }
+        InputMode::None => None,
     }
 }
 
-fn render_text_input(f: &mut Frame, app: &App) {
+fn render_text_input(f: &mut Frame, app: &App) -> Option<Rect> {
     let size = f.area();
     let input_area = Rect::new(size.width / 4, size.height / 2 - 1, size.width / 2, 3);
 
     let title = match app.input_mode {
         InputMode::Move => "Move to",
         InputMode::Rename => "Rename",
-        _ => return,
+        InputMode::SelectFiles => "Select files matching (globs or text)",
+        _ => return None,
     };
 
     let block = Block::default()
@@ -50,31 +81,45 @@ fn render_text_input(f: &mut Frame, app: &App) {
         input_area.x + cursor_offset + 1,
         input_area.y + 1,
     ));
+    Some(input_area)
 }
 
-fn render_filter_input(f: &mut Frame, app: &App) {
+fn render_filter_input(f: &mut Frame, app: &App) -> Rect {
     let size = f.area();
     let width = size.width.min(60);
     let input_area = Rect::new((size.width.saturating_sub(width)) / 2, 1, width, 3);
 
-    let filtered = app.filtered_torrents().len();
-    let total = app.torrents.len();
+    let (footer, border_color) = match app.filter_query() {
+        Ok(_) => {
+            let filtered = app.filtered_torrents().len();
+            let total = app.torrents.len();
+            let footer = Line::from(vec![
+                Span::raw(" "),
+                Span::styled(
+                    format!("{filtered}/{total}"),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(" matches "),
+            ]);
+            (footer, Color::Cyan)
+        }
+        Err(err) => {
+            let footer = Line::from(Span::styled(
+                format!(" {err} "),
+                Style::default().fg(Color::Red),
+            ));
+            (footer, Color::Red)
+        }
+    };
 
     let block = Block::default()
         .title(" Search ")
         .title_style(Style::default().fg(Color::Cyan).bold())
         .title_alignment(Alignment::Left)
-        .title_bottom(Line::from(vec![
-            Span::raw(" "),
-            Span::styled(
-                format!("{filtered}/{total}"),
-                Style::default().fg(Color::Yellow),
-            ),
-            Span::raw(" matches "),
-        ]))
+        .title_bottom(footer)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(border_color));
 
     f.render_widget(Clear, input_area);
     f.render_widget(block, input_area);
@@ -101,9 +146,636 @@ fn render_filter_input(f: &mut Frame, app: &App) {
         input_area.x + cursor_offset + 3,
         input_area.y + 1,
     ));
+    input_area
+}
+
+fn render_add_dialog(f: &mut Frame, app: &App) -> Rect {
+    const LABEL_WIDTH: u16 = 18;
+
+    let size = f.area();
+    let width = size.width.min(80);
+    #[allow(clippy::cast_possible_truncation)]
+    let height = AddField::ALL.len() as u16 + 2;
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(height)) / 2,
+        width,
+        height.min(size.height),
+    );
+
+    let block = Block::default()
+        .title(" Add torrent ")
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_bottom(Line::from(" ↑/↓ field │ Tab complete │ ←/→ change ").centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let form = &app.add_form;
+    let label_style = Style::default().fg(Color::DarkGray);
+    let focus_style = Style::default().fg(Color::Yellow).bold();
+    let lines = AddField::ALL
+        .iter()
+        .map(|&field| {
+            let value = match field {
+                AddField::Source => form.source.text.clone(),
+                AddField::DownloadDir if form.download_dir.text.is_empty() => "(default)".into(),
+                AddField::DownloadDir => form.download_dir.text.clone(),
+                AddField::Labels => form.labels.text.clone(),
+                AddField::Paused => if form.paused { "[x]" } else { "[ ]" }.into(),
+                AddField::Priority => {
+                    let priority = match form.priority {
+                        Some(Priority::Low) => "Low",
+                        Some(Priority::High) => "High",
+                        Some(Priority::Normal) | None => "Normal",
+                    };
+                    format!("< {priority} >")
+                }
+            };
+            let style = if field == form.focus {
+                focus_style
+            } else {
+                label_style
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}", field.label(), width = LABEL_WIDTH as usize),
+                    style,
+                ),
+                Span::raw(value),
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    let inner = dialog_area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    f.render_widget(Paragraph::new(lines), inner);
+
+    let (row, input) = match form.focus {
+        AddField::Source => (0, &form.source),
+        AddField::DownloadDir => (1, &form.download_dir),
+        AddField::Labels => (2, &form.labels),
+        AddField::Paused | AddField::Priority => return dialog_area,
+    };
+    let cursor_offset = u16::try_from(input.cursor_position).unwrap_or_else(|_| {
+        warn!("cursor_position out of range, clamping");
+        0
+    });
+    f.set_cursor_position(Position::new(
+        inner.x + LABEL_WIDTH + cursor_offset,
+        inner.y + row,
+    ));
+    dialog_area
+}
+
+fn render_limits_dialog(f: &mut Frame, app: &App) -> Rect {
+    const LABEL_WIDTH: u16 = 18;
+
+    let form = &app.limits_form;
+    let fields = form.fields();
+    let size = f.area();
+    let width = size.width.min(60);
+    #[allow(clippy::cast_possible_truncation)]
+    let height = fields.len() as u16 + 2 + u16::from(form.error.is_some());
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(height)) / 2,
+        width,
+        height.min(size.height),
+    );
+
+    let title = match form.target {
+        LimitTarget::Session => " Global speed limits ",
+        LimitTarget::Torrents => " Torrent speed limits ",
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_bottom(Line::from(" ↑/↓ field │ blank = unlimited ").centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let label_style = Style::default().fg(Color::DarkGray);
+    let focus_style = Style::default().fg(Color::Yellow).bold();
+    let mut lines = fields
+        .iter()
+        .map(|&field| {
+            let value = match field {
+                LimitField::Down if form.down.text.is_empty() => "(unlimited)".into(),
+                LimitField::Down => form.down.text.clone(),
+                LimitField::Up if form.up.text.is_empty() => "(unlimited)".into(),
+                LimitField::Up => form.up.text.clone(),
+                LimitField::HonorsSession => {
+                    format!(
+                        "[{}] also apply",
+                        if form.honors_session_limits { "x" } else { " " }
+                    )
+                }
+            };
+            let style = if field == form.focus {
+                focus_style
+            } else {
+                label_style
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}", field.label(), width = LABEL_WIDTH as usize),
+                    style,
+                ),
+                Span::raw(value),
+            ])
+        })
+        .collect::<Vec<_>>();
+    if let Some(error) = &form.error {
+        lines.push(Line::styled(error.clone(), Style::default().fg(Color::Red)));
+    }
+
+    let inner = dialog_area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    f.render_widget(Paragraph::new(lines), inner);
+
+    let (row, input) = match form.focus {
+        LimitField::Down => (0, &form.down),
+        LimitField::Up => (1, &form.up),
+        LimitField::HonorsSession => return dialog_area,
+    };
+    let cursor_offset = u16::try_from(input.cursor_position).unwrap_or_else(|_| {
+        warn!("cursor_position out of range, clamping");
+        0
+    });
+    f.set_cursor_position(Position::new(
+        inner.x + LABEL_WIDTH + cursor_offset,
+        inner.y + row,
+    ));
+    dialog_area
+}
+
+fn render_options_dialog(f: &mut Frame, form: &OptionsForm) -> Rect {
+    const LABEL_WIDTH: u16 = 20;
+
+    let size = f.area();
+    let width = size.width.min(60);
+    let height = u16::try_from(OptionField::ALL.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2 + u16::from(form.error.is_some()));
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(height)) / 2,
+        width,
+        height.min(size.height),
+    );
+
+    let title = match form.ids().len() {
+        1 => " Seeding options ".to_owned(),
+        n => format!(" Seeding options of {n} torrents "),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_bottom(Line::from(" ↑/↓ field │ ←/→ change ").centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let label_style = Style::default().fg(Color::DarkGray);
+    let focus_style = Style::default().fg(Color::Yellow).bold();
+    let choice = |value: Option<&str>| format!("< {} >", value.unwrap_or("mixed"));
+    let mut lines = OptionField::ALL
+        .iter()
+        .map(|&field| {
+            let value = match field {
+                OptionField::RatioMode => choice(form.ratio_mode.map(options::ratio_mode_label)),
+                OptionField::IdleMode => choice(form.idle_mode.map(options::idle_mode_label)),
+                OptionField::Priority => choice(form.priority.map(options::priority_label)),
+                _ if form.is_mixed(field) => "(mixed)".into(),
+                OptionField::RatioLimit => form.ratio_limit.text.clone(),
+                OptionField::IdleLimit => form.idle_limit.text.clone(),
+            };
+            let style = if field == form.focus {
+                focus_style
+            } else {
+                label_style
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}", field.label(), width = LABEL_WIDTH as usize),
+                    style,
+                ),
+                Span::raw(value),
+            ])
+        })
+        .collect::<Vec<_>>();
+    if let Some(error) = &form.error {
+        lines.push(Line::styled(error.clone(), Style::default().fg(Color::Red)));
+    }
+
+    let inner = dialog_area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    f.render_widget(Paragraph::new(lines), inner);
+
+    let (row, input) = match form.focus {
+        OptionField::RatioLimit => (1, &form.ratio_limit),
+        OptionField::IdleLimit => (3, &form.idle_limit),
+        OptionField::RatioMode | OptionField::IdleMode | OptionField::Priority => {
+            return dialog_area;
+        }
+    };
+    let cursor_offset = u16::try_from(input.cursor_position).unwrap_or_else(|_| {
+        warn!("cursor_position out of range, clamping");
+        0
+    });
+    f.set_cursor_position(Position::new(
+        inner.x + LABEL_WIDTH + cursor_offset,
+        inner.y + row,
+    ));
+    dialog_area
+}
+
+fn render_labels_dialog(f: &mut Frame, form: &LabelsForm) -> Rect {
+    let size = f.area();
+    let width = size.width.min(60);
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(5)) / 2,
+        width,
+        5.min(size.height),
+    );
+
+    let title = match form.ids().len() {
+        1 => " Labels of 1 torrent ".to_owned(),
+        n => format!(" Labels of {n} torrents "),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_bottom(Line::from(" comma separated │ -label removes │ Tab completes ").centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let suggestions = form.suggestions();
+    let suggestion_line = if suggestions.is_empty() {
+        Line::default()
+    } else {
+        Line::styled(format!("Tab: {}", suggestions.join("  ")), dim)
+    };
+    let edit = form.edit();
+    let change_line = if edit.is_empty() {
+        Line::styled("No changes", dim)
+    } else {
+        let added = edit
+            .add
+            .iter()
+            .map(|label| Span::styled(format!("+{label} "), Style::default().fg(Color::Green)));
+        let removed = edit
+            .remove
+            .iter()
+            .map(|label| Span::styled(format!("-{label} "), Style::default().fg(Color::Red)));
+        Line::from(added.chain(removed).collect::<Vec<_>>())
+    };
+    let lines = vec![
+        Line::raw(form.input.text.as_str()),
+        suggestion_line,
+        change_line,
+    ];
+
+    let inner = dialog_area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    f.render_widget(Paragraph::new(lines), inner);
+
+    let cursor_offset = u16::try_from(form.input.cursor_position).unwrap_or_else(|_| {
+        warn!("cursor_position out of range, clamping");
+        0
+    });
+    f.set_cursor_position(Position::new(inner.x + cursor_offset, inner.y));
+    dialog_area
+}
+
+fn render_trackers_dialog(f: &mut Frame, form: &TrackersForm) -> Rect {
+    const LABEL_WIDTH: u16 = 16;
+
+    let fields = form.fields();
+    let dim = Style::default().fg(Color::DarkGray);
+    let preview = tracker_preview(form);
+
+    let size = f.area();
+    let width = size.width.min(90);
+    let height = u16::try_from(fields.len() + 1 + preview.len())
+        .unwrap_or(u16::MAX)
+        .saturating_add(2);
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(height)) / 2,
+        width,
+        height.min(size.height),
+    );
+
+    let title = match form.len() {
+        1 => " Trackers of 1 torrent ".to_owned(),
+        n => format!(" Trackers of {n} torrents "),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_bottom(Line::from(" ↑/↓ field │ ←/→ action │ Enter apply ").centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let focus_style = Style::default().fg(Color::Yellow).bold();
+    let mut lines = fields
+        .iter()
+        .map(|&field| {
+            let value = match field {
+                TrackerField::Action => format!("< {} >", form.action.label()),
+                TrackerField::Find => form.find.text.clone(),
+                TrackerField::Replace => form.replace.text.clone(),
+            };
+            let style = if field == form.focus {
+                focus_style
+            } else {
+                dim
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{:<width$}",
+                        field.label(form.action),
+                        width = LABEL_WIDTH as usize
+                    ),
+                    style,
+                ),
+                Span::raw(value),
+            ])
+        })
+        .collect::<Vec<_>>();
+    lines.push(Line::default());
+    lines.extend(preview);
+
+    let inner = dialog_area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    f.render_widget(Paragraph::new(lines), inner);
+
+    let (row, input) = match form.focus {
+        TrackerField::Action => return dialog_area,
+        TrackerField::Find => (1, &form.find),
+        TrackerField::Replace => (2, &form.replace),
+    };
+    let cursor_offset = u16::try_from(input.cursor_position).unwrap_or_else(|_| {
+        warn!("cursor_position out of range, clamping");
+        0
+    });
+    f.set_cursor_position(Position::new(
+        inner.x + LABEL_WIDTH + cursor_offset,
+        inner.y + row,
+    ));
+    dialog_area
+}
+
+/// Torrents the tracker edit changes with their old and new URLs, or why
+/// the input is invalid.
+fn tracker_preview(form: &TrackersForm) -> Vec<Line<'static>> {
+    /// Most preview lines shown below the fields.
+    const PREVIEW_LINES: usize = 14;
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut preview = Vec::new();
+    // Errors show once something is typed or after a submit.
+    match form.changes() {
+        Err(_) if form.error.is_none() && form.find.text.trim().is_empty() => {}
+        Err(err) => preview.push(Line::styled(err, Style::default().fg(Color::Red))),
+        Ok(changes) if changes.is_empty() => preview.push(Line::styled("No tracker matches", dim)),
+        Ok(changes) => {
+            let torrents = changes.len();
+            preview.push(Line::styled(
+                match torrents {
+                    1 => "1 torrent affected:".to_owned(),
+                    n => format!("{n} torrents affected:"),
+                },
+                dim,
+            ));
+            for change in &changes {
+                preview.push(Line::styled(
+                    change.name.clone(),
+                    Style::default().fg(Color::Yellow),
+                ));
+                for (tracker, announce) in &change.replace {
+                    preview.push(Line::raw(format!("  {} → {announce}", tracker.announce)));
+                }
+                for announce in &change.add {
+                    preview.push(Line::styled(
+                        format!("  + {announce}"),
+                        Style::default().fg(Color::Green),
+                    ));
+                }
+                for tracker in &change.remove {
+                    preview.push(Line::styled(
+                        format!("  - {}", tracker.announce),
+                        Style::default().fg(Color::Red),
+                    ));
+                }
+            }
+            if preview.len() > PREVIEW_LINES {
+                let hidden = preview.len() - PREVIEW_LINES + 1;
+                preview.truncate(PREVIEW_LINES - 1);
+                preview.push(Line::styled(format!("… {hidden} more lines"), dim));
+            }
+        }
+    }
+
+    preview
+}
+
+fn render_settings_dialog(f: &mut Frame, app: &App) -> Rect {
+    const LABEL_WIDTH: u16 = 20;
+
+    let size = f.area();
+    let width = size.width.min(72);
+    let Some(form) = &app.settings_form else {
+        return render_message(f, " Settings ", "Loading settings…");
+    };
+    if let Some(changes) = &form.preview {
+        return render_settings_preview(f, changes, width);
+    }
+
+    let label_style = Style::default().fg(Color::DarkGray);
+    let focus_style = Style::default().fg(Color::Yellow).bold();
+    let section_style = Style::default().fg(Color::Cyan).bold();
+    let focus = form.focus();
+    let mut lines = Vec::new();
+    let mut focus_line = 0;
+    let mut section = "";
+    for field in form.fields() {
+        if field.section() != section {
+            section = field.section();
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::styled(section, section_style));
+        }
+        let text = form.display(field);
+        let value = match field.kind() {
+            SettingKind::Toggle => format!("[{}]", if text == "on" { "x" } else { " " }),
+            SettingKind::Encryption => format!("< {text} >"),
+            SettingKind::Path | SettingKind::Count | SettingKind::Port | SettingKind::Ratio => text,
+        };
+        let style = if Some(field) == focus {
+            focus_line = lines.len();
+            focus_style
+        } else {
+            label_style
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {:<width$}",
+                    field.label(),
+                    width = LABEL_WIDTH as usize - 2
+                ),
+                style,
+            ),
+            Span::raw(value),
+        ]));
+    }
+    if let Some(error) = &form.error {
+        lines.push(Line::default());
+        lines.push(Line::styled(error.clone(), Style::default().fg(Color::Red)));
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+    let block = Block::default()
+        .title(" Settings ")
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_bottom(
+            Line::from(" ↑/↓ field │ ←/→ change │ Tab complete │ Enter review ").centered(),
+        )
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(dialog_area);
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(block, dialog_area);
+
+    // Keep the focused setting in view on short terminals.
+    let focus_line = u16::try_from(focus_line).unwrap_or(u16::MAX);
+    let scroll = focus_line.saturating_sub(inner.height.saturating_sub(1));
+    f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+
+    let Some(input) = form.focused_input() else {
+        return dialog_area;
+    };
+    let cursor_offset = u16::try_from(input.cursor_position).unwrap_or_else(|_| {
+        warn!("cursor_position out of range, clamping");
+        0
+    });
+    f.set_cursor_position(Position::new(
+        inner.x + LABEL_WIDTH + cursor_offset,
+        inner.y + focus_line - scroll,
+    ));
+    dialog_area
+}
+
+/// Lists the pending setting changes for confirmation.
+fn render_settings_preview(f: &mut Frame, changes: &[SettingChange], width: u16) -> Rect {
+    let size = f.area();
+    let lines: Vec<_> = changes
+        .iter()
+        .map(|change| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<20}", change.field.label()),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(change.old.to_string(), Style::default().fg(Color::Red)),
+                Span::raw(" → "),
+                Span::styled(change.new.to_string(), Style::default().fg(Color::Green)),
+            ])
+        })
+        .collect();
+    #[allow(clippy::cast_possible_truncation)]
+    let height = (lines.len() as u16 + 2).min(size.height);
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+    let title = match changes.len() {
+        1 => " Apply 1 change? ".into(),
+        n => format!(" Apply {n} changes? "),
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_bottom(Line::from(" Enter apply │ Esc back ").centered())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(Paragraph::new(lines).block(block), dialog_area);
+    dialog_area
+}
+
+/// A small popup with a single line of text.
+fn render_message(f: &mut Frame, title: &str, message: &str) -> Rect {
+    let size = f.area();
+    let width = size.width.min(40);
+    let dialog_area = Rect::new(
+        (size.width.saturating_sub(width)) / 2,
+        (size.height.saturating_sub(3)) / 2,
+        width,
+        3.min(size.height),
+    );
+    let block = Block::default()
+        .title(title.to_owned())
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+    let text = Paragraph::new(message.to_owned())
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center)
+        .block(block);
+    f.render_widget(Clear, dialog_area);
+    f.render_widget(text, dialog_area);
+    dialog_area
 }
 
-fn render_confirm_delete(f: &mut Frame, delete_local_data: bool) {
+fn render_confirm_delete(f: &mut Frame, delete_local_data: bool) -> Rect {
     let size = f.area();
     let dialog_width = 40;
     let dialog_height = 5;
@@ -145,4 +817,5 @@ fn render_confirm_delete(f: &mut Frame, delete_local_data: bool) {
             horizontal: 1,
         }),
     );
+    dialog_area
 }
@@ -0,0 +1,46 @@
+use crate::app::{
+    App,
+    labels::{LabelFilter, LabelSidebar},
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, List, ListItem},
+};
+
+/// Width of the label sidebar, borders included.
+pub const WIDTH: u16 = 24;
+
+/// Renders the label sidebar and returns the area of its entries.
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) -> Rect {
+    let entries = LabelSidebar::entries(&app.torrents.torrents);
+    let selected = app.labels.position(&entries);
+    let items = entries
+        .iter()
+        .map(|(filter, count)| {
+            let (name, style) = match filter {
+                LabelFilter::All => ("All", Style::default().fg(Color::White)),
+                LabelFilter::Label(label) => (label.as_str(), Style::default()),
+                LabelFilter::Unlabeled => ("No label", Style::default().fg(Color::DarkGray)),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(name.to_owned(), style),
+                Span::styled(format!(" ({count})"), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect::<Vec<_>>();
+
+    let block = Block::default()
+        .title(" Labels ")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().fg(Color::Cyan).bold())
+        .highlight_symbol("▶ ");
+
+    app.labels.state.select(selected);
+    frame.render_stateful_widget(list, area, &mut app.labels.state);
+    inner
+}
@@ -1,10 +1,15 @@
+mod detail;
+mod graph;
 mod help;
 mod input;
+mod labels;
+mod notification;
+mod stats;
 mod status;
 mod table;
 
 use crate::{
-    app::{App, InputMode},
+    app::{App, InputMode, Tab, health::ConnectionState, layout::ClickAreas},
     config::color::ColorConfig,
 };
 use help::render_help;
@@ -13,8 +18,8 @@ use ratatui::{
     style::Modifier,
     widgets::{Block, BorderType, Borders, Tabs},
 };
-use std::str::FromStr;
-use table::build_table;
+use std::{str::FromStr, time::Instant};
+use table::{build_table, table_areas};
 
 /// Renders the user interface widgets.
 pub fn render(app: &mut App, frame: &mut Frame) {
@@ -27,11 +32,17 @@ pub fn render(app: &mut App, frame: &mut Frame) {
     let tab_style = tab_style(&app.config.colors);
     let highlighted_tab_style = highlighted_tab_style(&app.config.colors);
 
+    let graph_height = if app.history.visible {
+        graph::HEIGHT
+    } else {
+        0
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(0),
+            Constraint::Length(graph_height),
             Constraint::Length(3),
         ])
         .split(size);
@@ -42,8 +53,12 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         .map(|x| Line::from(x.to_string()))
         .collect::<Vec<_>>();
 
+    let mut tab_block = default_block();
+    if !app.connection.is_connected() {
+        tab_block = tab_block.title(Line::from(connection_indicator(app)).right_aligned());
+    }
     let tabs = Tabs::new(titles)
-        .block(default_block())
+        .block(tab_block)
         .select(app.index())
         .style(tab_style)
         .highlight_style(highlighted_tab_style)
@@ -51,25 +66,101 @@ pub fn render(app: &mut App, frame: &mut Frame) {
 
     frame.render_widget(tabs, chunks[0]); // renders tab
 
-    app.torrents.set_fields(None);
-    let torrents = app.filtered_torrents();
-    let selected = &app.torrents.selected;
-    let colors = &app.config.colors;
-    let time = &app.config.time;
-    let fields = app.tabs()[app.index()].fields();
+    let mut click_areas = ClickAreas {
+        tabs: tab_areas(app.tabs(), default_block().inner(chunks[0])),
+        ..ClickAreas::default()
+    };
+
+    if app.detail.is_some() {
+        detail::render(frame, app, chunks[1]);
+    } else {
+        let list_area = if app.labels.visible {
+            let [sidebar_area, table_area] =
+                Layout::horizontal([Constraint::Length(labels::WIDTH), Constraint::Fill(1)])
+                    .areas(chunks[1]);
+            click_areas.labels = labels::render(frame, app, sidebar_area);
+            table_area
+        } else {
+            chunks[1]
+        };
+        let torrents = app.filtered_torrents();
+        let selected = &app.torrents.selected;
+        let colors = &app.config.colors;
+        let time = &app.config.time;
+        let tab = &app.tabs()[app.index()];
+
+        // Rows keep the last known values while disconnected; grey them out.
+        let stale = !app.connection.is_usable();
+        (click_areas.headers, click_areas.rows) =
+            table_areas(list_area, tab.fields(), app.state.selected().is_some());
+        let table = build_table(
+            &torrents,
+            selected,
+            colors,
+            time,
+            tab.fields(),
+            tab.sort(),
+            stale,
+        );
+        frame.render_stateful_widget(table, list_area, &mut app.state);
+    }
 
-    let table = build_table(&torrents, selected, colors, time, fields);
-    frame.render_stateful_widget(table, chunks[1], &mut app.state);
+    if app.history.visible {
+        graph::render(frame, app, chunks[2]);
+    }
+    status::render(frame, app, chunks[3]);
+    notification::render_toast(frame, app, chunks[1]);
+
+    if app.show_notifications {
+        click_areas.popup = Some(notification::render_history(frame, app));
+    }
 
-    status::render(frame, app, chunks[2]);
+    if app.stats.visible {
+        click_areas.popup = Some(stats::render(frame, app));
+    }
 
     if app.show_help {
-        render_help(frame, app);
+        click_areas.popup = Some(render_help(frame, app));
     }
 
     if app.input_mode != InputMode::None {
-        input::render(frame, app);
+        click_areas.popup = input::render(frame, app).or(click_areas.popup);
     }
+    app.click_areas = click_areas;
+}
+
+/// Areas of the tab titles within `area`, matching how [`Tabs`] lays them
+/// out: each title padded by a space on both sides, separated by a divider.
+fn tab_areas(tabs: &[Tab], area: Rect) -> Vec<Rect> {
+    let mut x = area.x;
+    tabs.iter()
+        .map(|tab| {
+            let title = u16::try_from(Line::from(tab.to_string()).width()).unwrap_or(u16::MAX);
+            let width = title.saturating_add(2).min(area.right().saturating_sub(x));
+            let rect = Rect::new(x, area.y, width, area.height.min(1));
+            x = x.saturating_add(width).saturating_add(1).min(area.right());
+            rect
+        })
+        .collect()
+}
+
+/// Connection state with the time until the next retry, colored by severity.
+fn connection_indicator(app: &App) -> Span<'static> {
+    let state = app.connection.state();
+    let (symbol, color) = match state {
+        ConnectionState::Connected => ("●", Color::Green),
+        ConnectionState::Connecting | ConnectionState::Retrying => ("↻", Color::Yellow),
+        ConnectionState::Unauthorized | ConnectionState::Unreachable => ("✗", Color::Red),
+    };
+    let retry = app
+        .connection
+        .retry_in(Instant::now())
+        .map(|left| format!(", retry in {}s", left.as_secs() + 1))
+        .unwrap_or_default();
+    Span::styled(
+        format!(" {symbol} {}{retry} ", state.label()),
+        Style::default().fg(color),
+    )
 }
 
 #[must_use]
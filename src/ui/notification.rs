@@ -0,0 +1,115 @@
+use crate::app::{
+    App,
+    notification::{Notification, Severity},
+};
+use chrono::Local;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
+};
+
+const TOAST_WIDTH: u16 = 60;
+const HISTORY_WIDTH: u16 = 100;
+const HISTORY_HEIGHT: u16 = 20;
+
+/// Renders the latest notification in the top-right corner of `area` while
+/// it is recent.
+pub fn render_toast(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(notification) = app.notifications.toast(Local::now()) else {
+        return;
+    };
+    let color = severity_color(notification.severity);
+    let width = TOAST_WIDTH.min(area.width);
+    let text = message(notification);
+    // Borders take two columns; wrap long messages over at most three lines.
+    #[allow(clippy::cast_possible_truncation)]
+    let lines = text
+        .chars()
+        .count()
+        .div_ceil(width.saturating_sub(2).max(1).into()) as u16;
+    let height = (lines.clamp(1, 3) + 2).min(area.height);
+    let toast_area = Rect::new(area.right().saturating_sub(width), area.y, width, height);
+
+    let block = Block::default()
+        .title(format!(" {} ", notification.severity))
+        .title_style(Style::default().fg(color).bold())
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(color));
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: true }).block(block);
+
+    frame.render_widget(Clear, toast_area);
+    frame.render_widget(paragraph, toast_area);
+}
+
+/// Renders the notification history popup, newest first, and returns its area.
+pub fn render_history(frame: &mut Frame, app: &App) -> Rect {
+    let rows: Vec<_> = app
+        .notifications
+        .history()
+        .map(|notification| {
+            let color = severity_color(notification.severity);
+            Row::new(vec![
+                Cell::from(notification.timestamp.format("%H:%M:%S").to_string())
+                    .style(Style::default().fg(Color::DarkGray)),
+                Cell::from(notification.severity.to_string()).style(Style::default().fg(color)),
+                Cell::from(message(notification)),
+            ])
+        })
+        .collect();
+
+    let area = frame.area();
+    let width = HISTORY_WIDTH.min(area.width);
+    let height = HISTORY_HEIGHT.min(area.height);
+    let popup_area = Rect::new(
+        (area.width.saturating_sub(width)) / 2,
+        (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .title(" Notifications ")
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    frame.render_widget(Clear, popup_area);
+    if rows.is_empty() {
+        let empty = Paragraph::new("No notifications")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(block);
+        frame.render_widget(empty, popup_area);
+        return popup_area;
+    }
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+        ],
+    )
+    .block(block);
+    frame.render_widget(table, popup_area);
+    popup_area
+}
+
+fn message(notification: &Notification) -> String {
+    if notification.count > 1 {
+        format!("{} (×{})", notification.message, notification.count)
+    } else {
+        notification.message.clone()
+    }
+}
+
+const fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Info => Color::Cyan,
+        Severity::Warning => Color::Yellow,
+        Severity::Error => Color::Red,
+    }
+}
@@ -0,0 +1,138 @@
+use crate::app::{
+    App,
+    utils::{filesize::FileSize, format_duration},
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+};
+
+const STATS_WIDTH: u16 = 60;
+const STATS_HEIGHT: u16 = 15;
+
+/// Renders the session stats popup and returns its area.
+pub fn render(frame: &mut Frame, app: &App) -> Rect {
+    let area = frame.area();
+    let width = STATS_WIDTH.min(area.width);
+    let height = STATS_HEIGHT.min(area.height);
+    let popup_area = Rect::new(
+        (area.width.saturating_sub(width)) / 2,
+        (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .title(" Session Stats ")
+        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    frame.render_widget(Clear, popup_area);
+    let Some(stats) = app.stats.stats() else {
+        let (text, color) = app
+            .stats
+            .error()
+            .map_or(("Loading…", Color::DarkGray), |err| (err, Color::Red));
+        let empty = Paragraph::new(text)
+            .style(Style::default().fg(color))
+            .alignment(Alignment::Center)
+            .block(block);
+        frame.render_widget(empty, popup_area);
+        return popup_area;
+    };
+
+    let periods = [&stats.totals.current_stats, &stats.totals.cumulative_stats];
+    let size = |bytes: i64| FileSize::new(bytes.unsigned_abs()).to_string();
+    let dim = Style::default().fg(Color::DarkGray);
+    let rows = vec![
+        Row::new(vec![
+            Cell::default(),
+            Cell::from("This session"),
+            Cell::from("All time"),
+        ])
+        .style(dim),
+        row("Downloaded", periods, |s| size(s.downloaded_bytes)),
+        row("Uploaded", periods, |s| size(s.uploaded_bytes)),
+        row("Ratio", periods, |s| {
+            ratio(s.uploaded_bytes, s.downloaded_bytes)
+        }),
+        row("Files added", periods, |s| s.files_added.to_string()),
+        row("Time active", periods, |s| {
+            format_duration(s.seconds_active)
+        }),
+        row("Sessions", periods, |s| {
+            s.session_count
+                .map_or_else(|| "-".into(), |count| count.to_string())
+        }),
+    ];
+    let table_height = u16::try_from(rows.len()).unwrap_or(u16::MAX);
+
+    let key = Style::default().fg(Color::Yellow);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<14}", "Free space"), key),
+            Span::raw(FileSize::new(stats.free_space.unsigned_abs()).to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{:<14}", "Download dir"), key),
+            Span::raw(stats.download_dir.as_str()),
+        ]),
+        Line::default(),
+    ];
+    if let Some(err) = app.stats.error() {
+        lines.push(Line::styled(err, Style::default().fg(Color::Red)));
+    } else if let Some(fetched) = app.stats.fetched() {
+        let age = fetched.elapsed().as_secs();
+        lines.push(Line::styled(format!("Updated {age}s ago"), dim));
+    }
+
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+    let [table_area, _, details_area] = Layout::vertical([
+        Constraint::Length(table_height),
+        Constraint::Length(1),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(16),
+            Constraint::Fill(1),
+        ],
+    );
+    frame.render_widget(table, table_area);
+    frame.render_widget(Paragraph::new(lines), details_area);
+    popup_area
+}
+
+/// Row with a value for this session and one for all time.
+fn row<'a, T>(
+    name: &'static str,
+    [current, cumulative]: [&T; 2],
+    value: impl Fn(&T) -> String,
+) -> Row<'a> {
+    Row::new(vec![
+        Cell::from(name).style(Style::default().fg(Color::Yellow)),
+        Cell::from(value(current)),
+        Cell::from(value(cumulative)),
+    ])
+}
+
+/// Upload to download ratio, or `∞` when nothing was downloaded.
+fn ratio(uploaded: i64, downloaded: i64) -> String {
+    if downloaded == 0 {
+        return if uploaded == 0 {
+            "-".into()
+        } else {
+            "∞".into()
+        };
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let ratio = uploaded as f64 / downloaded as f64;
+    format!("{ratio:.2}")
+}
@@ -1,5 +1,7 @@
 use crate::app::{
     App, InputMode,
+    labels::LabelFilter,
+    types::SpeedLimits,
     utils::{filesize::FileSize, netspeed::NetSpeed},
 };
 use ratatui::{
@@ -31,10 +33,18 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let selected_count = app.torrents.selected.len();
 
     let active_filter = app.active_filter();
+    let filtering = !active_filter.is_empty() || app.labels.filter != LabelFilter::All;
 
     let mode_text = match app.input_mode {
         InputMode::Move => Some("MOVE".to_string()),
         InputMode::Rename => Some("RENAME".to_string()),
+        InputMode::Add => Some("ADD".to_string()),
+        InputMode::Limits => Some("SPEED LIMITS".to_string()),
+        InputMode::Settings => Some("SETTINGS".to_string()),
+        InputMode::Labels => Some("LABELS".to_string()),
+        InputMode::Options => Some("SEEDING OPTIONS".to_string()),
+        InputMode::Trackers => Some("TRACKERS".to_string()),
+        InputMode::SelectFiles => Some("SELECT FILES".to_string()),
         InputMode::Filter => Some(format!("Filter: {active_filter}")),
         InputMode::ConfirmDelete(_) => Some("DELETE".to_string()),
         InputMode::None if !active_filter.is_empty() => Some(format!("Filter: {active_filter}")),
@@ -42,6 +52,18 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     };
 
     let keybinds = match app.input_mode {
+        InputMode::None if app.detail.is_some() => vec![
+            Span::styled("Esc", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back │ "),
+            Span::styled(
+                format!(
+                    "{}/{}",
+                    app.config.keybinds.prev_tab, app.config.keybinds.next_tab
+                ),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(" Pane"),
+        ],
         InputMode::None if !app.filter_text.is_empty() => vec![
             Span::styled("Esc", Style::default().fg(Color::Yellow)),
             Span::raw(" Clear │ "),
@@ -54,7 +76,15 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("/", Style::default().fg(Color::Yellow)),
             Span::raw(" Search"),
         ],
-        InputMode::Move | InputMode::Rename => vec![
+        InputMode::Move
+        | InputMode::Rename
+        | InputMode::Add
+        | InputMode::Limits
+        | InputMode::Settings
+        | InputMode::Labels
+        | InputMode::Options
+        | InputMode::Trackers
+        | InputMode::SelectFiles => vec![
             Span::styled("Enter", Style::default().fg(Color::Yellow)),
             Span::raw(" Submit │ "),
             Span::styled("Esc", Style::default().fg(Color::Yellow)),
@@ -77,7 +107,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     // Build right side with colored spans
     let count_style = if selected_count > 0 {
         Style::default().fg(Color::Magenta).bold()
-    } else if !active_filter.is_empty() {
+    } else if filtering {
         Style::default().fg(Color::Cyan)
     } else {
         Style::default().fg(Color::White)
@@ -85,7 +115,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     let count_text = if selected_count > 0 {
         format!("{selected_count}/{total}")
-    } else if !active_filter.is_empty() {
+    } else if filtering {
         format!("{filtered}/{total}")
     } else {
         format!("{total}")
@@ -103,30 +133,31 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         Style::default().fg(Color::DarkGray)
     };
 
-    let right_spans = vec![
+    let mut right_spans = in_flight_spans(app);
+    right_spans.extend([
+        super::connection_indicator(app),
+        Span::styled("│ ", Style::default().fg(Color::DarkGray)),
         Span::styled(count_text, count_style),
         Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
         Span::styled(format!("↓{down_speed}"), down_style),
         Span::raw(" "),
         Span::styled(format!("↑{up_speed}"), up_style),
         Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+    ]);
+    if let Some(limits) = app.speed_limits {
+        right_spans.extend(limit_spans(&limits));
+    }
+    right_spans.extend([
         Span::styled("D:", Style::default().fg(Color::DarkGray)),
         Span::raw(format!("{downloaded} ")),
         Span::styled("U:", Style::default().fg(Color::DarkGray)),
         Span::raw(format!("{uploaded} ")),
-    ];
+    ]);
 
     // Calculate widths
     let available_width = area.width.saturating_sub(2) as usize;
-    let left_len = keybinds
-        .iter()
-        .map(|s| s.content.chars().count())
-        .sum::<usize>()
-        + 1;
-    let right_len = right_spans
-        .iter()
-        .map(|s| s.content.chars().count())
-        .sum::<usize>();
+    let left_len = keybinds.iter().map(Span::width).sum::<usize>() + 1;
+    let right_len = right_spans.iter().map(Span::width).sum::<usize>();
 
     let mut spans = vec![Span::raw(" ")];
     spans.extend(keybinds);
@@ -152,3 +183,50 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 
     frame.render_widget(paragraph, area);
 }
+
+/// Spinner with the oldest action still waiting for the daemon and how many
+/// more are queued behind it.
+fn in_flight_spans(app: &App) -> Vec<Span<'static>> {
+    const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    let mut jobs = app.jobs.in_flight();
+    let Some(oldest) = jobs.next() else {
+        return Vec::new();
+    };
+    let frame = usize::try_from(oldest.started.elapsed().as_millis() / 100).unwrap_or_default()
+        % SPINNER.len();
+    let more = match jobs.count() {
+        0 => String::new(),
+        n => format!(" +{n}"),
+    };
+    vec![
+        Span::styled(
+            format!("{} {}…{more} ", SPINNER[frame], oldest.label),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled("│", Style::default().fg(Color::DarkGray)),
+    ]
+}
+
+/// Active global limits, with a turtle while alternative limits are on.
+fn limit_spans(limits: &SpeedLimits) -> Vec<Span<'static>> {
+    let (down, up) = limits.active();
+    if down.is_none() && up.is_none() {
+        return Vec::new();
+    }
+    let format_limit = |limit: Option<i32>| limit.map_or_else(|| "∞".into(), |v| v.to_string());
+    let style = if limits.alt_speed_enabled {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let mut spans = Vec::new();
+    if limits.alt_speed_enabled {
+        spans.push(Span::styled("🐢 ", style));
+    }
+    spans.push(Span::styled(
+        format!("≤↓{} ↑{} kB/s", format_limit(down), format_limit(up)),
+        style,
+    ));
+    spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+    spans
+}
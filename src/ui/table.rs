@@ -1,22 +1,28 @@
 use super::to_color;
 use crate::{
-    app::utils::Wrapper,
+    app::{sort::SortOrder, utils::Wrapper},
     config::{color::ColorConfig, time::TimeConfig},
 };
 use ratatui::{
-    layout::Constraint,
+    layout::{Constraint, Flex, Layout, Rect},
     style::{Color, Modifier, Style, Styled},
+    text::Span,
     widgets::{Block, BorderType, Borders, Row, Table},
 };
 use std::collections::HashSet;
 use transmission_rpc::types::{Torrent, TorrentGetField, TorrentStatus};
 
+const HIGHLIGHT_SYMBOL: &str = "▶ ";
+const COLUMN_SPACING: u16 = 1;
+
 pub fn build_table(
     torrents: &[&Torrent],
     selected: &HashSet<i64>,
     colors: &ColorConfig,
     time: &TimeConfig,
     fields: &[TorrentGetField],
+    sort: Option<SortOrder>,
+    stale: bool,
 ) -> Table<'static> {
     let select_style = select_style(colors);
     let header_style = header_style(colors);
@@ -24,7 +30,7 @@ pub fn build_table(
 
     let rows = torrents
         .iter()
-        .map(|t| make_row(t, fields, selected, select_style, colors, time))
+        .map(|t| make_row(t, fields, selected, select_style, colors, time, stale))
         .collect::<Vec<_>>();
 
     let widths = fields
@@ -32,7 +38,7 @@ pub fn build_table(
         .map(|&field| column_width(field))
         .collect::<Vec<_>>();
 
-    let header = Row::new(fields.iter().map(|&field| field.title()))
+    let header = Row::new(fields.iter().map(|&field| header_title(field, sort)))
         .style(header_style)
         .bottom_margin(1);
 
@@ -40,8 +46,47 @@ pub fn build_table(
         .block(default_block())
         .header(header)
         .row_highlight_style(highlight_row_style)
-        .highlight_symbol("▶ ")
-        .column_spacing(1)
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .column_spacing(COLUMN_SPACING)
+}
+
+/// Areas of the column headers and of the rows of a table from
+/// [`build_table`] rendered into `area`, laid out the way [`Table`] does.
+pub fn table_areas(area: Rect, fields: &[TorrentGetField], highlighted: bool) -> (Vec<Rect>, Rect) {
+    let inner = default_block().inner(area);
+    let header = Rect { height: 1, ..inner };
+    // The highlight symbol column is only reserved while a row is highlighted.
+    let selection_width = if highlighted {
+        Span::raw(HIGHLIGHT_SYMBOL).width()
+    } else {
+        0
+    };
+    let [_, columns] = Layout::horizontal([
+        Constraint::Length(u16::try_from(selection_width).unwrap_or_default()),
+        Constraint::Fill(0),
+    ])
+    .areas(header);
+    let headers = Layout::horizontal(fields.iter().map(|&field| column_width(field)))
+        .flex(Flex::Start)
+        .spacing(COLUMN_SPACING)
+        .split(columns)
+        .to_vec();
+    // One line for the header and one for its bottom margin.
+    let rows = Rect {
+        y: inner.y.saturating_add(2),
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+    (headers, rows)
+}
+
+fn header_title(field: TorrentGetField, sort: Option<SortOrder>) -> String {
+    match sort {
+        Some(sort) if sort.field == field => {
+            format!("{} {}", field.title(), sort.direction.indicator())
+        }
+        _ => field.title(),
+    }
 }
 
 fn default_block() -> Block<'static> {
@@ -62,20 +107,20 @@ fn column_width(field: TorrentGetField) -> Constraint {
     }
 }
 
-fn select_style(cfg: &ColorConfig) -> Style {
+pub fn select_style(cfg: &ColorConfig) -> Style {
     let fg = to_color(&cfg.selected_foreground);
     let bg = to_color(&cfg.selected_background);
     Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD)
 }
 
-fn header_style(cfg: &ColorConfig) -> Style {
+pub fn header_style(cfg: &ColorConfig) -> Style {
     let fg = to_color(&cfg.header_foreground);
     Style::default()
         .fg(fg)
         .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
 }
 
-fn highlighted_row_style(cfg: &ColorConfig) -> Style {
+pub fn highlighted_row_style(cfg: &ColorConfig) -> Style {
     let fg = to_color(&cfg.highlight_foreground);
     let bg = to_color(&cfg.highlight_background);
     Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD)
@@ -88,8 +133,13 @@ fn make_row(
     highlight: Style,
     colors: &ColorConfig,
     time: &TimeConfig,
+    stale: bool,
 ) -> Row<'static> {
-    let status_style = status_style(torrent.status, colors);
+    let status_style = if stale {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        status_style(torrent.status, colors)
+    };
 
     let cells = fields
         .iter()
@@ -123,8 +173,11 @@ fn status_style(status: Option<TorrentStatus>, colors: &ColorConfig) -> Style {
 
 #[cfg(test)]
 mod tests {
-    use super::column_width;
-    use crate::app::utils::Wrapper;
+    use super::{column_width, header_title};
+    use crate::app::{
+        sort::{SortDirection, SortOrder},
+        utils::Wrapper,
+    };
     use ratatui::layout::Constraint;
     use transmission_rpc::types::TorrentGetField;
 
@@ -156,4 +209,14 @@ mod tests {
             Constraint::Length(TorrentGetField::Status.width())
         );
     }
+
+    #[test]
+    fn sorted_column_header_shows_direction() {
+        let sort = SortOrder::new(TorrentGetField::UploadRatio, SortDirection::Descending);
+        assert_eq!(
+            header_title(TorrentGetField::UploadRatio, Some(sort)),
+            "Ratio ▼"
+        );
+        assert_eq!(header_title(TorrentGetField::Name, Some(sort)), "Name");
+    }
 }
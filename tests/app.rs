@@ -1,6 +1,16 @@
 use claims::assert_ok;
-use transmission_rpc::types::{Torrent, TorrentStatus};
-use traxor::{app::App, config::Config};
+use transmission_rpc::types::{
+    ErrorType, File, FileStat, Priority, Torrent, TorrentGetField, TorrentStatus,
+};
+use traxor::{
+    app::{
+        App, InputMode, Tab,
+        detail::{DetailPane, DetailView},
+        sort::{SortDirection, SortOrder},
+        utils::Wrapper,
+    },
+    config::{Config, tabs::TabConfig, time::TimeConfig},
+};
 
 #[test]
 fn app_creation() {
@@ -87,7 +97,7 @@ fn app_open_close_popup() {
 fn app_next_uses_filtered_torrents() {
     let config = assert_ok!(Config::load());
     let mut app = assert_ok!(App::new(config));
-    app.torrents.torrents = vec![torrent(1, "alpha"), torrent(2, "beta")];
+    app.torrents.torrents = vec![torrent(1, "alpha"), torrent(2, "beta")].into();
     app.filter_text = "beta".into();
     app.state.select(Some(0));
 
@@ -101,7 +111,7 @@ fn app_next_uses_filtered_torrents() {
 fn app_prepare_rename_action_uses_filtered_selection() {
     let config = assert_ok!(Config::load());
     let mut app = assert_ok!(App::new(config));
-    app.torrents.torrents = vec![torrent(1, "alpha"), torrent(2, "beta")];
+    app.torrents.torrents = vec![torrent(1, "alpha"), torrent(2, "beta")].into();
     app.filter_text = "beta".into();
     app.state.select(Some(0));
 
@@ -119,7 +129,8 @@ fn app_filtered_torrents_respects_active_tab_statuses() {
         torrent_with_status(2, "queued", TorrentStatus::QueuedToDownload),
         torrent_with_status(3, "seeding", TorrentStatus::Seeding),
         torrent_with_status(4, "stopped", TorrentStatus::Stopped),
-    ];
+    ]
+    .into();
 
     app.switch_tab(1);
     let downloading = app.filtered_torrents();
@@ -138,10 +149,261 @@ fn app_filtered_torrents_respects_active_tab_statuses() {
     assert_eq!(queued[0].name.as_deref(), Some("queued"));
 }
 
+#[test]
+fn app_filter_query_combines_structured_terms_and_fuzzy_words() {
+    let config = assert_ok!(Config::load());
+    let mut app = assert_ok!(App::new(config));
+    let mut show = torrent_with_status(1, "Show.S01E01.1080p", TorrentStatus::Seeding);
+    show.upload_ratio = Some(0.4);
+    show.labels = Some(vec!["TV".into()]);
+    let mut seeded = torrent_with_status(2, "Show.S01E02.1080p", TorrentStatus::Seeding);
+    seeded.upload_ratio = Some(2.0);
+    seeded.labels = Some(vec!["tv".into()]);
+    let mut movie = torrent_with_status(3, "Movie.1080p", TorrentStatus::Seeding);
+    movie.upload_ratio = Some(0.1);
+    app.torrents.torrents = vec![show, seeded, movie].into();
+
+    let names = |app: &App| {
+        app.filtered_torrents()
+            .iter()
+            .filter_map(|t| t.name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    app.filter_text = "status:seeding ratio<1 label:tv".into();
+    assert_eq!(names(&app), ["Show.S01E01.1080p"]);
+
+    app.filter_text = "ratio<1 1080p".into();
+    assert_eq!(names(&app).len(), 2);
+
+    app.filter_text = "-label:tv".into();
+    assert_eq!(names(&app), ["Movie.1080p"]);
+}
+
+#[test]
+fn app_invalid_filter_query_keeps_popup_open() {
+    let config = assert_ok!(Config::load());
+    let mut app = assert_ok!(App::new(config));
+    app.torrents.torrents = vec![torrent(1, "alpha"), torrent(2, "beta")].into();
+
+    app.start_filter();
+    app.input_handler.set_text("ratio<lots".into());
+    assert!(app.filter_query().is_err());
+    assert_eq!(app.filtered_torrents().len(), 2);
+
+    app.apply_filter();
+    assert_eq!(app.input_mode, InputMode::Filter);
+    assert!(app.filter_text.is_empty());
+}
+
+#[test]
+fn tab_matches_labels_trackers_and_saved_query() {
+    let tab = Tab::new(TabConfig {
+        name: "Private TV".into(),
+        columns: vec!["name".into()],
+        statuses: Vec::new(),
+        sort: None,
+        labels: vec!["tv".into(), "anime".into()],
+        trackers: vec!["tracker.example.org".into()],
+        download_dir: Some("/mnt/media".into()),
+        error: Some(false),
+        private: Some(true),
+        query: Some("ratio<1".into()),
+    });
+
+    let mut show = torrent(1, "show");
+    show.labels = Some(vec!["Anime".into()]);
+    show.tracker_list = Some("https://tracker.example.org/announce\n".into());
+    show.download_dir = Some("/mnt/media/anime".into());
+    show.error = Some(ErrorType::Ok);
+    show.is_private = Some(true);
+    show.upload_ratio = Some(0.5);
+    assert!(tab.matches(&show));
+
+    let mut public = show.clone();
+    public.is_private = Some(false);
+    assert!(!tab.matches(&public));
+
+    let mut errored = show.clone();
+    errored.error = Some(ErrorType::TrackerError);
+    assert!(!tab.matches(&errored));
+
+    let mut other_tracker = show.clone();
+    other_tracker.tracker_list = Some("https://open.example.net/announce".into());
+    assert!(!tab.matches(&other_tracker));
+
+    let mut seeded = show;
+    seeded.upload_ratio = Some(1.5);
+    assert!(!tab.matches(&seeded));
+}
+
+#[test]
+fn app_sorts_by_typed_values_with_missing_last() {
+    let config = assert_ok!(Config::load());
+    let mut app = assert_ok!(App::new(config));
+    let mut small = torrent(1, "small");
+    small.total_size = Some(900_000_000);
+    let mut large = torrent(2, "large");
+    large.total_size = Some(1_500_000_000);
+    let unknown = torrent(3, "unknown");
+    app.torrents.torrents = vec![unknown, large, small].into();
+
+    // Overview columns: status, progress, size, ...
+    app.cycle_sort();
+    app.cycle_sort();
+    app.cycle_sort();
+    assert_eq!(
+        app.tabs()[0].sort(),
+        Some(SortOrder::new(
+            TorrentGetField::TotalSize,
+            SortDirection::Ascending
+        ))
+    );
+    let names = |app: &App| {
+        app.filtered_torrents()
+            .iter()
+            .filter_map(|t| t.name.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&app), ["small", "large", "unknown"]);
+
+    app.reverse_sort();
+    assert_eq!(names(&app), ["large", "small", "unknown"]);
+}
+
+#[test]
+fn app_sort_cycles_back_to_daemon_order() {
+    let config = assert_ok!(Config::load());
+    let mut app = assert_ok!(App::new(config));
+    let columns = app.tabs()[0].fields().len();
+    for _ in 0..columns {
+        app.cycle_sort();
+        assert!(app.tabs()[0].sort().is_some());
+    }
+    app.cycle_sort();
+    assert_eq!(app.tabs()[0].sort(), None);
+}
+
+#[test]
+fn app_uses_configured_tab_sort() {
+    let config = assert_ok!(Config::load());
+    let app = assert_ok!(App::new(config));
+    assert_eq!(
+        app.tabs()[3].sort(),
+        Some(SortOrder::new(
+            TorrentGetField::AddedDate,
+            SortDirection::Descending
+        ))
+    );
+}
+
+#[test]
+fn app_requests_only_fields_the_view_needs() {
+    let config = assert_ok!(Config::load());
+    let mut app = assert_ok!(App::new(config));
+    let fields = app.required_fields();
+    for field in [
+        TorrentGetField::Id,
+        TorrentGetField::PercentDone,
+        TorrentGetField::RecheckProgress,
+    ] {
+        assert!(fields.contains(&field), "missing {field:?}");
+    }
+    for field in [
+        TorrentGetField::Pieces,
+        TorrentGetField::Files,
+        TorrentGetField::Peers,
+        TorrentGetField::PeersSendingToUs,
+    ] {
+        assert!(!fields.contains(&field), "unneeded {field:?}");
+    }
+    assert!(
+        fields
+            .iter()
+            .enumerate()
+            .all(|(idx, field)| !fields[..idx].contains(field))
+    );
+
+    app.filter_text = "seeds>1".into();
+    assert!(
+        app.required_fields()
+            .contains(&TorrentGetField::PeersSendingToUs)
+    );
+
+    app.switch_tab(2);
+    assert!(
+        app.required_fields()
+            .contains(&TorrentGetField::TrackerList)
+    );
+}
+
+#[test]
+fn detail_view_selects_files_by_pattern() {
+    let mut torrent = torrent(1, "season");
+    let names = [
+        "S01E01.mkv",
+        "S01E01.nfo",
+        "Sample/S01E01.mkv",
+        "S01E02.mkv",
+    ];
+    torrent.files = Some(
+        names
+            .iter()
+            .map(|name| File {
+                length: 100,
+                bytes_completed: 0,
+                name: (*name).into(),
+                begin_piece: None,
+                end_piece: None,
+            })
+            .collect(),
+    );
+    torrent.file_stats = Some(
+        names
+            .iter()
+            .map(|_| FileStat {
+                bytes_completed: 0,
+                wanted: true,
+                priority: Priority::Normal,
+            })
+            .collect(),
+    );
+
+    let mut detail = DetailView::new(1);
+    detail.set_torrent(Some(torrent));
+    detail.next_pane();
+    assert_eq!(detail.pane, DetailPane::Files);
+
+    assert_eq!(detail.target_files(), vec![0]);
+    assert_eq!(detail.select_matching_files("*.nfo sample"), 2);
+    assert_eq!(detail.target_files(), vec![1, 2]);
+    assert!(detail.all_wanted(&detail.target_files()));
+    assert_eq!(detail.next_priority(&detail.target_files()), Priority::High);
+
+    detail.toggle_all_files();
+    assert_eq!(detail.target_files(), vec![0, 1, 2, 3]);
+    detail.toggle_all_files();
+    assert_eq!(detail.target_files(), vec![0]);
+}
+
 fn torrent(id: i64, name: &str) -> Torrent {
     torrent_with_status(id, name, TorrentStatus::Stopped)
 }
 
+#[test]
+fn status_column_shows_recheck_progress_while_verifying() {
+    let time = TimeConfig::default();
+    let mut torrent = torrent_with_status(1, "a", TorrentStatus::Verifying);
+    torrent.recheck_progress = Some(0.425);
+    assert_eq!(
+        TorrentGetField::Status.value(&torrent, &time),
+        "Verifying 42%"
+    );
+
+    torrent.status = Some(TorrentStatus::Seeding);
+    assert_eq!(TorrentGetField::Status.value(&torrent, &time), "Seeding");
+}
+
 fn torrent_with_status(id: i64, name: &str, status: TorrentStatus) -> Torrent {
     Torrent {
         activity_date: None,
@@ -1,4 +1,8 @@
-use traxor::event::Event;
+use std::{
+    fs::File,
+    io::{self, IsTerminal},
+};
+use traxor::event::{Event, EventHandler};
 
 #[test]
 fn event_from_key_code() {
@@ -10,3 +14,18 @@ fn event_from_key_code() {
     );
     assert_eq!(Event::Key(key_event), Event::Key(key_event));
 }
+
+#[tokio::test]
+async fn event_handler_reports_missing_terminal_instead_of_panicking() {
+    // crossterm reads from stdin when it is a terminal and from /dev/tty
+    // otherwise; with neither (e.g. in CI) reading must fail with an error.
+    let has_terminal = io::stdin().is_terminal() || File::open("/dev/tty").is_ok();
+    let mut events = EventHandler::new(250);
+    let event = events.next().await;
+    if has_terminal {
+        // The first torrent update is due right away.
+        assert_eq!(event.ok(), Some(Event::Update));
+    } else {
+        assert!(event.is_err());
+    }
+}
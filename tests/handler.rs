@@ -1,6 +1,27 @@
 #![allow(clippy::unwrap_used)]
-use crossterm::event::{KeyCode, KeyEvent};
-use traxor::{app::App, app::InputMode, app::action::Action, config::Config, handler::get_action};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{Terminal, backend::TestBackend, layout::Rect};
+use serde_json::json;
+use std::time::Instant;
+use transmission_rpc::types::{Torrent, TorrentGetField};
+use traxor::{
+    app::App,
+    app::InputMode,
+    app::action::Action,
+    app::detail::DetailView,
+    app::health::ConnectionState,
+    app::notification::Severity,
+    app::settings::{DaemonSettings, SettingField, SettingsForm},
+    app::store::Delta,
+    app::trackers::TrackersForm,
+    app::types::{QueueMove, SessionStats, SpeedLimits},
+    app::utils::Wrapper,
+    app::worker::{Job, Outcome},
+    config::Config,
+    error::TraxorError,
+    handler::{get_action, get_mouse_action, handle_key_event, handle_mouse_event, update},
+    ui,
+};
 
 #[tokio::test]
 async fn get_action_quit() {
@@ -104,6 +125,26 @@ async fn get_action_torrent_actions() {
     );
 }
 
+#[tokio::test]
+async fn get_action_queue_actions() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    for (key, action) in [
+        ('n', Action::StartNow),
+        ('T', Action::Queue(QueueMove::Top)),
+        ('K', Action::Queue(QueueMove::Up)),
+        ('J', Action::Queue(QueueMove::Down)),
+        ('G', Action::Queue(QueueMove::Bottom)),
+    ] {
+        assert_eq!(
+            get_action(KeyEvent::from(KeyCode::Char(key)), &mut app)
+                .await
+                .unwrap(),
+            Some(action)
+        );
+    }
+}
+
 #[tokio::test]
 async fn get_action_unhandled() {
     let config = Config::load().unwrap();
@@ -152,3 +193,676 @@ async fn get_action_input_mode() {
         Some(Action::Cancel)
     );
 }
+
+#[tokio::test]
+async fn get_action_add_dialog_routes_input_to_focused_field() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    assert_eq!(
+        get_action(KeyEvent::from(KeyCode::Char('o')), &mut app)
+            .await
+            .unwrap(),
+        Some(Action::Add)
+    );
+    app.prepare_add_action();
+    for ch in "magnet:".chars() {
+        get_action(KeyEvent::from(KeyCode::Char(ch)), &mut app)
+            .await
+            .unwrap();
+    }
+    get_action(KeyEvent::from(KeyCode::Down), &mut app)
+        .await
+        .unwrap();
+    get_action(KeyEvent::from(KeyCode::Char('/')), &mut app)
+        .await
+        .unwrap();
+    assert_eq!(app.add_form.source.text, "magnet:");
+    assert_eq!(app.add_form.download_dir.text, "/");
+    assert!(app.input_handler.text.is_empty());
+}
+
+#[tokio::test]
+async fn get_action_limits_dialog_edits_fields() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    assert_eq!(
+        get_action(KeyEvent::from(KeyCode::Char('t')), &mut app)
+            .await
+            .unwrap(),
+        Some(Action::ToggleAltSpeed)
+    );
+    assert_eq!(
+        get_action(KeyEvent::from(KeyCode::Char('b')), &mut app)
+            .await
+            .unwrap(),
+        Some(Action::SessionLimits)
+    );
+    app.prepare_session_limits();
+    assert_eq!(app.input_mode, InputMode::Limits);
+    for ch in "500".chars() {
+        get_action(KeyEvent::from(KeyCode::Char(ch)), &mut app)
+            .await
+            .unwrap();
+    }
+    get_action(KeyEvent::from(KeyCode::Tab), &mut app)
+        .await
+        .unwrap();
+    get_action(KeyEvent::from(KeyCode::Char('x')), &mut app)
+        .await
+        .unwrap();
+    assert_eq!(app.limits_form.down.text, "500");
+    assert_eq!(app.limits_form.up.text, "x");
+
+    app.apply_limits().unwrap();
+    assert_eq!(app.input_mode, InputMode::Limits);
+    assert!(app.limits_form.error.is_some());
+}
+
+#[tokio::test]
+async fn get_action_detail_view_switches_panes() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    app.detail = Some(DetailView::new(1));
+    assert_eq!(
+        get_action(KeyEvent::from(KeyCode::Char('l')), &mut app)
+            .await
+            .unwrap(),
+        Some(Action::NextPane)
+    );
+    assert_eq!(
+        get_action(KeyEvent::from(KeyCode::Char('h')), &mut app)
+            .await
+            .unwrap(),
+        Some(Action::PrevPane)
+    );
+    assert_eq!(
+        get_action(KeyEvent::from(KeyCode::Esc), &mut app)
+            .await
+            .unwrap(),
+        Some(Action::CloseDetails)
+    );
+}
+
+#[tokio::test]
+async fn handle_key_event_reports_failed_actions() {
+    let mut config = Config::load().unwrap();
+    // Nothing listens on port 9 (discard), so every RPC call fails.
+    config.connection.url = "http://127.0.0.1:9/transmission/rpc".into();
+    let mut app = App::new(config).unwrap();
+
+    app.tick().unwrap();
+    assert!(app.jobs.polling());
+    // A poll due while another is in flight is not stacked on it.
+    app.jobs.mark_poll_due();
+    app.tick().unwrap();
+    app.settle().await;
+    assert_eq!(app.connection.state(), ConnectionState::Retrying);
+    assert_eq!(app.notifications.history().count(), 1);
+    // Backoff holds off the next poll.
+    app.tick().unwrap();
+    assert!(!app.jobs.polling());
+
+    // Mutating actions are skipped while disconnected.
+    handle_key_event(KeyEvent::from(KeyCode::Char('t')), &mut app).await;
+    let notification = app.notifications.history().next().unwrap();
+    assert_eq!(notification.severity, Severity::Warning);
+
+    // Actions run on the worker and are shown in flight until they fail.
+    app.connection.record_success();
+    handle_key_event(KeyEvent::from(KeyCode::Char('t')), &mut app).await;
+    let job = app.jobs.in_flight().next().unwrap();
+    assert_eq!(job.label, "Enabling alternative speed limits");
+    app.settle().await;
+    assert!(app.running);
+    assert_eq!(app.jobs.in_flight().count(), 0);
+    let notification = app.notifications.history().next().unwrap();
+    assert_eq!(notification.severity, Severity::Error);
+    // The daemon being unreachable counts against the connection.
+    assert_eq!(app.connection.state(), ConnectionState::Retrying);
+
+    handle_key_event(KeyEvent::from(KeyCode::Char('N')), &mut app).await;
+    assert!(app.show_notifications);
+    handle_key_event(KeyEvent::from(KeyCode::Esc), &mut app).await;
+    assert!(!app.show_notifications);
+}
+
+const fn click(area: Rect, modifiers: KeyModifiers) -> MouseEvent {
+    MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column: area.x + 1,
+        row: area.y,
+        modifiers,
+    }
+}
+
+#[test]
+fn mouse_clicks_switch_tabs_highlight_select_and_sort() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    app.torrents.torrents = (1..=3)
+        .map(|id| serde_json::from_value(json!({ "id": id, "name": format!("t{id}") })).unwrap())
+        .collect();
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+
+    handle_mouse_event(click(app.click_areas.tabs[1], KeyModifiers::NONE), &mut app);
+    assert_eq!(app.index(), 1);
+    handle_mouse_event(click(app.click_areas.tabs[0], KeyModifiers::NONE), &mut app);
+    assert_eq!(app.index(), 0);
+
+    app.detail = Some(DetailView::new(1));
+    update(&mut app, Action::SwitchTab(1)).unwrap();
+    assert!(app.detail.is_some());
+    handle_mouse_event(click(app.click_areas.tabs[0], KeyModifiers::NONE), &mut app);
+    assert!(app.detail.is_none());
+    assert_eq!(app.index(), 0);
+
+    let rows = app.click_areas.rows;
+    handle_mouse_event(click(rows, KeyModifiers::NONE), &mut app);
+    assert_eq!(app.state.selected(), Some(0));
+    let second = Rect {
+        y: rows.y + 1,
+        ..rows
+    };
+    handle_mouse_event(click(second, KeyModifiers::CONTROL), &mut app);
+    assert_eq!(app.state.selected(), Some(1));
+    assert!(app.torrents.selected.contains(&2));
+
+    let scroll = MouseEvent {
+        kind: MouseEventKind::ScrollDown,
+        ..click(rows, KeyModifiers::NONE)
+    };
+    handle_mouse_event(scroll, &mut app);
+    assert_eq!(app.state.selected(), Some(2));
+
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let header = app.click_areas.headers[0];
+    let field = app.tabs()[0].fields()[0];
+    let title = field.title();
+    let buffer = terminal.backend().buffer();
+    let rendered: String = (header.x..header.x + u16::try_from(title.len()).unwrap())
+        .map(|x| buffer[(x, header.y)].symbol())
+        .collect();
+    assert_eq!(rendered, title);
+    assert_eq!(
+        get_mouse_action(click(header, KeyModifiers::NONE), &app),
+        Some(Action::SortBy(0))
+    );
+    handle_mouse_event(click(header, KeyModifiers::NONE), &mut app);
+    assert_eq!(app.tabs()[0].sort().map(|sort| sort.field), Some(field));
+}
+
+#[test]
+fn mouse_click_outside_popup_closes_it() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    app.open_help();
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let popup = app.click_areas.popup.unwrap();
+
+    // Clicks inside the popup are ignored, even over a tab title.
+    handle_mouse_event(click(popup, KeyModifiers::NONE), &mut app);
+    assert!(app.show_help);
+    let outside = Rect::new(0, popup.bottom(), 1, 1);
+    handle_mouse_event(click(outside, KeyModifiers::NONE), &mut app);
+    assert!(!app.show_help);
+}
+
+#[tokio::test]
+async fn graphs_key_toggles_speed_history_panel() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    let torrent = json!({ "id": 1, "name": "t1", "rateDownload": 2048, "rateUpload": 0 });
+    app.torrents.torrents = vec![serde_json::from_value(torrent).unwrap()].into();
+    app.history.record(&app.torrents.torrents);
+    app.state.select(Some(0));
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let screen = |terminal: &Terminal<TestBackend>| -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    };
+
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    assert!(!screen(&terminal).contains("Session"));
+
+    handle_key_event(KeyEvent::from(KeyCode::Char('g')), &mut app).await;
+    assert!(app.history.visible);
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let screen = screen(&terminal);
+    assert!(screen.contains("Session"));
+    assert!(screen.contains(" t1 "));
+    assert!(screen.contains("↓ 2.00 KB/s"));
+}
+
+#[tokio::test]
+async fn session_stats_popup_shows_totals_and_closes_on_escape() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let screen = |terminal: &Terminal<TestBackend>| -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    };
+
+    handle_key_event(KeyEvent::from(KeyCode::Char('I')), &mut app).await;
+    assert!(app.stats.visible);
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    assert!(screen(&terminal).contains("Loading…"));
+
+    let period = |bytes: i64, sessions: i32| {
+        json!({
+            "downloadedBytes": bytes,
+            "uploadedBytes": bytes * 2,
+            "filesAdded": 3,
+            "secondsActive": 3600,
+            "sessionCount": sessions,
+        })
+    };
+    let totals = serde_json::from_value(json!({
+        "torrentCount": 2,
+        "activeTorrentCount": 1,
+        "pausedTorrentCount": 1,
+        "downloadSpeed": 0,
+        "uploadSpeed": 0,
+        "current-stats": period(1024, 1),
+        "cumulative-stats": period(1024 * 1024, 7),
+    }))
+    .unwrap();
+    let stats = SessionStats {
+        totals,
+        download_dir: "/downloads".into(),
+        free_space: 5 * 1024 * 1024 * 1024,
+    };
+    app.stats.set(Ok(stats), Instant::now());
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let shown = screen(&terminal);
+    assert!(shown.contains("All time"));
+    assert!(shown.contains("1.00 MB"));
+    assert!(shown.contains("5.00 GB"));
+    assert!(shown.contains("/downloads"));
+
+    handle_key_event(KeyEvent::from(KeyCode::Esc), &mut app).await;
+    assert!(!app.stats.visible);
+}
+
+#[tokio::test]
+async fn settings_screen_previews_changes_before_applying() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    let screen = |terminal: &Terminal<TestBackend>| -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    };
+    app.input_mode = InputMode::Settings;
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    assert!(screen(&terminal).contains("Loading settings…"));
+
+    let serde_json::Value::Object(response) = json!({
+        "download-dir": "/downloads",
+        "peer-port": 51413,
+        "dht-enabled": true,
+    }) else {
+        unreachable!();
+    };
+    app.settings_form = Some(SettingsForm::new(DaemonSettings::from_response(&response)));
+    let key = |code| KeyEvent::from(code);
+
+    handle_key_event(key(KeyCode::Down), &mut app).await;
+    handle_key_event(key(KeyCode::Backspace), &mut app).await;
+    handle_key_event(key(KeyCode::Char('0')), &mut app).await;
+    handle_key_event(key(KeyCode::Down), &mut app).await;
+    handle_key_event(key(KeyCode::Char(' ')), &mut app).await;
+    handle_key_event(key(KeyCode::Enter), &mut app).await;
+    let form = app.settings_form.as_ref().unwrap();
+    assert_eq!(form.preview.as_ref().map(Vec::len), Some(2));
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let shown = screen(&terminal);
+    assert!(shown.contains("Apply 2 changes?"));
+    assert!(shown.contains("51413 → 51410"));
+    assert!(shown.contains("on → off"));
+
+    // Esc returns to editing instead of discarding the changes.
+    handle_key_event(key(KeyCode::Esc), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::Settings);
+    let form = app.settings_form.as_ref().unwrap();
+    assert!(form.preview.is_none());
+    assert_eq!(form.display(SettingField::PeerPort), "51410");
+
+    handle_key_event(key(KeyCode::Enter), &mut app).await;
+    handle_key_event(key(KeyCode::Enter), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::None);
+    assert!(app.settings_form.is_none());
+    let job = app.jobs.in_flight().next().map(|job| job.label.clone());
+    assert_eq!(job.as_deref(), Some("Applying 2 settings"));
+}
+
+#[tokio::test]
+async fn settings_screen_rejects_invalid_port() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    let serde_json::Value::Object(response) = json!({ "peer-port": 51413 }) else {
+        unreachable!();
+    };
+    app.input_mode = InputMode::Settings;
+    app.settings_form = Some(SettingsForm::new(DaemonSettings::from_response(&response)));
+    for _ in 0..5 {
+        handle_key_event(KeyEvent::from(KeyCode::Backspace), &mut app).await;
+    }
+    handle_key_event(KeyEvent::from(KeyCode::Char('0')), &mut app).await;
+    handle_key_event(KeyEvent::from(KeyCode::Enter), &mut app).await;
+    let form = app.settings_form.as_ref().unwrap();
+    assert!(form.preview.is_none());
+    assert!(form.error.as_deref().unwrap().starts_with("Peer port"));
+}
+
+#[tokio::test]
+async fn labels_popup_edits_selection_and_sidebar_filters_by_label() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    app.torrents.torrents = [(1, vec!["tv"]), (2, vec!["tv", "hd"]), (3, vec![])]
+        .into_iter()
+        .map(|(id, labels)| {
+            serde_json::from_value(json!({ "id": id, "name": format!("t{id}"), "labels": labels }))
+                .unwrap()
+        })
+        .collect();
+    app.torrents.selected.extend([1, 2]);
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let screen = |terminal: &Terminal<TestBackend>| -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    };
+    let key = |code| KeyEvent::from(code);
+
+    handle_key_event(key(KeyCode::Char('e')), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::Labels);
+    for ch in ", h".chars() {
+        handle_key_event(key(KeyCode::Char(ch)), &mut app).await;
+    }
+    handle_key_event(key(KeyCode::Tab), &mut app).await;
+    let form = app.labels_form.as_ref().unwrap();
+    assert_eq!(form.input.text, "tv, hd");
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let shown = screen(&terminal);
+    assert!(shown.contains("Labels of 2 torrents"));
+    assert!(shown.contains("+hd"));
+
+    handle_key_event(key(KeyCode::Enter), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::None);
+    let job = app.jobs.in_flight().next().map(|job| job.label.clone());
+    assert_eq!(job.as_deref(), Some("Labeling 1 torrent"));
+
+    handle_key_event(key(KeyCode::Char('L')), &mut app).await;
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    assert!(screen(&terminal).contains("hd (1)"));
+    handle_key_event(key(KeyCode::Char(']')), &mut app).await;
+    let ids = |app: &App| -> Vec<i64> {
+        app.filtered_torrents()
+            .iter()
+            .filter_map(|t| t.id)
+            .collect()
+    };
+    assert_eq!(ids(&app), [2]);
+
+    let unlabeled = Rect {
+        y: app.click_areas.labels.y + 3,
+        ..app.click_areas.labels
+    };
+    handle_mouse_event(click(unlabeled, KeyModifiers::NONE), &mut app);
+    assert_eq!(ids(&app), [3]);
+
+    handle_key_event(key(KeyCode::Char('L')), &mut app).await;
+    assert_eq!(ids(&app).len(), 3);
+}
+
+#[tokio::test]
+async fn tracker_dialog_previews_host_replacement_across_selection() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    let torrent = |id: i64, announce: &str| -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": format!("t{id}"),
+            "trackers": [{ "id": 0, "announce": announce, "scrape": "", "tier": 0 }],
+        })
+    };
+    let torrents = vec![
+        torrent(1, "https://old.example/announce?key=1"),
+        torrent(2, "https://old.example/announce?key=2"),
+        torrent(3, "udp://other.example:80"),
+    ];
+    app.torrents.torrents = torrents
+        .iter()
+        .map(|t| serde_json::from_value(t.clone()).unwrap())
+        .collect();
+    app.torrents.selected.extend([1, 2, 3]);
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let screen = |terminal: &Terminal<TestBackend>| -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    };
+    let key = |code| KeyEvent::from(code);
+
+    handle_key_event(key(KeyCode::Char('u')), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::Trackers);
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    assert!(screen(&terminal).contains("Loading trackers…"));
+
+    app.trackers_form = Some(TrackersForm::new(
+        torrents
+            .into_iter()
+            .map(|t| serde_json::from_value(t).unwrap())
+            .collect(),
+    ));
+    handle_key_event(key(KeyCode::Down), &mut app).await;
+    for ch in "old.example".chars() {
+        handle_key_event(key(KeyCode::Char(ch)), &mut app).await;
+    }
+    handle_key_event(key(KeyCode::Down), &mut app).await;
+    for ch in "new.example".chars() {
+        handle_key_event(key(KeyCode::Char(ch)), &mut app).await;
+    }
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let shown = screen(&terminal);
+    assert!(shown.contains("Trackers of 3 torrents"));
+    assert!(shown.contains("2 torrents affected"));
+    assert!(
+        shown.contains("https://old.example/announce?key=2 → https://new.example/announce?key=2")
+    );
+
+    handle_key_event(key(KeyCode::Enter), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::None);
+    assert!(app.trackers_form.is_none());
+    let job = app.jobs.in_flight().last().map(|job| job.label.clone());
+    assert_eq!(job.as_deref(), Some("Updating trackers of 2 torrents"));
+}
+
+#[tokio::test]
+async fn options_dialog_sets_seeding_limits_of_selection() {
+    let config = Config::load().unwrap();
+    let mut app = App::new(config).unwrap();
+    let torrents: Vec<Torrent> = [(1, 2.0), (2, 1.5)]
+        .into_iter()
+        .map(|(id, ratio)| {
+            serde_json::from_value(json!({
+                "id": id,
+                "name": format!("t{id}"),
+                "seedRatioMode": 1,
+                "seedRatioLimit": ratio,
+                "seedIdleMode": 0,
+                "seedIdleLimit": 30,
+                "bandwidthPriority": id - 1,
+            }))
+            .unwrap()
+        })
+        .collect();
+    app.torrents.torrents = torrents
+        .iter()
+        .map(|t| serde_json::from_value(json!({ "id": t.id, "name": t.name })).unwrap())
+        .collect();
+    app.torrents.selected.extend([1, 2]);
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let screen = |terminal: &Terminal<TestBackend>| -> String {
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect()
+    };
+    let key = |code| KeyEvent::from(code);
+
+    // The seeding options are not polled; they are loaded for the dialog.
+    handle_key_event(key(KeyCode::Char('O')), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::Options);
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    assert!(screen(&terminal).contains("Loading options…"));
+    let job = app.jobs.in_flight().next().cloned().unwrap();
+    assert_eq!(job.label, "Loading options");
+    app.finish(&job, Ok(Outcome::Options(torrents)));
+    terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+    let shown = screen(&terminal);
+    assert!(shown.contains("Seeding options of 2 torrents"));
+    assert!(shown.contains("(mixed)"));
+    assert!(shown.contains("< mixed >"));
+
+    // The differing ratio limits must be replaced while stopping at a ratio.
+    handle_key_event(key(KeyCode::Enter), &mut app).await;
+    let form = app.options_form.as_ref().unwrap();
+    assert_eq!(
+        form.error.as_deref(),
+        Some("Enter the ratio limit to stop at")
+    );
+
+    handle_key_event(key(KeyCode::Down), &mut app).await;
+    handle_key_event(key(KeyCode::Char('3')), &mut app).await;
+    for _ in 0..3 {
+        handle_key_event(key(KeyCode::Down), &mut app).await;
+    }
+    handle_key_event(key(KeyCode::Right), &mut app).await;
+    let form = app.options_form.as_ref().unwrap();
+    assert_eq!(form.ratio_limit.text, "3");
+    assert!(form.priority.is_some());
+
+    handle_key_event(key(KeyCode::Enter), &mut app).await;
+    assert_eq!(app.input_mode, InputMode::None);
+    assert!(app.options_form.is_none());
+    let job = app.jobs.in_flight().last().map(|job| job.label.clone());
+    assert_eq!(job.as_deref(), Some("Setting options of 2 torrents"));
+}
+
+#[tokio::test]
+async fn rules_act_on_matching_torrents_after_each_poll() {
+    let mut config = Config::load().unwrap();
+    let audit_log = std::env::temp_dir().join(format!("traxor-audit-{}.log", std::process::id()));
+    config.automation.audit_log = Some(audit_log.clone());
+    config.rules = vec![
+        toml::from_str(
+            r#"
+            name = "Ratio reached"
+            statuses = ["seeding"]
+            min_ratio = 2.0
+            action = "stop"
+            "#,
+        )
+        .unwrap(),
+        toml::from_str(
+            r#"
+            name = "Errored"
+            error = true
+            action = "remove-with-data"
+            dry_run = true
+            "#,
+        )
+        .unwrap(),
+    ];
+    let mut app = App::new(config).unwrap();
+    assert!(
+        app.required_fields()
+            .contains(&TorrentGetField::UploadRatio)
+    );
+
+    let poll = |ratio: f64| Outcome::Polled {
+        limits: SpeedLimits::default(),
+        delta: Delta::Full(vec![
+            serde_json::from_value(json!({
+                "id": 1, "name": "seeded", "status": 6, "uploadRatio": ratio,
+            }))
+            .unwrap(),
+            serde_json::from_value(json!({
+                "id": 2, "name": "broken", "status": 0, "error": 3,
+            }))
+            .unwrap(),
+        ]),
+        detail: None,
+        stats: None,
+    };
+    let job = Job {
+        // Clear of the ids the worker hands out.
+        id: u64::MAX,
+        label: "Updating torrents".into(),
+        background: true,
+        started: Instant::now(),
+    };
+    app.finish(&job, Ok(poll(2.5)));
+    let jobs: Vec<String> = app.jobs.in_flight().map(|job| job.label.clone()).collect();
+    assert_eq!(jobs, ["Stopping 1 torrent"]);
+    let messages: Vec<String> = app
+        .notifications
+        .history()
+        .map(|n| n.message.clone())
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "Rule \"Errored\" would remove with data: broken",
+            "Rule \"Ratio reached\": stop: seeded",
+        ]
+    );
+
+    // Nothing fires again while the torrents keep matching.
+    app.finish(&job, Ok(poll(2.6)));
+    assert_eq!(app.notifications.history().count(), 2);
+
+    // Dry runs are logged right away, actions once their outcome is known.
+    let log = std::fs::read_to_string(&audit_log).unwrap();
+    assert_eq!(log.lines().count(), 1);
+    assert!(log.contains("[dry run] rule \"Errored\": remove with data: broken (#2)"));
+    let stop = app.jobs.in_flight().next().unwrap().clone();
+    let refused = TraxorError::TransmissionRpc("torrent is busy".into());
+    app.finish(&stop, Err(refused));
+    let log = std::fs::read_to_string(&audit_log).unwrap();
+    let _ = std::fs::remove_file(&audit_log);
+    assert_eq!(log.lines().count(), 2);
+    assert!(log.contains(
+        "rule \"Ratio reached\": stop: seeded (#1) failed: Transmission RPC error: torrent is busy"
+    ));
+}